@@ -21,6 +21,7 @@ use crate::endpoints::udf::{udf_config_t, udf_history_t, udf_symbols_t};
 use crate::endpoints::udf::{udf_search_t, udf_symbol_info_t};
 use crate::endpoints::udf::udf_error_t::{Status, UdfError};
 use crate::udf_config_t::{Exchange, SymbolsType};
+use crate::metrics::METRICS;
 
 //region PARAMS
 
@@ -30,6 +31,7 @@ use crate::udf_config_t::{Exchange, SymbolsType};
 pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
 {
     let psql_pool = create_psql_pool_diesel();
+    METRICS.register_diesel_pool("default", &psql_pool);
     let home = warp::path!("info")
         .and(warp::get())
         .and(warp::path::end())
@@ -0,0 +1,109 @@
+use chrono::NaiveDate;
+use deadpool_postgres::Pool;
+use postgres::Row;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use warp::{Filter, Rejection, Reply};
+
+use database_psql::connection::create_psql_raw_pool;
+
+use crate::endpoints::responses::response_error::ResponseError;
+use crate::endpoints::responses::response_trade::create_response;
+use crate::error::ApiError;
+use crate::helper::with_raw_psql_store;
+use crate::metrics::METRICS;
+
+//region PARAMS
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeeSummary {
+    #[schema(value_type = String)]
+    day: NaiveDate,
+    currency_mint: String,
+    asset_mint: String,
+    symbol: String,
+    trades: i64,
+    fees: f64,
+    fees_exact: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeeSummaryParams {
+    #[param(style = Form, example = "1659164001")]
+    from: i64,
+    to: Option<i64>,
+    #[param(style = Form, example = "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx")]
+    currency_mint: Option<String>,
+    asset_mint: Option<String>,
+}
+
+//endregion
+
+//region HANDLERS
+pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
+{
+    let psql_raw_pool = create_psql_raw_pool();
+    METRICS.register_raw_pool("fees_raw", &psql_raw_pool);
+
+    let summary = warp::path!("fees" / "summary")
+        .and(warp::get())
+        .and(warp::path::end())
+        .and(with_raw_psql_store(psql_raw_pool.clone()))
+        .and(warp::query::<FeeSummaryParams>())
+        .and_then(get_summary);
+
+    summary
+}
+
+//endregion
+
+/// Protocol fee revenue
+///
+/// Responses with the collected protocol fees grouped by day, currency and asset.
+#[utoipa::path(
+get,
+path = "/fees/summary",
+params(FeeSummaryParams),
+responses(
+(status = 200, description = "Response: Fee summary successful", body = [FeeSummary]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
+)
+)]
+pub async fn get_summary(
+    pool: Pool,
+    query: FeeSummaryParams,
+) -> Result<impl Reply, Rejection> {
+    let db = pool.get().await.map_err(ApiError::from)?;
+
+    let data: Vec<Row> = db.query("SELECT date(timestamp_ts)          AS day,
+                                          currency_mint,
+                                          asset_mint,
+                                          symbol,
+                                          count(*)                   AS trades,
+                                          sum(market_fee)::float8    AS fees,
+                                          sum(market_fee)::text      AS fees_exact
+                                   FROM trades
+                                   WHERE timestamp >= $1
+                                     AND timestamp < $2
+                                     AND ($3::text IS NULL OR currency_mint = $3)
+                                     AND ($4::text IS NULL OR asset_mint = $4)
+                                   GROUP BY day, currency_mint, asset_mint, symbol
+                                   ORDER BY day ASC, currency_mint, asset_mint",
+                                  &[&query.from, &query.to.unwrap_or(i64::MAX), &query.currency_mint, &query.asset_mint],
+    ).await.map_err(ApiError::from)?;
+
+    let fee_summary: Vec<FeeSummary> = data.into_iter().map(|d| FeeSummary {
+        day: d.get("day"),
+        currency_mint: d.get("currency_mint"),
+        asset_mint: d.get("asset_mint"),
+        symbol: d.get("symbol"),
+        trades: d.get("trades"),
+        fees: d.get("fees"),
+        fees_exact: d.get("fees_exact"),
+    }).collect();
+
+    create_response(&fee_summary)
+}
@@ -0,0 +1,178 @@
+use std::convert::Infallible;
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use log::warn;
+use serde::Serialize;
+use utoipa::ToSchema;
+use warp::{Filter, hyper::StatusCode, Reply};
+
+use staratlas::symbolstore::SharedSymbolStore;
+
+use crate::helper::{with_psql_store, with_raw_psql_store};
+
+const DEFAULT_MAX_TRADE_AGE_MINS: i64 = 30;
+
+//region PARAMS
+#[derive(Debug, Serialize, ToSchema, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Unavailable,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthCheck {
+    name: String,
+    status: HealthStatus,
+    detail: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthReport {
+    status: HealthStatus,
+    checks: Vec<HealthCheck>,
+}
+
+impl HealthCheck {
+    fn new(name: &str, status: HealthStatus, detail: String) -> HealthCheck {
+        HealthCheck { name: name.to_string(), status, detail }
+    }
+}
+//endregion
+
+//region HANDLERS
+/// Checks the pools the trade handlers are served from, so readiness reflects the connections requests use.
+pub async fn handlers(store: SharedSymbolStore, psql_pool: Pool<ConnectionManager<PgConnection>>, psql_raw_pool: deadpool_postgres::Pool) -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
+{
+    let max_trade_age_mins = max_trade_age_mins();
+
+    let live = warp::path!("health" / "live")
+        .and(warp::get())
+        .and(warp::path::end())
+        .and_then(get_live);
+
+    let ready = warp::path!("health" / "ready")
+        .and(warp::get())
+        .and(warp::path::end())
+        .and(with_psql_store(psql_pool.clone()))
+        .and(with_raw_psql_store(psql_raw_pool.clone()))
+        .and(warp::any().map(move || store.clone()))
+        .and(warp::any().map(move || max_trade_age_mins))
+        .and_then(get_ready);
+
+    live.or(ready)
+}
+//endregion
+
+/// Liveness
+///
+/// Responses with ok as long as the process serves requests.
+#[utoipa::path(
+get,
+path = "/health/live",
+responses(
+(status = 200, description = "Process is alive", body = HealthReport)
+)
+)]
+pub async fn get_live() -> Result<impl Reply, Infallible> {
+    Ok(warp::reply::json(&HealthReport { status: HealthStatus::Ok, checks: vec![] }))
+}
+
+/// Readiness
+///
+/// Checks both database pools, the symbol store and the age of the last trade. A trade older than
+/// `HEALTH_MAX_TRADE_AGE_MINS` (default 30) or no trade at all degrades the status, an unavailable dependency
+/// responds with 503.
+#[utoipa::path(
+get,
+path = "/health/ready",
+responses(
+(status = 200, description = "Ready, status ok or degraded", body = HealthReport),
+(status = 503, description = "A dependency is unavailable", body = HealthReport)
+)
+)]
+pub async fn get_ready(
+    db_pool: Pool<ConnectionManager<PgConnection>>,
+    raw_db_pool: deadpool_postgres::Pool,
+    store: SharedSymbolStore,
+    max_trade_age_mins: i64,
+) -> Result<impl Reply, Infallible> {
+    let mut checks = vec![
+        check_diesel_pool(&db_pool),
+        check_symbol_store(&store),
+    ];
+    let (raw_check, last_trade) = check_raw_pool(&raw_db_pool).await;
+    checks.push(raw_check);
+    if let Some(last_trade) = last_trade {
+        checks.push(check_freshness(last_trade, max_trade_age_mins));
+    }
+
+    let status = checks.iter().map(|check| check.status).max().unwrap_or(HealthStatus::Ok);
+    let code = if status == HealthStatus::Unavailable { StatusCode::SERVICE_UNAVAILABLE } else { StatusCode::OK };
+    Ok(warp::reply::with_status(warp::reply::json(&HealthReport { status, checks }), code))
+}
+
+fn check_diesel_pool(db_pool: &Pool<ConnectionManager<PgConnection>>) -> HealthCheck {
+    use diesel::prelude::*;
+
+    let result = db_pool.get()
+        .map_err(|err| err.to_string())
+        .and_then(|mut db| diesel::sql_query("SELECT 1").execute(&mut db).map_err(|err| err.to_string()));
+    match result {
+        Ok(_) => HealthCheck::new("postgres", HealthStatus::Ok, "connected".to_string()),
+        Err(err) => HealthCheck::new("postgres", HealthStatus::Unavailable, err),
+    }
+}
+
+/// Checks the raw pool with the query of the last trade timestamp, `None` if the query failed and `Some(None)` if
+/// there are no trades.
+async fn check_raw_pool(raw_db_pool: &deadpool_postgres::Pool) -> (HealthCheck, Option<Option<i64>>) {
+    let db = match raw_db_pool.get().await {
+        Ok(db) => db,
+        Err(err) => return (HealthCheck::new("postgres_raw", HealthStatus::Unavailable, err.to_string()), None),
+    };
+    match db.query_one("SELECT MAX(timestamp) AS last_trade FROM trades", &[]).await {
+        Ok(row) => (HealthCheck::new("postgres_raw", HealthStatus::Ok, "connected".to_string()), Some(row.get("last_trade"))),
+        Err(err) => (HealthCheck::new("postgres_raw", HealthStatus::Unavailable, err.to_string()), None),
+    }
+}
+
+fn check_symbol_store(store: &SharedSymbolStore) -> HealthCheck {
+    let assets = match store.read() {
+        Ok(store) => store.assets.len(),
+        Err(_) => return HealthCheck::new("symbol_store", HealthStatus::Unavailable, "symbol store poisoned".to_string()),
+    };
+    if assets == 0 {
+        HealthCheck::new("symbol_store", HealthStatus::Unavailable, "no assets loaded".to_string())
+    } else {
+        HealthCheck::new("symbol_store", HealthStatus::Ok, format!("{} assets", assets))
+    }
+}
+
+/// Degraded if the last trade is older than `max_trade_age_mins` or no trade is stored.
+fn check_freshness(last_trade: Option<i64>, max_trade_age_mins: i64) -> HealthCheck {
+    let last_trade = match last_trade {
+        Some(last_trade) => last_trade,
+        None => return HealthCheck::new("freshness", HealthStatus::Degraded, "no trades stored".to_string()),
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+    let age_mins = (now - last_trade) / 60;
+    let status = if age_mins > max_trade_age_mins { HealthStatus::Degraded } else { HealthStatus::Ok };
+    HealthCheck::new("freshness", status, format!("last trade {} minutes ago", age_mins))
+}
+
+/// `HEALTH_MAX_TRADE_AGE_MINS`, the default of 30 minutes if unset or invalid.
+fn max_trade_age_mins() -> i64 {
+    let value = match env::var("HEALTH_MAX_TRADE_AGE_MINS") {
+        Ok(value) => value,
+        Err(_) => return DEFAULT_MAX_TRADE_AGE_MINS,
+    };
+    value.parse::<i64>().unwrap_or_else(|err| {
+        warn!("Invalid HEALTH_MAX_TRADE_AGE_MINS {:?}, using {}: {}", value, DEFAULT_MAX_TRADE_AGE_MINS, err);
+        DEFAULT_MAX_TRADE_AGE_MINS
+    })
+}
@@ -2,6 +2,9 @@ pub mod default;
 pub mod udf;
 pub mod trades;
 pub mod stats;
-mod responses;
+pub mod fees;
+pub mod health;
+pub mod symbols;
+pub mod responses;
 
 
@@ -1,8 +1,21 @@
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
+use warp::hyper::StatusCode;
 
+/// Body of every error response, `status` repeats the HTTP status.
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct ResponseError {
     pub(crate) s: i64,
     pub(crate) errmsg: String,
-}
\ No newline at end of file
+    pub(crate) status: u16,
+}
+
+impl ResponseError {
+    pub fn new(status: StatusCode, errmsg: &str) -> ResponseError {
+        ResponseError {
+            s: 1,
+            errmsg: errmsg.to_string(),
+            status: status.as_u16(),
+        }
+    }
+}
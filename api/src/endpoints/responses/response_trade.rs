@@ -1,21 +1,98 @@
-use std::convert::Infallible;
-
-use log::warn;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::NaiveDateTime;
 use serde::Serialize;
-use warp::Reply;
+use utoipa::ToSchema;
+use warp::{Rejection, Reply};
 
 use database_psql::model::Trade;
 
-use crate::endpoints::responses::response_error::ResponseError;
+use crate::error::ApiError;
 
-pub fn create_response<T: Serialize>(cursor_db: &Vec<T>) -> Result<impl Reply, Infallible> {
+/// Json of the rows, an empty result is rejected as not found.
+pub fn create_response<T: Serialize>(cursor_db: &Vec<T>) -> Result<impl Reply, Rejection> {
     return if cursor_db.is_empty() {
-        warn!("There seems to be no data...");
-        Ok(warp::reply::json(&ResponseError {
-            s: 1,
-            errmsg: "No data found".to_string(),
-        }))
+        Err(ApiError::no_data().into())
     } else {
         Ok(warp::reply::json(&cursor_db))
     };
 }
+
+/// Trade as returned by the API.
+///
+/// Amounts are stored as exact NUMERIC values, every amount is offered as float for charting and as
+/// exact decimal string (`*_exact`) for anything that needs to add them up.
+#[derive(Debug, Serialize, ToSchema, Clone)]
+pub struct ResponseTrade {
+    pub pk: String,
+    pub signature: String,
+    pub symbol: String,
+    pub block: i64,
+    pub timestamp: i64,
+    pub timestamp_ts: NaiveDateTime,
+    pub order_taker: String,
+    pub order_initializer: String,
+    pub currency_mint: String,
+    pub asset_mint: String,
+    pub asset_receiving_wallet: String,
+    pub asset_change: f64,
+    pub asset_change_exact: String,
+    pub currency_change: f64,
+    pub currency_change_exact: String,
+    pub market_fee: f64,
+    pub market_fee_exact: String,
+    pub total_cost: f64,
+    pub total_cost_exact: String,
+    pub price: f64,
+    pub price_exact: String,
+    pub fee_payer: String,
+    pub fee_rate: f64,
+    pub fee_rate_exact: String,
+    pub royalty: f64,
+    pub royalty_exact: String,
+    pub royalty_discount: f64,
+    pub royalty_discount_exact: String,
+}
+
+impl From<Trade> for ResponseTrade {
+    fn from(trade: Trade) -> Self {
+        ResponseTrade {
+            pk: trade.pk,
+            signature: trade.signature,
+            symbol: trade.symbol,
+            block: trade.block,
+            timestamp: trade.timestamp,
+            timestamp_ts: trade.timestamp_ts,
+            order_taker: trade.order_taker,
+            order_initializer: trade.order_initializer,
+            currency_mint: trade.currency_mint,
+            asset_mint: trade.asset_mint,
+            asset_receiving_wallet: trade.asset_receiving_wallet,
+            asset_change: to_f64(&trade.asset_change),
+            asset_change_exact: trade.asset_change.to_string(),
+            currency_change: to_f64(&trade.currency_change),
+            currency_change_exact: trade.currency_change.to_string(),
+            market_fee: to_f64(&trade.market_fee),
+            market_fee_exact: trade.market_fee.to_string(),
+            total_cost: to_f64(&trade.total_cost),
+            total_cost_exact: trade.total_cost.to_string(),
+            price: to_f64(&trade.price),
+            price_exact: trade.price.to_string(),
+            fee_payer: trade.fee_payer,
+            fee_rate: to_f64(&trade.fee_rate),
+            fee_rate_exact: trade.fee_rate.to_string(),
+            royalty: to_f64(&trade.royalty),
+            royalty_exact: trade.royalty.to_string(),
+            royalty_discount: to_f64(&trade.royalty_discount),
+            royalty_discount_exact: trade.royalty_discount.to_string(),
+        }
+    }
+}
+
+fn to_f64(value: &BigDecimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+pub fn create_trades_response(cursor_db: Vec<Trade>) -> Result<impl Reply, Rejection> {
+    let response: Vec<ResponseTrade> = cursor_db.into_iter().map(ResponseTrade::from).collect();
+    create_response(&response)
+}
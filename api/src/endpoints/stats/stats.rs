@@ -14,22 +14,35 @@ use staratlas::symbolstore::{BuilderSymbolStore};
 use types::databasetrade::DBTrade;
 use types::m_ohclvt::M_OHCLVT;
 use utoipa::{IntoParams, ToSchema};
-use warp::{Filter, hyper::StatusCode, Reply};
+use warp::{Filter, hyper::StatusCode, Rejection, Reply};
 use warp::sse::reply;
 
 use database_psql::connection::create_psql_pool_diesel;
+use database_psql::coverage::{compute_coverage, coverage_window, module_cursors};
 use database_psql::model::Trade;
 
 use crate::endpoints::responses::response_error::ResponseError;
+use crate::error::ApiError;
+use crate::endpoints::responses::response_trade::{create_response, create_trades_response, ResponseTrade};
 use crate::endpoints::trades::trades;
 use crate::endpoints::udf::{udf_config_t, udf_history_t, udf_symbols_t};
 use crate::endpoints::udf::{udf_search_t, udf_symbol_info_t};
 use crate::endpoints::udf::udf_error_t::{Status, UdfError};
 use crate::helper::with_psql_store;
+use crate::metrics::METRICS;
 use crate::udf_config_t::{Exchange, SymbolsType};
 
 //region PARAMS
-
+/// Module of the worker's substream, its cursors are `{module}_{start}_{end}`.
+const DEFAULT_COVERAGE_MODULE: &str = "map_sa_trades";
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CoverageParams {
+    /// Substream module whose cursors are counted, defaults to `map_sa_trades`.
+    #[param(style = Form, example = "map_sa_trades")]
+    module: Option<String>,
+}
 
 //endregion
 
@@ -37,6 +50,7 @@ use crate::udf_config_t::{Exchange, SymbolsType};
 pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
 {
     let psql_pool = create_psql_pool_diesel();
+    METRICS.register_diesel_pool("stats", &psql_pool);
 
     let last_timestamp = warp::path!("stats"  / "last_timestamp")
         .and(warp::get())
@@ -56,7 +70,14 @@ pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rej
         .and(warp::path::end())
         .and_then(get_ranges);
 
-    last_timestamp.or(first_timestamp).or(ranges)
+    let coverage = warp::path!("stats"  / "coverage")
+        .and(warp::get())
+        .and(with_psql_store(psql_pool.clone()))
+        .and(warp::path::end())
+        .and(warp::query::<CoverageParams>())
+        .and_then(get_coverage);
+
+    last_timestamp.or(first_timestamp).or(ranges).or(coverage)
 }
 //endregion
 
@@ -67,11 +88,14 @@ pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rej
 get,
 path = "/stats/last_timestamp",
 responses(
-(status = 200, description = "Get time successfully", body = [Trade])
+(status = 200, description = "Get time successfully", body = [ResponseTrade]),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
-pub async fn get_last_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().unwrap();
+pub async fn get_last_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
 
     use diesel::prelude::*;
     use database_psql::model::*;
@@ -81,17 +105,9 @@ pub async fn get_last_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>)
         .order(timestamp.desc())
         .limit(1)
         .load::<Trade>(&mut db)
-        .expect("Error loading cursors");
-
-    return if cursor_db.is_empty() {
-        warn!("While getting get_first_timestamp");
-        Ok(warp::reply::json(&ResponseError {
-            s: 1,
-            errmsg: "No data found".to_string(),
-        }))
-    } else {
-        Ok(warp::reply::json(&cursor_db))
-    };
+        .map_err(ApiError::from)?;
+
+    create_trades_response(cursor_db)
 }
 
 /// First timestamp
@@ -101,11 +117,14 @@ pub async fn get_last_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>)
 get,
 path = "/stats/first_timestamp",
 responses(
-(status = 200, description = "Get time successfully", body = [Trade])
+(status = 200, description = "Get time successfully", body = [ResponseTrade]),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
-pub async fn get_first_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().unwrap();
+pub async fn get_first_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
 
     use diesel::prelude::*;
     use database_psql::model::*;
@@ -115,17 +134,9 @@ pub async fn get_first_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>)
         .order(timestamp.asc())
         .limit(1)
         .load::<Trade>(&mut db)
-        .expect("Error loading cursors");
-
-    return if cursor_db.is_empty() {
-        warn!("While getting get_first_timestamp");
-        Ok(warp::reply::json(&ResponseError {
-            s: 1,
-            errmsg: "No data found".to_string(),
-        }))
-    } else {
-        Ok(warp::reply::json(&cursor_db))
-    };
+        .map_err(ApiError::from)?;
+
+    create_trades_response(cursor_db)
 }
 
 
@@ -136,11 +147,14 @@ pub async fn get_first_timestamp(db_pool: Pool<ConnectionManager<PgConnection>>)
 get,
 path = "/stats/ranges",
 responses(
-(status = 200, description = "Get range successfully", body = [Cursor])
+(status = 200, description = "Get range successfully", body = [Cursor]),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
-pub async fn get_ranges(db_pool: Pool<ConnectionManager<PgConnection>>) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().unwrap();
+pub async fn get_ranges(db_pool: Pool<ConnectionManager<PgConnection>>) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
 
     use diesel::prelude::*;
     use database_psql::model::*;
@@ -149,16 +163,41 @@ pub async fn get_ranges(db_pool: Pool<ConnectionManager<PgConnection>>) -> Resul
     let cursor_db: Vec<Cursor> = cursors
         .order(id.desc())
         .load::<Cursor>(&mut db)
-        .expect("Error loading cursors");
-
-    return if cursor_db.is_empty() {
-        warn!("While requesting get_ranges");
-        Ok(warp::reply::json(&ResponseError {
-            s: 1,
-            errmsg: "No data found".to_string(),
-        }))
-    } else {
-        Ok(warp::reply::json(&cursor_db))
-    };
+        .map_err(ApiError::from)?;
+
+    create_response(&cursor_db)
 }
 
+
+
+/// Coverage
+///
+/// Responses with the covered and missing block intervals of a module between its first cursor start and its
+/// last covered block. Cursors of other modules and of the RPC backfill are not counted.
+#[utoipa::path(
+get,
+path = "/stats/coverage",
+params(CoverageParams),
+responses(
+(status = 200, description = "Get coverage successfully", body = Coverage),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
+)
+)]
+pub async fn get_coverage(db_pool: Pool<ConnectionManager<PgConnection>>, query: CoverageParams) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
+
+    use diesel::prelude::*;
+    use database_psql::model::*;
+    use database_psql::schema::cursors::dsl::*;
+
+    let cursor_db: Vec<Cursor> = cursors
+        .order(id.asc())
+        .load::<Cursor>(&mut db)
+        .map_err(ApiError::from)?;
+
+    let module_cursor_db = module_cursors(&cursor_db, query.module.as_deref().unwrap_or(DEFAULT_COVERAGE_MODULE));
+    let window = coverage_window(&module_cursor_db).ok_or_else(ApiError::no_data)?;
+    Ok(warp::reply::json(&compute_coverage(&module_cursor_db, window)))
+}
@@ -0,0 +1,149 @@
+use std::convert::Infallible;
+use std::env;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use warp::{Filter, Rejection, Reply};
+
+use staratlas::symbolstore::{BuilderSymbolStore, SharedSymbolStore};
+
+use crate::endpoints::responses::response_error::ResponseError;
+use crate::error::ApiError;
+use crate::helper::constant_time_eq;
+
+//region PARAMS
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SymbolRefresh {
+    assets: usize,
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SymbolDetails {
+    symbol: String,
+    asset_name: String,
+    pair_name: String,
+    description: String,
+    asset_type: String,
+    mint: String,
+    pair_mint: String,
+    pricescale: i64,
+    name: Option<String>,
+    image: Option<String>,
+    rarity: Option<String>,
+    class: Option<String>,
+    tier: Option<i64>,
+    make: Option<String>,
+    model: Option<String>,
+}
+
+//endregion
+
+//region HANDLERS
+pub async fn handlers(store: SharedSymbolStore, builder: BuilderSymbolStore) -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
+{
+    let refresh = warp::path!("symbols" / "refresh")
+        .and(warp::post())
+        .and(warp::path::end())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(with_shared_store(store.clone()))
+        .and(warp::any().map(move || builder.clone()))
+        .and_then(post_refresh);
+
+    let symbol = warp::path!("symbols" / String)
+        .and(warp::get())
+        .and(warp::path::end())
+        .and(with_shared_store(store.clone()))
+        .and_then(get_symbol);
+
+    refresh.or(symbol)
+}
+
+fn with_shared_store(
+    store: SharedSymbolStore,
+) -> impl Filter<Extract=(SharedSymbolStore, ), Error=Infallible> + Clone {
+    warp::any().map(move || store.clone())
+}
+
+//endregion
+
+/// Refresh symbols
+///
+/// Reloads the symbol store from its source and responses with the added and removed assets.
+/// Requires the `ADMIN_TOKEN` of the server in the authorization header.
+#[utoipa::path(
+post,
+path = "/symbols/refresh",
+responses(
+(status = 200, description = "Response: Refresh successful", body = SymbolRefresh),
+(status = 401, description = "Missing or wrong admin token", body = ResponseError),
+(status = 502, description = "Galaxy api not reachable", body = ResponseError)
+)
+)]
+pub async fn post_refresh(
+    authorization: Option<String>,
+    store: SharedSymbolStore,
+    builder: BuilderSymbolStore,
+) -> Result<impl Reply, Rejection> {
+    let admin_token = env::var("ADMIN_TOKEN").ok().filter(|token| !token.is_empty());
+    let authorized = match (admin_token, authorization) {
+        (Some(admin_token), Some(authorization)) => constant_time_eq(admin_token.as_bytes(), authorization.as_bytes()),
+        _ => false,
+    };
+    if !authorized {
+        return Err(ApiError::Unauthorized("Unauthorized".to_string()).into());
+    }
+
+    let diff = builder.refresh(&store).await
+        .map_err(|err| ApiError::BadGateway(err.to_string()))?;
+    let refresh = SymbolRefresh {
+        assets: store.read().map_err(|_| ApiError::Internal("Symbol store poisoned".to_string()))?.assets.len(),
+        added: diff.added,
+        removed: diff.removed,
+    };
+    Ok(warp::reply::json(&refresh))
+}
+
+/// Get symbol details
+///
+/// Responses with the asset of the symbol, including the galaxy metadata like image and rarity.
+#[utoipa::path(
+get,
+path = "/symbols/{symbol}",
+params(
+("symbol" = String, Path, description = "Symbol of the pair", example = "PX4ATLAS")
+),
+responses(
+(status = 200, description = "Response: Symbol found", body = SymbolDetails),
+(status = 404, description = "Unknown symbol", body = ResponseError)
+)
+)]
+pub async fn get_symbol(
+    symbol: String,
+    store: SharedSymbolStore,
+) -> Result<impl Reply, Rejection> {
+    let store = store.read().map_err(|_| ApiError::Internal("Symbol store poisoned".to_string()))?.clone();
+    let asset = store.asset_by_symbol(symbol.as_str())
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown symbol {}", symbol)))?;
+
+    let metadata = asset.metadata.as_ref();
+    let details = SymbolDetails {
+        symbol: asset.symbol.clone(),
+        asset_name: asset.asset_name.clone(),
+        pair_name: asset.pair_name.clone(),
+        description: asset.description.clone(),
+        asset_type: asset.asset_type.clone(),
+        mint: asset.mint.clone(),
+        pair_mint: asset.pair_mint.clone(),
+        pricescale: asset.pricescale,
+        name: metadata.map(|metadata| metadata.name.clone()),
+        image: metadata.map(|metadata| metadata.image.clone()),
+        rarity: metadata.map(|metadata| metadata.rarity.clone()),
+        class: metadata.map(|metadata| metadata.class.clone()),
+        tier: metadata.and_then(|metadata| metadata.tier),
+        make: metadata.and_then(|metadata| metadata.make.clone()),
+        model: metadata.and_then(|metadata| metadata.model.clone()),
+    };
+    Ok(warp::reply::json(&details))
+}
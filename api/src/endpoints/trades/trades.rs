@@ -9,7 +9,6 @@ use std::mem::swap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, NaiveDate, Utc};
-use database_psql::connection::{create_psql_pool_diesel, create_psql_raw_pool};
 use database_psql::model::Trade;
 use diesel::{PgConnection, QueryDsl, RunQueryDsl, sql_query};
 use diesel::dsl::date;
@@ -22,11 +21,12 @@ use staratlas::symbolstore::{BuilderSymbolStore};
 use types::databasetrade::DBTrade;
 use types::m_ohclvt::M_OHCLVT;
 use utoipa::{IntoParams, ToSchema};
-use warp::{Filter, hyper::StatusCode, Reply};
+use warp::{Filter, hyper::StatusCode, Rejection, Reply};
 use warp::sse::reply;
 
 use crate::endpoints::responses::response_error::ResponseError;
-use crate::endpoints::responses::response_trade::create_response;
+use crate::error::ApiError;
+use crate::endpoints::responses::response_trade::{create_response, create_trades_response, ResponseTrade};
 use crate::endpoints::udf::{udf_config_t, udf_history_t, udf_symbols_t};
 use crate::endpoints::udf::{udf_search_t, udf_symbol_info_t};
 use crate::endpoints::udf::udf_error_t::{Status, UdfError};
@@ -96,11 +96,8 @@ pub struct DefaultVolumeParams {
 //endregion
 
 //region HANDLERS
-pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
+pub async fn handlers(psql_pool: Pool<ConnectionManager<PgConnection>>, psql_raw_pool: deadpool_postgres::Pool) -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
 {
-    let psql_raw_pool = create_psql_raw_pool();
-    let psql_pool = create_psql_pool_diesel();
-
     let base = warp::path!("trades")
         .and(warp::get())
         .and(warp::path::end())
@@ -158,14 +155,18 @@ get,
 path = "/trades",
 params(DefaultBaseParams),
 responses(
-(status = 200, description = "Response: Time successful", body = [Trade])
+(status = 200, description = "Response: Time successful", body = [ResponseTrade]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
 pub async fn get_base(
     db_pool: Pool<ConnectionManager<PgConnection>>,
     query: DefaultBaseParams,
-) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().expect("Unable to get connection from pool!");
+) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
     use diesel::prelude::*;
     use database_psql::model::*;
     use database_psql::schema::trades::dsl::*;
@@ -178,7 +179,7 @@ pub async fn get_base(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(10))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading trades")
+                        .map_err(ApiError::from)?
                 }
                 Some(from) => {
                     trades
@@ -186,7 +187,7 @@ pub async fn get_base(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(10))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading trades")
+                        .map_err(ApiError::from)?
                 }
             }
         }
@@ -198,7 +199,7 @@ pub async fn get_base(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(10))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading trades")
+                        .map_err(ApiError::from)?
                 }
                 Some(from) => {
                     trades
@@ -207,12 +208,12 @@ pub async fn get_base(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(10))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading trades")
+                        .map_err(ApiError::from)?
                 }
             }
         }
     };
-    create_response(&cursor_db)
+    create_trades_response(cursor_db)
 }
 
 
@@ -224,14 +225,18 @@ get,
 path = "/trades/symbol",
 params(DefaultSymbolParams),
 responses(
-(status = 200, description = "Response: Time successful", body = [Trade])
+(status = 200, description = "Response: Time successful", body = [ResponseTrade]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
 pub async fn get_symbol(
     db_pool: Pool<ConnectionManager<PgConnection>>,
     query: DefaultSymbolParams,
-) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().expect("Unable to get connection from pool!");
+) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
     use diesel::prelude::*;
     use database_psql::model::*;
     use database_psql::schema::trades::dsl::*;
@@ -243,7 +248,7 @@ pub async fn get_symbol(
                 .order(timestamp.desc())
                 .limit(query.limit.unwrap_or(10))
                 .load::<Trade>(&mut db)
-                .expect("Error loading cursors")
+                .map_err(ApiError::from)?
         }
         Some(to) => {
             trades
@@ -252,10 +257,10 @@ pub async fn get_symbol(
                 .order(timestamp.desc())
                 .limit(query.limit.unwrap_or(10))
                 .load::<Trade>(&mut db)
-                .expect("Error loading cursors")
+                .map_err(ApiError::from)?
         }
     };
-    create_response(&cursor_db)
+    create_trades_response(cursor_db)
 }
 
 
@@ -267,14 +272,18 @@ get,
 path = "/trades/signature",
 params(DefaultSignatureParams),
 responses(
-(status = 200, description = "Response: Time successful", body = [Trade])
+(status = 200, description = "Response: Time successful", body = [ResponseTrade]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
 pub async fn get_signature(
     db_pool: Pool<ConnectionManager<PgConnection>>,
     query: DefaultSignatureParams,
-) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().expect("Unable to get connection from pool!");
+) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
 
     use diesel::prelude::*;
     use database_psql::model::*;
@@ -287,7 +296,7 @@ pub async fn get_signature(
                 .order(timestamp.desc())
                 .limit(query.limit.unwrap_or(10))
                 .load::<Trade>(&mut db)
-                .expect("Error loading cursors")
+                .map_err(ApiError::from)?
         }
         Some(to) => {
             trades
@@ -296,11 +305,11 @@ pub async fn get_signature(
                 .order(timestamp.desc())
                 .limit(query.limit.unwrap_or(10))
                 .load::<Trade>(&mut db)
-                .expect("Error loading cursors")
+                .map_err(ApiError::from)?
         }
     };
 
-    create_response(&cursor_db)
+    create_trades_response(cursor_db)
 }
 
 
@@ -312,14 +321,18 @@ get,
 path = "/trades/address",
 params(DefaultAddressParams),
 responses(
-(status = 200, description = "Response: Time successful", body = [Trade])
+(status = 200, description = "Response: Time successful", body = [ResponseTrade]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
 pub async fn get_address(
     db_pool: Pool<ConnectionManager<PgConnection>>,
     query: DefaultAddressParams,
-) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().expect("Unable to get connection from pool!");
+) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
 
     use diesel::prelude::*;
     use database_psql::model::*;
@@ -332,7 +345,7 @@ pub async fn get_address(
                 .order(timestamp.desc())
                 .limit(query.limit.unwrap_or(100))
                 .load::<Trade>(&mut db)
-                .expect("Error loading cursors")
+                .map_err(ApiError::from)?
         }
         Some(to) => {
             trades
@@ -342,11 +355,11 @@ pub async fn get_address(
                 .order(timestamp.desc())
                 .limit(query.limit.unwrap_or(100))
                 .load::<Trade>(&mut db)
-                .expect("Error loading cursors")
+                .map_err(ApiError::from)?
         }
     };
 
-    create_response(&cursor_db)
+    create_trades_response(cursor_db)
 }
 
 
@@ -358,14 +371,18 @@ get,
 path = "/trades/mint",
 params(DefaultMintParams),
 responses(
-(status = 200, description = "Response: Time successful", body = [Trade])
+(status = 200, description = "Response: Time successful", body = [ResponseTrade]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
 pub async fn get_mint(
     db_pool: Pool<ConnectionManager<PgConnection>>,
     query: DefaultMintParams,
-) -> Result<impl Reply, Infallible> {
-    let mut db = db_pool.get().expect("Unable to get connection from pool!");
+) -> Result<impl Reply, Rejection> {
+    let mut db = db_pool.get().map_err(ApiError::from)?;
 
     use diesel::prelude::*;
     use database_psql::model::*;
@@ -382,7 +399,7 @@ pub async fn get_mint(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(100))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading cursors")
+                        .map_err(ApiError::from)?
                 }
                 Some(to) => {
                     trades
@@ -393,7 +410,7 @@ pub async fn get_mint(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(100))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading cursors")
+                        .map_err(ApiError::from)?
                 }
             }
         }
@@ -407,7 +424,7 @@ pub async fn get_mint(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(100))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading cursors")
+                        .map_err(ApiError::from)?
                 }
                 Some(to) => {
                     trades
@@ -418,12 +435,12 @@ pub async fn get_mint(
                         .order(timestamp.desc())
                         .limit(query.limit.unwrap_or(100))
                         .load::<Trade>(&mut db)
-                        .expect("Error loading cursors")
+                        .map_err(ApiError::from)?
                 }
             }
         }
     };
-    create_response(&cursor_db)
+    create_trades_response(cursor_db)
 }
 
 
@@ -435,14 +452,18 @@ get,
 path = "/trades/volume",
 params(DefaultVolumeParams),
 responses(
-(status = 200, description = "Response: Time successful", body = [VolumeData])
+(status = 200, description = "Response: Time successful", body = [VolumeData]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "No data found", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
 pub async fn get_volume(
     pool: deadpool_postgres::Pool,
     query: DefaultVolumeParams,
-) -> Result<impl Reply, Infallible> {
-    let mut db = pool.get().await.expect("Unable to get connection from pool!");
+) -> Result<impl Reply, Rejection> {
+    let mut db = pool.get().await.map_err(ApiError::from)?;
 
 
     use diesel::{prelude::*, sql_types::*};
@@ -454,20 +475,20 @@ pub async fn get_volume(
 
     let data: Vec<Row> = match query.asset_mint {
         None => {
-            db.query("SELECT date(timestamp_ts) as timestamp,  sum(price*asset_change) as volume
+            db.query("SELECT date(timestamp_ts) as timestamp,  sum(price*asset_change)::float8 as volume
                                             from trades
                                             WHERE (currency_mint LIKE $1) AND timestamp > $2
                                             GROUP BY date(timestamp_ts)
                                             ORDER BY timestamp ASC",
-                     &[&query.currency_mint, &query.from]).await.unwrap_or_default()
+                     &[&query.currency_mint, &query.from]).await.map_err(ApiError::from)?
         }
         Some(value) => {
-            db.query("SELECT date(timestamp_ts) as timestamp,  sum(price*asset_change) as volume
+            db.query("SELECT date(timestamp_ts) as timestamp,  sum(price*asset_change)::float8 as volume
                                             from trades
                                             WHERE (currency_mint LIKE $1 AND asset_mint LIKE $2) AND timestamp > $2
                                             GROUP BY date(timestamp_ts)
                                             ORDER BY timestamp ASC",
-                     &[&query.currency_mint, &value, &query.from]).await.unwrap_or_default()
+                     &[&query.currency_mint, &value, &query.from]).await.map_err(ApiError::from)?
         }
     };
 
@@ -1,3 +1,4 @@
+use bigdecimal::{ToPrimitive, Zero};
 use database_psql::model::Trade;
 
 use crate::endpoints::udf::udf_history_t::UdfHistory;
@@ -71,5 +72,8 @@ struct OHLC {
 // }
 
 fn calc_price(item: &Trade) -> f64 {
-    item.total_cost / item.asset_change as f64
+    if item.asset_change.is_zero() {
+        return 0.0;
+    }
+    (&item.total_cost / &item.asset_change).to_f64().unwrap_or_default()
 }
\ No newline at end of file
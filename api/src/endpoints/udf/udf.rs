@@ -1,9 +1,10 @@
 use std::{
     convert::Infallible,
     env,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, PoisonError},
 };
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, NaiveDateTime, Utc};
 use database_psql::connection::create_psql_pool_diesel;
@@ -15,20 +16,23 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use log::{info, warn};
 use postgres::{NoTls, Row};
 use serde::{Deserialize, Serialize};
-use staratlas::symbolstore::{BuilderSymbolStore};
+use staratlas::symbolstore::SharedSymbolStore;
 use types::databasetrade::DBTrade;
 use types::m_ohclvt::M_OHCLVT;
 use utoipa::{IntoParams, ToSchema};
-use warp::{Filter, hyper::StatusCode, Reply};
+use warp::{Filter, hyper::StatusCode, Rejection, Reply};
 use warp::sse::reply;
-use staratlas_symbols::symbol_store::SymbolStore;
+use staratlas_symbols::symbol_store::{SymbolSearch, SymbolStore};
 
 use udf::time_convert::{convert_udf_time_to_seconds, convert_udf_time_to_timestamp_minute};
 
+use crate::endpoints::responses::response_error::ResponseError;
 use crate::endpoints::udf::{udf_config_t, udf_history_t, udf_symbols_t};
 use crate::endpoints::udf::{udf_search_t, udf_symbol_info_t};
 use crate::endpoints::udf::udf_error_t::{Status, UdfError};
+use crate::error::ApiError;
 use crate::helper::{with_psql_store, with_raw_psql_store};
+use crate::metrics::METRICS;
 use crate::udf_config_t::{Exchange, SymbolsType};
 
 //region PARAMS
@@ -54,6 +58,10 @@ pub struct SearchParams {
     #[serde(rename = "type")]
     ship_type: Option<String>,
     exchange: Option<String>,
+    #[param(style = Form, example = "Common")]
+    rarity: Option<String>,
+    #[param(style = Form, example = "XX-Small")]
+    class: Option<String>,
     #[param(style = Form, example = "2")]
     limit: usize,
 }
@@ -74,12 +82,47 @@ pub struct HistoryParams {
 }
 //endregion
 
+/// How long the traded volumes ranking the search results are reused before they are queried again.
+const VOLUME_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Traded volumes of the last 7 days per symbol, shared by all search requests.
+#[derive(Clone, Default)]
+pub struct VolumeCache {
+    volumes: Arc<tokio::sync::Mutex<Option<(Instant, Arc<HashMap<String, f64>>)>>>,
+}
+
+impl VolumeCache {
+    /// Cached volumes, queried once `VOLUME_TTL` passed. Concurrent requests wait for the same query, a failed
+    /// query keeps the previous volumes.
+    async fn get(&self, db_pool: &deadpool_postgres::Pool) -> Arc<HashMap<String, f64>> {
+        let mut cached = self.volumes.lock().await;
+        if let Some((loaded_at, volumes)) = cached.as_ref() {
+            if loaded_at.elapsed() < VOLUME_TTL {
+                return volumes.clone();
+            }
+        }
+
+        match get_recent_volumes(db_pool).await {
+            Ok(volumes) => {
+                let volumes = Arc::new(volumes);
+                *cached = Some((Instant::now(), volumes.clone()));
+                volumes
+            }
+            Err(err) => {
+                warn!("Unable to load volumes for search ranking: {}", err);
+                cached.as_ref().map(|(_, volumes)| volumes.clone()).unwrap_or_default()
+            }
+        }
+    }
+}
+
 //region HANDLERS
-pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
+pub async fn handlers(store_sa: SharedSymbolStore) -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone
 {
-    let store_sa = BuilderSymbolStore::new().init().await;
     let psql_raw_pool = create_psql_raw_pool();
     let psql_pool = create_psql_pool_diesel();
+    METRICS.register_raw_pool("udf_raw", &psql_raw_pool);
+    METRICS.register_diesel_pool("udf", &psql_pool);
 
     let home = warp::path!("udf")
         .and(warp::get())
@@ -114,6 +157,8 @@ pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rej
         .and(warp::get())
         .and(warp::path::end())
         .and(with_sa_store(store_sa.clone()))
+        .and(with_raw_psql_store(psql_raw_pool.clone()))
+        .and(with_volume_cache(VolumeCache::default()))
         .and(warp::query::<SearchParams>())
         .and_then(get_search);
 
@@ -133,12 +178,18 @@ pub async fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rej
 }
 
 fn with_sa_store(
-    store: SymbolStore,
-) -> impl Filter<Extract=(SymbolStore, ), Error=Infallible> + Clone {
-    warp::any().map(move || store.clone())
+    store: SharedSymbolStore,
+) -> impl Filter<Extract=(Arc<SymbolStore>, ), Error=Infallible> + Clone {
+    warp::any().map(move || store.read().unwrap_or_else(PoisonError::into_inner).clone())
 }
 
 
+fn with_volume_cache(
+    cache: VolumeCache,
+) -> impl Filter<Extract=(VolumeCache, ), Error=Infallible> + Clone {
+    warp::any().map(move || cache.clone())
+}
+
 // fn with_mongo_store(
 //     store: Collection<Document>,
 // ) -> impl Filter<Extract=(Collection<Document>, ), Error=Infallible> + Clone {
@@ -191,7 +242,7 @@ responses(
 (status = 200, description = "Response: Config successful", body = [UdfConfig])
 )
 )]
-pub async fn get_config(store: SymbolStore) -> Result<impl Reply, Infallible> {
+pub async fn get_config(store: Arc<SymbolStore>) -> Result<impl Reply, Infallible> {
     let mut all_symbols = Vec::new();
 
     store
@@ -235,46 +286,40 @@ responses(
 (status = 200, description = "Response: SymbolInfo successful", body = [UdfSymbolInfo])
 )
 )]
-pub async fn get_symbol_info(store: SymbolStore) -> Result<impl Reply, Infallible> {
+pub async fn get_symbol_info(store: Arc<SymbolStore>) -> Result<impl Reply, Infallible> {
     let config = udf_symbol_info_t::UdfSymbolInfo {
         symbol: store
             .assets
-            .clone()
-            .into_iter()
-            .map(|asset| asset.symbol)
+            .iter()
+            .map(|asset| asset.symbol.clone())
             .collect(),
         ticker: store
             .assets
-            .clone()
-            .into_iter()
-            .map(|asset| asset.symbol)
+            .iter()
+            .map(|asset| asset.symbol.clone())
             .collect(),
         name: store
             .assets
-            .clone()
-            .into_iter()
-            .map(|asset| asset.symbol)
+            .iter()
+            .map(|asset| asset.symbol.clone())
             .collect(),
         full_name: store
             .assets
-            .clone()
-            .into_iter()
-            .map(|asset| asset.symbol)
+            .iter()
+            .map(|asset| asset.symbol.clone())
             .collect(),
         description: store
             .assets
-            .clone()
-            .into_iter()
-            .map(|asset| asset.description)
+            .iter()
+            .map(|asset| asset.description.clone())
             .collect(),
         exchange: store.exchange.clone().name,
         listed_exchange: store.exchange.clone().name,
         udf_symbol_info_type: store.exchange.clone().asset_type[0].clone(),
         currency_code: store
             .assets
-            .clone()
-            .into_iter()
-            .map(|asset| asset.pair_name)
+            .iter()
+            .map(|asset| asset.pair_name.clone())
             .collect(),
         session: store.exchange.clone().sesstion,
         timezone: store.exchange.clone().timezone,
@@ -284,15 +329,14 @@ pub async fn get_symbol_info(store: SymbolStore) -> Result<impl Reply, Infallibl
         minmov2: store.exchange.clone().minmov2,
         pricescale: store
             .assets
-            .clone()
-            .into_iter()
+            .iter()
             .map(|asset| asset.pricescale)
             .collect(),
         supported_resolutions: store.exchange.clone().supported_resolutions,
         has_intraday: store.exchange.clone().has_intraday,
         has_daily: store.exchange.clone().has_daily,
         has_weekly_and_monthly: store.exchange.clone().has_weekly_and_monthly,
-        data_status: store.exchange.data_status,
+        data_status: store.exchange.data_status.clone(),
     };
 
     Ok(warp::reply::json(&config))
@@ -307,100 +351,110 @@ path = "/udf/symbols",
 params(SymbolsParams),
 responses(
 (status = 200, description = "Response: SymbolInfo successful", body = [UdfSymbolInfo]),
-(status = 404, description = "Nothing found")
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 404, description = "Unknown symbol", body = ResponseError)
 )
 )]
 pub async fn get_symbols(
-    store: SymbolStore,
+    store: Arc<SymbolStore>,
     query: SymbolsParams,
-) -> Result<Box<dyn Reply>, Infallible> {
-    let filtered = store
-        .assets
-        .clone()
-        .into_iter()
-        .filter(|asset| asset.symbol == query.symbol)
-        .collect::<Vec<_>>();
-
-    if filtered.len() == 1 {
+) -> Result<impl Reply, Rejection> {
+    if let Some(asset) = store.asset_by_symbol(query.symbol.as_str()) {
         let symbols = udf_symbols_t::UdfSymbols {
-            symbol: filtered[0].clone().symbol,
-            ticker: filtered[0].clone().symbol,
-            name: filtered[0].clone().symbol,
-            full_name: filtered[0].clone().symbol,
-            description: filtered[0].clone().description,
+            symbol: asset.symbol.clone(),
+            ticker: asset.symbol.clone(),
+            name: asset.symbol.clone(),
+            full_name: asset.symbol.clone(),
+            description: asset.description.clone(),
             exchange: store.exchange.clone().name,
             listed_exchange: store.exchange.clone().name,
-            udf_symbols_type: filtered[0].clone().asset_type,
-            currency_code: filtered[0].clone().pair_name,
+            udf_symbols_type: asset.asset_type.clone(),
+            currency_code: asset.pair_name.clone(),
             session: store.exchange.clone().sesstion,
             timezone: store.exchange.clone().timezone,
             minmovement: store.exchange.clone().minmovement,
             minmov: store.exchange.clone().minmov,
             minmovement2: store.exchange.clone().minmovement2,
             minmov2: store.exchange.clone().minmov2,
-            pricescale: filtered[0].clone().pricescale,
+            pricescale: asset.pricescale,
+            logo_urls: asset.metadata.iter().map(|metadata| metadata.image.clone()).collect(),
             supported_resolutions: store.exchange.clone().supported_resolutions,
             has_intraday: store.exchange.clone().has_intraday,
             has_daily: store.exchange.clone().has_daily,
             has_weekly_and_monthly: store.exchange.clone().has_weekly_and_monthly,
-            data_status: store.exchange.data_status,
+            data_status: store.exchange.data_status.clone(),
         };
-        return Ok(Box::new(warp::reply::json(&symbols)));
+        return Ok(warp::reply::json(&symbols));
     }
-    return Ok(Box::new(StatusCode::NOT_FOUND));
+    Err(ApiError::NotFound(format!("Unknown symbol {}", query.symbol)).into())
 }
 
 /// Get Search request
 ///
-/// Responses with a Search-Result in json.
+/// Responses with a Search-Result in json. The query is matched case-insensitive and fuzzy against
+/// symbol, name and description, results can be filtered by type, rarity and class and are ranked by
+/// match quality and traded volume of the last 7 days.
 #[utoipa::path(
 get,
 path = "/udf/search",
 params(SearchParams),
 responses(
-(status = 200, description = "Response: SymbolInfo successful", body = [UdfSearchSymbol])
+(status = 200, description = "Response: SymbolInfo successful", body = [UdfSearchSymbol]),
+(status = 400, description = "Invalid query parameters", body = ResponseError)
 )
 )]
-pub async fn get_search(store: SymbolStore, query: SearchParams) -> Result<impl Reply, Infallible> {
-    let filtered = store
-        .assets
+pub async fn get_search(
+    store: Arc<SymbolStore>,
+    db_pool: deadpool_postgres::Pool,
+    volume_cache: VolumeCache,
+    query: SearchParams,
+) -> Result<impl Reply, Infallible> {
+    let exchange = query.exchange.as_deref().unwrap_or_default();
+    if !exchange.is_empty() && !exchange.eq_ignore_ascii_case(store.exchange.symbol.as_str()) {
+        return Ok(warp::reply::json(&Vec::<udf_search_t::UdfSearchSymbol>::new()));
+    }
+
+    let search = SymbolSearch {
+        query: query.query.clone(),
+        asset_type: query.ship_type.clone(),
+        rarity: query.rarity.clone(),
+        class: query.class.clone(),
+    };
+    let volumes = volume_cache.get(&db_pool).await;
+
+    let search_limited: Vec<udf_search_t::UdfSearchSymbol> = store
+        .search(&search, &volumes)
         .into_iter()
-        .filter(|asset| {
-            asset.symbol.contains(query.query.clone().as_str())
-                && asset
-                .asset_type
-                .contains(query.ship_type.clone().unwrap_or("".to_string()).as_str())
+        .take(query.limit)
+        .map(|asset| udf_search_t::UdfSearchSymbol {
+            symbol: asset.symbol.clone(),
+            full_name: asset.asset_name.clone(),
+            description: asset.description.clone(),
+            exchange: store.exchange.symbol.clone(),
+            ticker: asset.symbol.clone(),
+            udf_symbol_type: asset.asset_type.clone(),
+            logo_urls: asset.metadata.iter().map(|metadata| metadata.image.clone()).collect(),
         })
-        .collect::<Vec<_>>();
-
-    let mut search: Vec<udf_search_t::UdfSearchSymbol> = Vec::new();
-    filtered.into_iter().for_each(|asset| {
-        search.push(udf_search_t::UdfSearchSymbol {
-            symbol: asset.clone().symbol,
-            full_name: asset.clone().asset_name,
-            description: asset.clone().description,
-            exchange: store.exchange.clone().symbol,
-            ticker: asset.clone().symbol,
-            udf_symbol_type: asset.clone().asset_type,
-        })
-    });
-
-    let mut search_limited: Vec<udf_search_t::UdfSearchSymbol> = Vec::new();
-    if (query.limit > 0) {
-        if (search.len() < query.limit) {
-            for l in 0..search.len() {
-                search_limited.push(search[l].clone());
-            }
-        } else {
-            for l in 0..query.limit {
-                search_limited.push(search[l].clone());
-            }
-        }
-    }
+        .collect();
 
     Ok(warp::reply::json(&search_limited))
 }
 
+/// Traded asset amount per symbol of the last 7 days.
+async fn get_recent_volumes(db_pool: &deadpool_postgres::Pool) -> Result<HashMap<String, f64>, ApiError> {
+    let since = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64 - 7 * 24 * 60 * 60;
+
+    let db = db_pool.get().await?;
+    let rows = db.query("SELECT symbol, SUM(asset_change)::float8 AS volume
+                    FROM trades
+                    WHERE timestamp >= $1
+                    GROUP BY symbol", &[&since]).await?;
+
+    Ok(rows.into_iter()
+        .map(|row| (row.get("symbol"), row.try_get("volume").unwrap_or_default()))
+        .collect())
+}
+
 /// Get History request
 ///
 /// Responses with a History-Result in json (oclh).
@@ -409,13 +463,16 @@ get,
 path = "/udf/history",
 params(HistoryParams),
 responses(
-(status = 200, description = "Response: SymbolInfo successful", body = [UdfHistory])
+(status = 200, description = "Response: SymbolInfo successful", body = [UdfHistory]),
+(status = 400, description = "Invalid query parameters", body = ResponseError),
+(status = 500, description = "Query failed", body = ResponseError),
+(status = 503, description = "Database not available", body = ResponseError)
 )
 )]
 pub async fn get_history(
     db_pool: deadpool_postgres::Pool,
     query: HistoryParams,
-) -> Result<impl Reply, Infallible> {
+) -> Result<impl Reply, Rejection> {
     let mut history = udf_history_t::UdfHistory {
         s: "ok".to_string(),
         t: vec![],
@@ -426,12 +483,12 @@ pub async fn get_history(
         v: vec![],
     };
 
-    let mut db = db_pool.get().await.expect("Unable to get connection from pool!");
+    let mut db = db_pool.get().await.map_err(ApiError::from)?;
 
     let candle_timeframe_seconds = convert_udf_time_to_timestamp_minute(query.resolution).unwrap_or(60) * 60;
     println!("candle_timeframe_seconds: {}", candle_timeframe_seconds);
 
-    let default_price: Option<f64> = match db.query("SELECT price from trades where symbol like $1 AND timestamp < $2 LIMIT 1", &[&query.symbol, &query.from.unwrap_or_default()]).await {
+    let default_price: Option<f64> = match db.query("SELECT price::float8 AS price from trades where symbol like $1 AND timestamp < $2 LIMIT 1", &[&query.symbol, &query.from.unwrap_or_default()]).await {
         Ok(p) => {
             if !p.is_empty() {
                 Some(p[0].try_get("price").unwrap_or_default())
@@ -448,11 +505,11 @@ pub async fn get_history(
             db.query("
             SELECT
                    bucket,
-                   coalesce(open, close)  AS open,
-                   coalesce(high, close)  AS high,
-                   coalesce(low, close)   AS low,
-                   coalesce(close, close) AS close,
-                   coalesce(volume, 0)    AS volume
+                   coalesce(open, close)::float8 AS open,
+                   coalesce(high, close)::float8 AS high,
+                   coalesce(low, close)::float8 AS low,
+                   coalesce(close, close)::float8 AS close,
+                   coalesce(volume, 0)::float8 AS volume
 
             FROM (SELECT time_bucket_gapfill($4, timestamp)     AS bucket,
                          first(price, timestamp)                AS open,
@@ -468,7 +525,7 @@ pub async fn get_history(
                   GROUP BY bucket
                   ORDER BY bucket ASC) AS P",
                      &[&query.symbol, &query.from.unwrap_or_default(), &query.to.unwrap_or_default(), &candle_timeframe_seconds],
-            ).await.unwrap_or_default()
+            ).await.map_err(ApiError::from)?
         }
         Some(countback) => {
             let c = countback as i64;
@@ -476,11 +533,11 @@ pub async fn get_history(
                 "
                 SELECT
                    bucket,
-                   coalesce(open, close)  AS open,
-                   coalesce(high, close)  AS high,
-                   coalesce(low, close)   AS low,
-                   coalesce(close, close) AS close,
-                   coalesce(volume, 0)    AS volume
+                   coalesce(open, close)::float8 AS open,
+                   coalesce(high, close)::float8 AS high,
+                   coalesce(low, close)::float8 AS low,
+                   coalesce(close, close)::float8 AS close,
+                   coalesce(volume, 0)::float8 AS volume
 
                 FROM (SELECT time_bucket_gapfill($4, timestamp)     AS bucket,
                              first(price, timestamp)                AS open,
@@ -497,7 +554,7 @@ pub async fn get_history(
                       ORDER BY bucket ASC
                       LIMIT $5 ) AS P",
                 &[&query.symbol, &query.from.unwrap_or_default(), &query.to.unwrap_or_default(), &candle_timeframe_seconds, &c],
-            ).await.expect("Error querrying using countback")
+            ).await.map_err(ApiError::from)?
         }
     };
 
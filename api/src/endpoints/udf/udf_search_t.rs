@@ -10,4 +10,6 @@ pub struct UdfSearchSymbol {
     pub ticker: String,
     #[serde(rename = "type")]
     pub udf_symbol_type: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logo_urls: Vec<String>,
 }
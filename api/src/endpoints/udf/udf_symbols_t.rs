@@ -20,6 +20,8 @@ pub struct UdfSymbols {
     pub minmovement2: f64,
     pub minmov2: f64,
     pub pricescale: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub logo_urls: Vec<String>,
     pub supported_resolutions: Vec<String>,
     pub has_intraday: bool,
     pub has_daily: bool,
@@ -0,0 +1,147 @@
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::fmt;
+
+use deadpool_postgres::tokio_postgres;
+use diesel::r2d2::PoolError;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use log::{error, warn};
+use warp::{Rejection, Reply};
+use warp::hyper::StatusCode;
+use warp::reject::{InvalidQuery, MethodNotAllowed, Reject};
+use warp::reply::{Json, WithStatus};
+
+use crate::endpoints::responses::response_error::ResponseError;
+
+/// Error of a handler, raised as `warp::reject::custom` and turned into a [`ResponseError`] reply by the recovery.
+#[derive(Debug)]
+pub enum ApiError {
+    /// Parameters of the request are invalid.
+    BadRequest(String),
+    Unauthorized(String),
+    NotFound(String),
+    /// Database not reachable or pool exhausted, worth a retry.
+    Unavailable(String),
+    /// Upstream service like the galaxy api failed.
+    BadGateway(String),
+    Internal(String),
+}
+
+impl ApiError {
+    /// Empty result of a query.
+    pub fn no_data() -> ApiError {
+        ApiError::NotFound("No data found".to_string())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::BadGateway(_) => StatusCode::BAD_GATEWAY,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            ApiError::BadRequest(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::NotFound(message)
+            | ApiError::Unavailable(message)
+            | ApiError::BadGateway(message)
+            | ApiError::Internal(message) => message.as_str(),
+        }
+    }
+
+    pub fn reply(&self) -> WithStatus<Json> {
+        error_reply(self.status(), self.message())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status().as_u16(), self.message())
+    }
+}
+
+impl Reject for ApiError {}
+
+impl From<PoolError> for ApiError {
+    fn from(err: PoolError) -> Self {
+        ApiError::Unavailable(format!("Unable to get connection from pool: {}", err))
+    }
+}
+
+impl From<deadpool_postgres::PoolError> for ApiError {
+    fn from(err: deadpool_postgres::PoolError) -> Self {
+        ApiError::Unavailable(format!("Unable to get connection from pool: {}", err))
+    }
+}
+
+impl From<DieselError> for ApiError {
+    fn from(err: DieselError) -> Self {
+        match err {
+            DieselError::NotFound => ApiError::no_data(),
+            DieselError::DatabaseError(DatabaseErrorKind::ClosedConnection, _) => ApiError::Unavailable(format!("Database connection closed: {}", err)),
+            err => ApiError::Internal(format!("Error querying database: {}", err)),
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for ApiError {
+    fn from(err: tokio_postgres::Error) -> Self {
+        let connection_lost = err.is_closed() || err.source().map_or(false, |source| source.is::<std::io::Error>());
+        if connection_lost {
+            ApiError::Unavailable(format!("Database connection lost: {}", err))
+        } else {
+            ApiError::Internal(format!("Error querying database: {}", err))
+        }
+    }
+}
+
+fn error_reply(status: StatusCode, message: &str) -> WithStatus<Json> {
+    warp::reply::with_status(warp::reply::json(&ResponseError::new(status, message)), status)
+}
+
+fn log_error(err: &ApiError) {
+    if err.status().is_server_error() {
+        error!("{}", err);
+    } else {
+        warn!("{}", err);
+    }
+}
+
+/// Replies to the [`ApiError`] and invalid query of a handler group, passes on all other rejections.
+///
+/// Applied per group so the error of a matched route is replied before a later group of the `or` chain is tried,
+/// while a path of another group still falls through to it.
+pub async fn recover_api_error(rejection: Rejection) -> Result<WithStatus<Json>, Rejection> {
+    if let Some(err) = rejection.find::<ApiError>() {
+        log_error(err);
+        Ok(err.reply())
+    } else if let Some(err) = rejection.find::<InvalidQuery>() {
+        Ok(error_reply(StatusCode::BAD_REQUEST, err.to_string().as_str()))
+    } else {
+        Err(rejection)
+    }
+}
+
+/// Replies to every rejection left over by the routes, unknown paths become a 404.
+pub async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Infallible> {
+    let reply = if rejection.is_not_found() {
+        error_reply(StatusCode::NOT_FOUND, "Not found")
+    } else if let Some(err) = rejection.find::<ApiError>() {
+        log_error(err);
+        err.reply()
+    } else if let Some(err) = rejection.find::<InvalidQuery>() {
+        error_reply(StatusCode::BAD_REQUEST, err.to_string().as_str())
+    } else if rejection.find::<MethodNotAllowed>().is_some() {
+        error_reply(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed")
+    } else {
+        error!("Unhandled rejection: {:?}", rejection);
+        error_reply(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+    };
+    Ok(reply)
+}
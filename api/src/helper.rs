@@ -16,5 +16,11 @@ pub fn with_raw_psql_store(db_pool: deadpool_postgres::Pool) -> impl Filter<Extr
     warp::any().map(move || db_pool.clone())
 }
 
-
-
+/// Compares two secrets without returning at the first differing byte, so the response time does not reveal
+/// how much of a guessed token matches.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
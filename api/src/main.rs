@@ -1,4 +1,4 @@
-use std::{env, net::Ipv4Addr, sync::Arc};
+use std::{env, net::Ipv4Addr, sync::Arc, time::Duration};
 
 use log::info;
 use tokio::net::unix::SocketAddr;
@@ -16,7 +16,9 @@ use warp::{
 use warp::http::Method;
 use warp::http::uri::Port;
 
-use database_psql::connection::create_psql_pool_diesel;
+use database_psql::connection::{create_psql_pool_diesel, create_psql_raw_pool};
+use staratlas::currency_source::MAINNET_RPC_URL;
+use staratlas::symbolstore::{BuilderSymbolStore, shared_symbol_store};
 //use types::trade_t;
 use database_psql::model::Trade;
 use endpoints::udf::udf_config_t;
@@ -26,12 +28,22 @@ use endpoints::udf::udf_symbol_info_t;
 use endpoints::udf::udf_symbols_t;
 
 use crate::endpoints::default::default;
+use crate::endpoints::fees::fees;
+use crate::endpoints::health::health;
 use crate::endpoints::stats::stats;
+use crate::endpoints::symbols::symbols;
 use crate::endpoints::trades::trades;
 use crate::endpoints::udf::udf;
+use crate::endpoints::responses::{response_error, response_trade};
+use crate::error::{handle_rejection, recover_api_error};
+use crate::metrics::traced;
 
 mod endpoints;
+mod error;
 mod helper;
+mod metrics;
+#[cfg(test)]
+mod tests;
 
 #[tokio::main]
 async fn main() {
@@ -59,12 +71,28 @@ async fn main() {
     stats::get_last_timestamp,
     stats::get_first_timestamp,
     stats::get_ranges,
+    stats::get_coverage,
+    fees::get_summary,
+    symbols::post_refresh,
+    symbols::get_symbol,
+    health::get_live,
+    health::get_ready,
     ),
     components(
     schemas(
     trades::VolumeData,
+    fees::FeeSummary,
+    symbols::SymbolRefresh,
+    symbols::SymbolDetails,
+    health::HealthStatus,
+    health::HealthCheck,
+    health::HealthReport,
     database_psql::model::Trade,
+    response_trade::ResponseTrade,
+    response_error::ResponseError,
     database_psql::model::Cursor,
+    database_psql::coverage::Coverage,
+    database_psql::coverage::CoverageInterval,
     udf_config_t::UdfConfig,
     udf_config_t::Exchange,
     udf_config_t::SymbolsType,
@@ -77,7 +105,10 @@ async fn main() {
     (name = "default", description = "Default Data endpoints"),
     (name = "udf", description = "UDF compatible endpoints"),
     (name = "stats", description = "Stats endpoints"),
-    (name = "trades", description = "Trade endpoints")
+    (name = "trades", description = "Trade endpoints"),
+    (name = "fees", description = "Fee endpoints"),
+    (name = "symbols", description = "Symbol store endpoints"),
+    (name = "health", description = "Liveness and readiness endpoints")
     )
     )]
     struct ApiDoc;
@@ -114,18 +145,43 @@ async fn main() {
         .parse::<u16>()
         .unwrap();
 
+    let symbol_refresh_secs = env::var("SYMBOL_REFRESH_SECS")
+        .unwrap_or("600".to_string())
+        .parse::<u64>()
+        .unwrap();
+    let symbol_cache = env::var("SYMBOL_CACHE").unwrap_or("symbol_store.cache.json".to_string());
+    let rpc_url = env::var("RPC_URL").unwrap_or(MAINNET_RPC_URL.to_string());
+    let currency_cache = env::var("CURRENCY_CACHE").unwrap_or("currencies.cache.json".to_string());
+    let symbol_builder = BuilderSymbolStore::cached(symbol_cache.as_str(), rpc_url.as_str(), currency_cache.as_str());
+    let symbol_store = shared_symbol_store(symbol_builder.init().await);
+    symbol_builder.spawn_refresh(symbol_store.clone(), Duration::from_secs(symbol_refresh_secs));
+
     println!("Running on http://{}:{}/docs/", Ipv4Addr::UNSPECIFIED, port);
     let cors = warp::cors()
         .allow_any_origin()
         .allow_methods(&[Method::GET]);
 
+    // shared by the trade handlers and the readiness check
+    let psql_pool = create_psql_pool_diesel();
+    let psql_raw_pool = create_psql_raw_pool();
+    metrics::METRICS.register_diesel_pool("trades", &psql_pool);
+    metrics::METRICS.register_raw_pool("trades_raw", &psql_raw_pool);
+
+    let api = health::handlers(symbol_store.clone(), psql_pool.clone(), psql_raw_pool.clone()).await
+        .or(default::handlers().await.with(cors.clone()))
+        .or(udf::handlers(symbol_store.clone()).await.recover(recover_api_error).with(cors.clone()))
+        .or(stats::handlers().await.recover(recover_api_error).with(cors.clone()))
+        .or(fees::handlers().await.recover(recover_api_error).with(cors.clone()))
+        .or(symbols::handlers(symbol_store, symbol_builder).await.recover(recover_api_error))
+        .or(trades::handlers(psql_pool, psql_raw_pool).await.recover(recover_api_error).with(cors))
+        .recover(handle_rejection)
+        .with(traced());
+
     warp::serve(
         root.or(api_doc)
             .or(swagger_ui)
-            .or(default::handlers().await.with(cors.clone()))
-            .or(udf::handlers().await.with(cors.clone()))
-            .or(stats::handlers().await.with(cors.clone()))
-            .or(trades::handlers().await.with(cors)),
+            .or(metrics::handlers())
+            .or(api),
     )
         .run((Ipv4Addr::UNSPECIFIED, port))
         .await
@@ -0,0 +1,165 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use log::info;
+use once_cell::sync::Lazy;
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder};
+use warp::Filter;
+use warp::log::{Info, Log};
+
+pub static METRICS: Lazy<ApiMetrics> = Lazy::new(ApiMetrics::new);
+
+static REQUEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+static PROCESS_ID: Lazy<u64> = Lazy::new(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
+
+enum PoolRef {
+    Diesel(Pool<ConnectionManager<PgConnection>>),
+    Raw(deadpool_postgres::Pool),
+}
+
+/// Prometheus collectors of the api, served as text on `/metrics`.
+pub struct ApiMetrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    request_seconds: HistogramVec,
+    pool_connections: IntGaugeVec,
+    pool_max_size: IntGaugeVec,
+    pools: Mutex<Vec<(String, PoolRef)>>,
+}
+
+impl ApiMetrics {
+    pub fn new() -> ApiMetrics {
+        let registry = Registry::new_custom(Some("roguemarket_api".to_string()), None).unwrap();
+        let metrics = ApiMetrics {
+            requests: IntCounterVec::new(Opts::new("http_requests_total", "Handled requests per route and status"), &["group", "route", "method", "status"]).unwrap(),
+            request_seconds: HistogramVec::new(HistogramOpts::new("http_request_duration_seconds", "Request latency per route"), &["group", "route", "method"]).unwrap(),
+            pool_connections: IntGaugeVec::new(Opts::new("db_pool_connections", "Open connections per pool and state"), &["pool", "state"]).unwrap(),
+            pool_max_size: IntGaugeVec::new(Opts::new("db_pool_max_size", "Maximum connections per pool"), &["pool"]).unwrap(),
+            pools: Mutex::new(vec![]),
+            registry,
+        };
+
+        metrics.registry.register(Box::new(metrics.requests.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.request_seconds.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.pool_connections.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.pool_max_size.clone())).unwrap();
+        metrics
+    }
+
+    /// Reports the utilization of the diesel pool as `pool`.
+    pub fn register_diesel_pool(&self, pool: &str, db_pool: &Pool<ConnectionManager<PgConnection>>) {
+        self.pools.lock().unwrap().push((pool.to_string(), PoolRef::Diesel(db_pool.clone())));
+    }
+
+    /// Reports the utilization of the deadpool pool as `pool`.
+    pub fn register_raw_pool(&self, pool: &str, db_pool: &deadpool_postgres::Pool) {
+        self.pools.lock().unwrap().push((pool.to_string(), PoolRef::Raw(db_pool.clone())));
+    }
+
+    /// Text exposition of all collectors, the pool gauges are read at this point.
+    pub fn gather(&self) -> String {
+        for (pool, db_pool) in self.pools.lock().unwrap().iter() {
+            let (max_size, connections, idle) = match db_pool {
+                PoolRef::Diesel(db_pool) => {
+                    let state = db_pool.state();
+                    (db_pool.max_size() as i64, state.connections as i64, state.idle_connections as i64)
+                }
+                PoolRef::Raw(db_pool) => {
+                    let status = db_pool.status();
+                    (status.max_size as i64, status.size as i64, status.available.max(0) as i64)
+                }
+            };
+            self.pool_max_size.with_label_values(&[pool]).set(max_size);
+            self.pool_connections.with_label_values(&[pool, "idle"]).set(idle);
+            self.pool_connections.with_label_values(&[pool, "in_use"]).set(connections - idle);
+        }
+
+        let mut buffer = vec![];
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn observe_request(&self, group: &str, route: &str, method: &str, status: u16, seconds: f64) {
+        self.requests.with_label_values(&[group, route, method, status.to_string().as_str()]).inc();
+        self.request_seconds.with_label_values(&[group, route, method]).observe(seconds);
+    }
+}
+
+impl Default for ApiMetrics {
+    fn default() -> Self {
+        ApiMetrics::new()
+    }
+}
+
+/// Path with its parameters replaced by `{param}`, to keep the label count of the route bounded.
+///
+/// Segments which are not lowercase words, like symbols, mints or signatures, are taken as parameters.
+pub fn route_label(path: &str) -> String {
+    let segments: Vec<&str> = path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if segment.len() <= 32 && segment.chars().all(|char| char.is_ascii_lowercase() || char == '_' || char == '-') {
+                segment
+            } else {
+                "{param}"
+            }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+/// Handler group serving `path`, by its first segment, `unknown` for paths no group serves.
+pub fn route_group(path: &str) -> &'static str {
+    match path.split('/').find(|segment| !segment.is_empty()) {
+        Some("info") => "default",
+        Some("udf") => "udf",
+        Some("stats") => "stats",
+        Some("fees") => "fees",
+        Some("symbols") => "symbols",
+        Some("trades") => "trades",
+        Some("health") => "health",
+        _ => "unknown",
+    }
+}
+
+/// Client supplied `x-request-id` or a new id unique within the process.
+fn request_id(info: &Info) -> String {
+    match info.request_headers().get("x-request-id").and_then(|value| value.to_str().ok()) {
+        Some(request_id) => request_id.to_string(),
+        None => format!("{:x}-{:x}", *PROCESS_ID, REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed)),
+    }
+}
+
+/// Access log and request metrics, applied once to the recovered routes so every request is logged with its final
+/// status. The group is derived from the path by [`route_group`].
+pub fn traced() -> Log<impl Fn(Info) + Clone> {
+    warp::log::custom(move |info: Info| {
+        let group = route_group(info.path());
+        let route = route_label(info.path());
+        let status = info.status().as_u16();
+        let seconds = info.elapsed().as_secs_f64();
+        METRICS.observe_request(group, route.as_str(), info.method().as_str(), status, seconds);
+
+        info!(
+            target: "access",
+            "request_id={} group={} method={} path={} route={} status={} latency_ms={:.3} remote={}",
+            request_id(&info),
+            group,
+            info.method(),
+            info.path(),
+            route,
+            status,
+            seconds * 1000.0,
+            info.remote_addr().map(|addr| addr.to_string()).unwrap_or("-".to_string()),
+        );
+    })
+}
+
+pub fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone {
+    warp::path!("metrics")
+        .and(warp::get())
+        .map(|| warp::reply::with_header(METRICS.gather(), "Content-Type", TextEncoder::new().format_type().to_string()))
+}
@@ -0,0 +1,107 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use deadpool_postgres::{Manager, ManagerConfig, RecyclingMethod};
+use deadpool_postgres::tokio_postgres::{Config, NoTls};
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use serde_json::{json, Value};
+use staratlas::symbolstore::shared_symbol_store;
+use staratlas_symbols::symbol_store::{Exchange, SymbolStore};
+use warp::{Filter, Rejection, Reply};
+use warp::http::Response;
+use warp::hyper::body::Bytes;
+
+use crate::endpoints::health::health;
+use crate::endpoints::trades::trades;
+use crate::error::{ApiError, handle_rejection, recover_api_error};
+
+/// Nothing listens on port 1, every connection is refused.
+const UNREACHABLE_DATABASE_URL: &str = "postgres://roguemarket@127.0.0.1:1/roguemarket";
+
+fn unreachable_pools() -> (Pool<ConnectionManager<PgConnection>>, deadpool_postgres::Pool) {
+    let psql_pool = Pool::builder()
+        .connection_timeout(Duration::from_millis(200))
+        .build_unchecked(ConnectionManager::<PgConnection>::new(UNREACHABLE_DATABASE_URL));
+
+    let manager = Manager::from_config(Config::from_str(UNREACHABLE_DATABASE_URL).unwrap(), NoTls, ManagerConfig {
+        recycling_method: RecyclingMethod::Fast
+    });
+    let psql_raw_pool = deadpool_postgres::Pool::builder(manager).max_size(1).build().unwrap();
+    (psql_pool, psql_raw_pool)
+}
+
+fn symbol_store() -> SymbolStore {
+    SymbolStore::new(vec![], vec![], Exchange::galactic_market())
+}
+
+/// Group whose only route fails with an internal error.
+fn failing_handlers() -> impl Filter<Extract=impl Reply, Error=Rejection> + Clone {
+    warp::path!("failing")
+        .and(warp::get())
+        .and_then(|| async { Err::<String, Rejection>(ApiError::Internal("Error querying database".to_string()).into()) })
+}
+
+/// Groups recovered like in `main`, the health group last so its paths fall through the others.
+async fn routes() -> impl Filter<Extract=impl Reply, Error=std::convert::Infallible> + Clone {
+    let (psql_pool, psql_raw_pool) = unreachable_pools();
+
+    failing_handlers().recover(recover_api_error)
+        .or(trades::handlers(psql_pool.clone(), psql_raw_pool.clone()).await.recover(recover_api_error))
+        .or(health::handlers(shared_symbol_store(symbol_store()), psql_pool, psql_raw_pool).await)
+        .recover(handle_rejection)
+}
+
+async fn get(path: &str) -> Response<Bytes> {
+    warp::test::request().method("GET").path(path).reply(&routes().await).await
+}
+
+fn body(response: &Response<Bytes>) -> Value {
+    serde_json::from_slice(response.body()).unwrap()
+}
+
+#[tokio::test]
+async fn test_invalid_query_is_bad_request() {
+    let response = get("/trades/volume?from=yesterday").await;
+
+    assert_eq!(response.status(), 400);
+    let body = body(&response);
+    assert_eq!((&body["s"], &body["status"]), (&json!(1), &json!(400)));
+    assert!(body["errmsg"].as_str().unwrap().starts_with("Invalid query string"));
+}
+
+#[tokio::test]
+async fn test_unknown_route_is_not_found() {
+    let response = get("/unknown").await;
+
+    assert_eq!(response.status(), 404);
+    assert_eq!(body(&response), json!({ "s": 1, "errmsg": "Not found", "status": 404 }));
+}
+
+#[tokio::test]
+async fn test_unreachable_database_is_unavailable() {
+    for path in ["/trades?limit=1", "/trades/volume?currency_mint=ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx&from=0"] {
+        let response = get(path).await;
+
+        assert_eq!(response.status(), 503, "{}", path);
+        let body = body(&response);
+        assert_eq!((&body["s"], &body["status"]), (&json!(1), &json!(503)), "{}", path);
+        assert!(body["errmsg"].as_str().unwrap().starts_with("Unable to get connection from pool"), "{}", path);
+    }
+}
+
+#[tokio::test]
+async fn test_failed_query_is_internal_error() {
+    let response = get("/failing").await;
+
+    assert_eq!(response.status(), 500);
+    assert_eq!(body(&response), json!({ "s": 1, "errmsg": "Error querying database", "status": 500 }));
+}
+
+#[tokio::test]
+async fn test_path_of_another_group_reaches_its_handler() {
+    let response = get("/health/live").await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(body(&response), json!({ "status": "ok", "checks": [] }));
+}
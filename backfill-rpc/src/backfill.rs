@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::process::exit;
+
+use database_psql::db_cursors::{create_cursor, get_cursor, update_cursor};
+use database_psql::db_trades::create_or_update_trade_table;
+use database_psql::model::Cursor;
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use indicatif::{ProgressBar, ProgressStyle};
+use log::info;
+use structopt::StructOpt;
+
+use sa_decoder::PROGRAM_ID;
+use solana_tools::fetcher::fetcher::Fetcher;
+use staratlas_symbols::symbol_store::SymbolStore;
+
+use crate::helper::decode_block;
+
+#[derive(Debug, StructOpt)]
+pub struct BackfillConfig {
+    #[structopt(long = "start-slot")]
+    start_slot: u64,
+    /// Last slot to backfill, `0` backfills up to the most recent slot.
+    #[structopt(long = "stop-slot", default_value = "0")]
+    stop_slot: u64,
+    /// Signature to start paging from, skips the signatures newer than `stop-slot` for old ranges.
+    #[structopt(long = "before-signature")]
+    before_signature: Option<String>,
+    /// Transactions fetched between two cursor updates.
+    #[structopt(long = "batch-size", default_value = "100")]
+    batch_size: usize,
+}
+
+pub async fn run_backfill(config: BackfillConfig, fetcher: &Fetcher, royalties: &HashMap<String, u64>, symbol_store: &SymbolStore, database_pool: &Pool<ConnectionManager<PgConnection>>) {
+    let cursor_id = format!("backfill_rpc_{}_{}", config.start_slot, config.stop_slot);
+    let cursor_db = get_cursor(&mut database_pool.get().expect("Error getting connection"), cursor_id.clone());
+    let mut start_slot = config.start_slot;
+    if cursor_db.len() > 0 {
+        // the last slot is safe to process again, `create_or_update_trade_table` upserts on the per-instruction pk
+        // (signature and instruction index)
+        start_slot = cursor_db[0].block.map(|block| block as u64).unwrap_or(config.start_slot);
+        println!("Cursor (loaded from db) for [{:?}, {:?}]: slot {:?}", config.start_slot, config.stop_slot, start_slot);
+    } else {
+        create_cursor(&mut database_pool.get().expect("Error getting connection"), Cursor {
+            id: cursor_id.clone(),
+            value: None,
+            block: None,
+            start_block: Some(config.start_slot as i64),
+            end_block: Some(config.stop_slot as i64),
+        });
+        println!("Cursor (created) for [{:?}, {:?}]", config.start_slot, config.stop_slot);
+    }
+
+    let stop_slot = if config.stop_slot > 0 { config.stop_slot } else { u64::MAX };
+    println!("\n Fetching signatures of {} between slots {} and {}...\n", PROGRAM_ID, start_slot, config.stop_slot);
+    let signatures = match fetcher.fetch_signatures_between_slots(PROGRAM_ID, start_slot, stop_slot, config.before_signature.clone()).await {
+        Ok(signatures) => signatures,
+        Err(err) => {
+            println!("Fetching signatures failed: {}", err);
+            exit(1);
+        }
+    };
+    info!("Found {} signatures", signatures.len());
+
+    let pb_task = ProgressBar::new(signatures.len() as u64);
+    pb_task.set_style(ProgressStyle::with_template(
+        "[{elapsed_precise}] {bar:50.cyan/blue} {pos:>7}/{len:7} {msg} ",
+    )
+        .unwrap()
+        .progress_chars("=>-"));
+
+    // starts with the currently registered royalties, changes within the range replace them like the store of the substream
+    let mut royalties = royalties.clone();
+
+    for batch in signatures.chunks(config.batch_size.max(1)) {
+        let blocks = match fetcher.fetch_blocks(&batch.to_vec()).await {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                // the cursor still points to the last stored batch, a restart continues from there
+                println!("Backfill stopped: {}", err);
+                exit(1);
+            }
+        };
+        for block in blocks {
+            let slot = block.slot;
+            for mut trade in decode_block(block, &mut royalties, symbol_store) {
+                trade.source = cursor_id.clone();
+                create_or_update_trade_table(&mut database_pool.get().expect("Error getting connection"), trade);
+            }
+            pb_task.set_message(format!("slot {}", slot));
+        }
+
+        //Update cursor
+        let last = batch.last().unwrap();
+        update_cursor(&mut database_pool.get().expect("Error getting connection"), cursor_id.clone(), Cursor {
+            id: cursor_id.clone(),
+            value: Some(last.signature.clone()),
+            block: Some(last.slot as i64),
+            start_block: Some(config.start_slot as i64),
+            end_block: Some(config.stop_slot as i64),
+        });
+        pb_task.inc(batch.len() as u64);
+    }
+
+    pb_task.finish_with_message(format!("Backfill DONE with range {}:{}", config.start_slot, config.stop_slot));
+}
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use database_psql::model::Trade;
+use log::warn;
+use sa_decoder::{apply_royalty, process_blocks};
+use sa_decoder::trade::map_exchange_to_trade;
+use staratlas_symbols::symbol_store::SymbolStore;
+use substreams_solana::pb::sol::v1::Block;
+
+/// Decodes the exchanges of the block into trades.
+///
+/// Royalties registered in the block are added to `royalties` first, the exchanges get the royalty
+/// last registered for their currency.
+pub fn decode_block(block: Block, royalties: &mut HashMap<String, u64>, symbol_store: &SymbolStore) -> Vec<Trade> {
+    let mut exchanges = vec![];
+    let mut warnings = vec![];
+    let mut registered = vec![];
+    process_blocks(block, &mut exchanges, &mut warnings, &mut registered);
+
+    for royalty in registered {
+        royalties.insert(royalty.currency_mint, royalty.royalty);
+    }
+    for warning in warnings {
+        warn!("Skipping instruction {} of {}: {}", warning.instruction_index, warning.signature, warning.reason);
+    }
+
+    let mut trades = vec![];
+    for mut exchange in exchanges {
+        if let Some(royalty) = royalties.get(&exchange.currency_mint) {
+            apply_royalty(&mut exchange, *royalty);
+        }
+        let signature = exchange.signature.clone();
+        match map_exchange_to_trade(exchange, symbol_store) {
+            Ok(trade) => trades.push(trade),
+            Err(err) => warn!("Unable to map exchange {}: {}", signature, err),
+        }
+    }
+    trades
+}
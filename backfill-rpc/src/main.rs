@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use std::collections::HashMap;
+
+use database_psql::connection::create_psql_pool_diesel;
+use log::{info, warn};
+use structopt::StructOpt;
+
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_tools::fetcher::fetcher::{Fetcher, FetcherConfig};
+use solana_tools::fetcher::retry::RetryPolicy;
+use staratlas::currency_source::RpcCurrencySource;
+use staratlas::symbolstore::BuilderSymbolStore;
+
+use crate::backfill::{run_backfill, BackfillConfig};
+use crate::reconcile::{run_verify, VerifyConfig};
+
+mod backfill;
+mod helper;
+mod reconcile;
+mod tests;
+
+#[derive(Debug, StructOpt)]
+struct Config {
+    #[structopt(long = "rpc-url", default_value = "https://api.mainnet-beta.solana.com")]
+    rpc_url: String,
+    /// `confirmed` or `finalized`, getTransaction does not support `processed`.
+    #[structopt(long = "commitment", default_value = "finalized")]
+    commitment: String,
+    #[structopt(long = "requests-per-second", default_value = "10")]
+    requests_per_second: f64,
+    #[structopt(long = "max-concurrent", default_value = "10")]
+    max_concurrent: usize,
+    #[structopt(long = "max-attempts", default_value = "5")]
+    max_attempts: u32,
+    #[structopt(long = "symbol-cache", default_value = "symbol_store.cache.json")]
+    symbol_cache: String,
+    #[structopt(long = "currency-cache", default_value = "currencies.cache.json")]
+    currency_cache: String,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Decodes the marketplace transactions between two slots and writes them into `trades`.
+    Backfill(BackfillConfig),
+    /// Re-decodes sampled slot ranges and diffs them against `trades`.
+    Verify(VerifyConfig),
+}
+
+#[tokio::main]
+async fn main() {
+    //Start-up and init
+    env_logger::init();
+    let config = Config::from_args();
+    info!("Config:\n {:?}", config);
+
+    let database_pool = create_psql_pool_diesel();
+    // royalties are only part of RegisterCurrency and UpdateCurrencyRoyalty, a range without them would decode every trade without royalty
+    let royalties = match (RpcCurrencySource { rpc_url: config.rpc_url.clone() }).royalties().await {
+        Ok(royalties) => royalties,
+        Err(err) => {
+            warn!("Unable to load the registered royalties, only royalties registered within the range are applied: {}", err);
+            HashMap::new()
+        }
+    };
+    let symbol_store = BuilderSymbolStore::cached(config.symbol_cache.as_str(), config.rpc_url.as_str(), config.currency_cache.as_str()).init().await;
+    let fetcher = Fetcher::with_config(config.rpc_url.as_str(), FetcherConfig {
+        commitment: CommitmentConfig::from_str(config.commitment.as_str()).expect("Invalid commitment!"),
+        requests_per_second: config.requests_per_second,
+        burst: config.max_concurrent as u32,
+        max_concurrent: config.max_concurrent,
+        retry: RetryPolicy { max_attempts: config.max_attempts, ..Default::default() },
+    });
+
+    match config.command {
+        Command::Backfill(backfill) => run_backfill(backfill, &fetcher, &royalties, &symbol_store, &database_pool).await,
+        Command::Verify(verify) => run_verify(verify, &fetcher, &royalties, &symbol_store, &database_pool).await,
+    }
+}
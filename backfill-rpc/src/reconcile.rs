@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::process::exit;
+
+use database_psql::db_trades::{get_trades_between_blocks, upsert_trade};
+use database_psql::model::Trade;
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use log::info;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use structopt::StructOpt;
+
+use sa_decoder::PROGRAM_ID;
+use solana_tools::fetcher::fetcher::Fetcher;
+use solana_tools::fetcher::retry::FetcherError;
+use staratlas_symbols::symbol_store::SymbolStore;
+
+use crate::helper::decode_block;
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyConfig {
+    #[structopt(long = "start-slot")]
+    start_slot: u64,
+    #[structopt(long = "stop-slot")]
+    stop_slot: u64,
+    /// Number of slot ranges sampled within `[start-slot, stop-slot]`.
+    #[structopt(long = "samples", default_value = "10")]
+    samples: usize,
+    /// Slots per sampled range.
+    #[structopt(long = "range-size", default_value = "1000")]
+    range_size: u64,
+    /// Seed of the sampling, to verify the same ranges again after a repair.
+    #[structopt(long = "seed")]
+    seed: Option<u64>,
+    /// Signature to start paging from, skips the signatures newer than `stop-slot` for old ranges.
+    #[structopt(long = "before-signature")]
+    before_signature: Option<String>,
+    /// Upserts missing and mismatched trades with the re-decoded values, extra trades are only reported.
+    #[structopt(long = "repair")]
+    repair: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub indexed: String,
+    pub decoded: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub stored: Trade,
+    pub decoded: Trade,
+    pub fields: Vec<FieldDiff>,
+}
+
+/// Result of comparing the trades decoded from RPC against the ones stored in `trades`, matched by `pk`.
+#[derive(Debug, Default)]
+pub struct TradeDiff {
+    /// Decoded but not stored.
+    pub missing: Vec<Trade>,
+    /// Stored but not decoded.
+    pub extra: Vec<Trade>,
+    pub mismatched: Vec<Mismatch>,
+}
+
+impl TradeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+/// Picks up to `samples` ranges of `range_size` slots within `[start_slot, stop_slot]`, newest first.
+///
+/// Overlapping ranges are merged, a range which is smaller than the requested samples is returned as a whole.
+pub fn sample_ranges(start_slot: u64, stop_slot: u64, samples: usize, range_size: u64, seed: u64) -> Vec<(u64, u64)> {
+    let range_size = range_size.max(1);
+    if stop_slot < start_slot || samples == 0 {
+        return vec![];
+    }
+    if stop_slot - start_slot + 1 <= range_size.saturating_mul(samples as u64) {
+        return vec![(start_slot, stop_slot)];
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut starts: Vec<u64> = (0..samples)
+        .map(|_| rng.gen_range(start_slot..=stop_slot - range_size + 1))
+        .collect();
+    starts.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut ranges: Vec<(u64, u64)> = vec![];
+    for start in starts {
+        let end = start + range_size - 1;
+        match ranges.last_mut() {
+            Some(last) if end >= last.0 => last.0 = start,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+/// Compares the fields written by the decoder, the symbol and royalties are left out as they depend on
+/// the symbol store and on the royalties registered before the range.
+pub fn compare_trades(indexed: &Trade, decoded: &Trade) -> Vec<FieldDiff> {
+    let fields: Vec<(&'static str, String, String)> = vec![
+        ("signature", indexed.signature.clone(), decoded.signature.clone()),
+        ("block", indexed.block.to_string(), decoded.block.to_string()),
+        ("timestamp", indexed.timestamp.to_string(), decoded.timestamp.to_string()),
+        ("order_taker", indexed.order_taker.clone(), decoded.order_taker.clone()),
+        ("order_initializer", indexed.order_initializer.clone(), decoded.order_initializer.clone()),
+        ("asset_receiving_wallet", indexed.asset_receiving_wallet.clone(), decoded.asset_receiving_wallet.clone()),
+        ("currency_mint", indexed.currency_mint.clone(), decoded.currency_mint.clone()),
+        ("asset_mint", indexed.asset_mint.clone(), decoded.asset_mint.clone()),
+        ("asset_change", indexed.asset_change.normalized().to_string(), decoded.asset_change.normalized().to_string()),
+        ("currency_change", indexed.currency_change.normalized().to_string(), decoded.currency_change.normalized().to_string()),
+        ("market_fee", indexed.market_fee.normalized().to_string(), decoded.market_fee.normalized().to_string()),
+        ("total_cost", indexed.total_cost.normalized().to_string(), decoded.total_cost.normalized().to_string()),
+        ("price", indexed.price.normalized().to_string(), decoded.price.normalized().to_string()),
+        ("fee_payer", indexed.fee_payer.clone(), decoded.fee_payer.clone()),
+        ("fee_rate", indexed.fee_rate.normalized().to_string(), decoded.fee_rate.normalized().to_string()),
+    ];
+
+    fields.into_iter()
+        .filter(|(_, indexed, decoded)| indexed != decoded)
+        .map(|(field, indexed, decoded)| FieldDiff { field, indexed, decoded })
+        .collect()
+}
+
+/// Source of the missing trades written by `verify --repair`.
+pub const REPAIR_SOURCE: &str = "backfill_rpc_verify";
+
+/// The decoded trade with the symbol, royalties and source of the stored one, which `compare_trades` leaves out.
+pub fn repair_trade(stored: &Trade, decoded: &Trade) -> Trade {
+    Trade {
+        symbol: stored.symbol.clone(),
+        source: stored.source.clone(),
+        royalty: stored.royalty.clone(),
+        royalty_discount: stored.royalty_discount.clone(),
+        ..decoded.clone()
+    }
+}
+
+pub fn diff_trades(decoded: Vec<Trade>, indexed: Vec<Trade>) -> TradeDiff {
+    let mut indexed: HashMap<String, Trade> = indexed.into_iter().map(|trade| (trade.pk.clone(), trade)).collect();
+    let mut diff = TradeDiff::default();
+
+    for trade in decoded {
+        match indexed.remove(&trade.pk) {
+            None => diff.missing.push(trade),
+            Some(stored) => {
+                let fields = compare_trades(&stored, &trade);
+                if !fields.is_empty() {
+                    diff.mismatched.push(Mismatch { stored, decoded: trade, fields });
+                }
+            }
+        }
+    }
+    diff.extra = indexed.into_values().collect();
+    diff.extra.sort_by(|a, b| a.pk.cmp(&b.pk));
+    diff
+}
+
+/// Re-decodes the marketplace transactions of `[start_slot, stop_slot]`.
+///
+/// Returns the trades and the oldest signature of the range, which can be passed as `before` to page
+/// on to an older range without starting at the most recent signature again. `royalties` are the
+/// registered royalties the range starts with.
+pub async fn decode_range(fetcher: &Fetcher, start_slot: u64, stop_slot: u64, before: Option<String>, royalties: &HashMap<String, u64>, symbol_store: &SymbolStore) -> Result<(Vec<Trade>, Option<String>), FetcherError> {
+    let signatures = fetcher.fetch_signatures_between_slots(PROGRAM_ID, start_slot, stop_slot, before).await?;
+    let oldest = signatures.first().map(|signature| signature.signature.clone());
+
+    let mut royalties = royalties.clone();
+    let mut trades = vec![];
+    for block in fetcher.fetch_blocks(&signatures).await? {
+        trades.extend(decode_block(block, &mut royalties, symbol_store));
+    }
+    Ok((trades, oldest))
+}
+
+pub fn print_report(start_slot: u64, stop_slot: u64, diff: &TradeDiff) {
+    println!("[{}, {}] missing={} extra={} mismatched={}", start_slot, stop_slot, diff.missing.len(), diff.extra.len(), diff.mismatched.len());
+    for trade in diff.missing.iter() {
+        println!("  missing   {} (block {})", trade.pk, trade.block);
+    }
+    for trade in diff.extra.iter() {
+        println!("  extra     {} (block {})", trade.pk, trade.block);
+    }
+    for mismatch in diff.mismatched.iter() {
+        for field in mismatch.fields.iter() {
+            println!("  mismatch  {} {}: indexed={} decoded={}", mismatch.decoded.pk, field.field, field.indexed, field.decoded);
+        }
+    }
+}
+
+pub async fn run_verify(config: VerifyConfig, fetcher: &Fetcher, royalties: &HashMap<String, u64>, symbol_store: &SymbolStore, database_pool: &Pool<ConnectionManager<PgConnection>>) {
+    let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let ranges = sample_ranges(config.start_slot, config.stop_slot, config.samples, config.range_size, seed);
+    println!("\n Verifying {} ranges between slots {} and {} (seed {})\n", ranges.len(), config.start_slot, config.stop_slot, seed);
+
+    let mut before = config.before_signature.clone();
+    let mut total = TradeDiff::default();
+    for (start_slot, stop_slot) in ranges {
+        let (decoded, oldest) = match decode_range(fetcher, start_slot, stop_slot, before.clone(), royalties, symbol_store).await {
+            Ok(result) => result,
+            Err(err) => {
+                println!("Verification stopped: {}", err);
+                exit(1);
+            }
+        };
+        if oldest.is_some() {
+            before = oldest;
+        }
+
+        let mut connection = match database_pool.get() {
+            Ok(connection) => connection,
+            Err(err) => {
+                println!("Verification stopped, no database connection: {}", err);
+                exit(1);
+            }
+        };
+        let indexed = get_trades_between_blocks(&mut connection, start_slot as i64, stop_slot as i64);
+        info!("[{}, {}] decoded={} indexed={}", start_slot, stop_slot, decoded.len(), indexed.len());
+        let diff = diff_trades(decoded, indexed);
+        print_report(start_slot, stop_slot, &diff);
+
+        if config.repair {
+            let repaired = diff.missing.iter().map(|trade| Trade { source: REPAIR_SOURCE.to_string(), ..trade.clone() })
+                .chain(diff.mismatched.iter().map(|mismatch| repair_trade(&mismatch.stored, &mismatch.decoded)));
+            for trade in repaired {
+                if let Err(err) = upsert_trade(&mut connection, trade.clone()) {
+                    println!("Repair stopped at {}: {}", trade.pk, err);
+                    exit(1);
+                }
+            }
+        }
+        total.missing.extend(diff.missing);
+        total.extra.extend(diff.extra);
+        total.mismatched.extend(diff.mismatched);
+    }
+
+    println!("\nTotal missing={} extra={} mismatched={}{}", total.missing.len(), total.extra.len(), total.mismatched.len(),
+             if config.repair { " (missing and mismatched repaired)" } else { "" });
+    if !total.is_empty() && !config.repair {
+        exit(2);
+    }
+}
@@ -0,0 +1,109 @@
+//! In-process Solana JSON-RPC server answering the requests made by `solana_tools::Fetcher`.
+//!
+//! `getSignaturesForAddress` pages through the scripted signatures (newest first) and `getTransaction`
+//! returns the scripted transaction of the signature, the first `failures` requests fail with an rpc error.
+#![cfg(test)]
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+use warp::Filter;
+
+struct MockState {
+    signatures: Vec<Value>,
+    transactions: HashMap<String, Value>,
+    failures: Mutex<u32>,
+    requests: Mutex<Vec<String>>,
+}
+
+impl MockState {
+    fn handle(&self, request: Value) -> Value {
+        let method = request["method"].as_str().unwrap_or_default().to_string();
+        let params = &request["params"];
+        self.requests.lock().unwrap().push(method.clone());
+
+        let mut failures = self.failures.lock().unwrap();
+        if *failures > 0 && method != "getVersion" {
+            *failures -= 1;
+            return json!({ "jsonrpc": "2.0", "id": request["id"], "error": { "code": -32005, "message": "Node is behind" } });
+        }
+
+        let result = match method.as_str() {
+            "getVersion" => json!({ "solana-core": "1.14.3", "feature-set": 1 }),
+            "getSignaturesForAddress" => {
+                let config = &params[1];
+                let skip = match config["before"].as_str() {
+                    None => 0,
+                    Some(before) => self.signatures.iter().position(|signature| signature["signature"] == before).map(|index| index + 1).unwrap_or(self.signatures.len()),
+                };
+                let limit = config["limit"].as_u64().unwrap_or(1000) as usize;
+                Value::Array(self.signatures.iter().skip(skip).take(limit).cloned().collect())
+            }
+            "getTransaction" => self.transactions.get(params[0].as_str().unwrap_or_default()).cloned().unwrap_or(Value::Null),
+            _ => return json!({ "jsonrpc": "2.0", "id": request["id"], "error": { "code": -32601, "message": "Method not found" } }),
+        };
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    }
+}
+
+pub struct MockRpc {
+    pub url: String,
+    state: Arc<MockState>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockRpc {
+    /// Starts the server on a random local port, it is stopped when dropped.
+    pub fn start(signatures: Vec<Value>, transactions: HashMap<String, Value>, failures: u32) -> MockRpc {
+        let state = Arc::new(MockState {
+            signatures,
+            transactions,
+            failures: Mutex::new(failures),
+            requests: Mutex::new(vec![]),
+        });
+
+        let handler_state = state.clone();
+        let route = warp::post()
+            .and(warp::body::json())
+            .map(move |request: Value| warp::reply::json(&handler_state.handle(request)));
+
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+        let (address, server) = warp::serve(route).bind_with_graceful_shutdown(([127, 0, 0, 1], 0), async {
+            shutdown_signal.await.ok();
+        });
+        tokio::spawn(server);
+
+        MockRpc { url: format!("http://{}", address), state, shutdown: Some(shutdown) }
+    }
+
+    /// Methods requested so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockRpc {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+pub fn signature(signature: &str, slot: u64) -> Value {
+    json!({ "signature": signature, "slot": slot, "err": null, "memo": null, "blockTime": null, "confirmationStatus": "finalized" })
+}
+
+pub const EXCHANGE_SIGNATURE: &str = "HyKP1yrsHPu75ym8zVD9EKS4KAYPnRcB1HfazyP1Cvh32hFnvuP7gkQBQLhw7qZwTzcxfgpoVKqgaBn6DH7J6X3";
+pub const EXCHANGE_SLOT: u64 = 153357697;
+
+/// Base64 encoded ProcessExchange transaction of the solana_tools samples, AMMO bought for ATLAS in `EXCHANGE_SLOT`.
+pub fn exchange_transaction() -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../libs/solana_tools/tests/samples/input/exchange_base64.in.sample.json");
+    let transactions: Value = serde_json::from_str(fs::read_to_string(path).unwrap().as_str()).unwrap();
+    transactions[0].clone()
+}
@@ -0,0 +1,2 @@
+mod mock_rpc;
+mod test_reconcile;
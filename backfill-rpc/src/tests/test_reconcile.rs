@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use bigdecimal::BigDecimal;
+    use database_psql::model::Trade;
+    use solana_tools::fetcher::fetcher::{Fetcher, FetcherConfig};
+    use solana_tools::fetcher::retry::RetryPolicy;
+    use staratlas_symbols::symbol_store::{Asset, Exchange, SymbolStore};
+
+    use crate::reconcile::{decode_range, diff_trades, repair_trade, sample_ranges};
+    use crate::tests::mock_rpc::{EXCHANGE_SIGNATURE, EXCHANGE_SLOT, exchange_transaction, MockRpc, signature};
+
+    const AMMO_MINT: &str = "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK";
+    const ATLAS_MINT: &str = "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx";
+
+    fn symbol_store() -> SymbolStore {
+        SymbolStore::new(
+            vec![Asset {
+                asset_name: "Ammunition".to_string(),
+                pair_name: "ATLAS".to_string(),
+                description: "".to_string(),
+                asset_type: "resource".to_string(),
+                symbol: "AMMOATLAS".to_string(),
+                mint: AMMO_MINT.to_string(),
+                pair_mint: ATLAS_MINT.to_string(),
+                pricescale: 100000000,
+                metadata: None,
+            }],
+            vec![],
+            Exchange::galactic_market(),
+        )
+    }
+
+    fn generated_signature(seed: u8) -> String {
+        bs58::encode([seed; 64]).into_string()
+    }
+
+    /// One signature above, the exchange and one signature below the range `[153357000, 153358000]`.
+    fn mock_rpc(failures: u32) -> MockRpc {
+        MockRpc::start(
+            vec![
+                signature(generated_signature(9).as_str(), 153360000),
+                signature(EXCHANGE_SIGNATURE, EXCHANGE_SLOT),
+                signature(generated_signature(8).as_str(), 153350000),
+            ],
+            HashMap::from([(EXCHANGE_SIGNATURE.to_string(), exchange_transaction())]),
+            failures,
+        )
+    }
+
+    fn fetcher(rpc: &MockRpc) -> Fetcher {
+        Fetcher::with_config(rpc.url.as_str(), FetcherConfig {
+            requests_per_second: 1000.0,
+            retry: RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(5),
+            },
+            ..Default::default()
+        })
+    }
+
+    async fn decoded_trades() -> Vec<Trade> {
+        let rpc = mock_rpc(0);
+        let (trades, _) = decode_range(&fetcher(&rpc), 153357000, 153358000, None, &HashMap::new(), &symbol_store()).await.unwrap();
+        trades
+    }
+
+    #[tokio::test]
+    async fn test_decode_range_from_rpc() {
+        let rpc = mock_rpc(0);
+
+        let (trades, oldest) = decode_range(&fetcher(&rpc), 153357000, 153358000, None, &HashMap::new(), &symbol_store()).await.unwrap();
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].signature, EXCHANGE_SIGNATURE);
+        assert_eq!(trades[0].symbol, "AMMOATLAS");
+        assert_eq!(trades[0].block, EXCHANGE_SLOT as i64);
+        assert_eq!(trades[0].price, BigDecimal::from_str("0.00215039").unwrap());
+        assert_eq!(trades[0].market_fee, BigDecimal::from_str("0.03225585").unwrap());
+        assert_eq!(trades[0].fee_payer, "seller");
+        assert_eq!(oldest, Some(EXCHANGE_SIGNATURE.to_string()));
+        // only the transaction within the range is fetched
+        assert_eq!(rpc.requests().iter().filter(|method| *method == "getTransaction").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_registered_royalties_are_applied() {
+        let rpc = mock_rpc(0);
+        let royalties = HashMap::from([(ATLAS_MINT.to_string(), 60000u64)]);
+
+        let (trades, _) = decode_range(&fetcher(&rpc), 153357000, 153358000, None, &royalties, &symbol_store()).await.unwrap();
+
+        assert_eq!(trades[0].royalty, BigDecimal::from_str("0.06").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_are_retried() {
+        let rpc = mock_rpc(2);
+
+        let (trades, _) = decode_range(&fetcher(&rpc), 153357000, 153358000, None, &HashMap::new(), &symbol_store()).await.unwrap();
+
+        assert_eq!(trades.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_matching_trades_have_no_diff() {
+        let decoded = decoded_trades().await;
+        let indexed = decoded.clone();
+
+        assert!(diff_trades(decoded, indexed).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_diff_reports_missing_extra_and_mismatched() {
+        let decoded = decoded_trades().await;
+
+        let diff = diff_trades(decoded.clone(), vec![]);
+        assert_eq!(diff.missing.len(), 1);
+        assert_eq!(diff.missing[0].pk, decoded[0].pk);
+
+        let mut mismatched = decoded[0].clone();
+        mismatched.price = BigDecimal::from_str("0.002150390").unwrap();
+        mismatched.market_fee = BigDecimal::from_str("0.03").unwrap();
+        mismatched.asset_receiving_wallet = mismatched.order_initializer.clone();
+        let mut extra = decoded[0].clone();
+        extra.pk = "extra".to_string();
+
+        let diff = diff_trades(decoded, vec![mismatched, extra]);
+        assert!(diff.missing.is_empty());
+        assert_eq!(diff.extra.iter().map(|trade| trade.pk.as_str()).collect::<Vec<_>>(), vec!["extra"]);
+        assert_eq!(diff.mismatched.len(), 1);
+        // equal decimals with a different scale are no mismatch
+        assert_eq!(diff.mismatched[0].fields.iter().map(|field| field.field).collect::<Vec<_>>(), vec!["asset_receiving_wallet", "market_fee"]);
+        assert_eq!(diff.mismatched[0].fields[1].indexed, "0.03");
+        assert_eq!(diff.mismatched[0].fields[1].decoded, "0.03225585");
+    }
+
+    #[tokio::test]
+    async fn test_repair_keeps_the_stored_symbol_and_royalties() {
+        let decoded = decoded_trades().await;
+        let mut stored = decoded[0].clone();
+        stored.symbol = "AMMOATLAS-CUSTOM".to_string();
+        stored.royalty = BigDecimal::from_str("0.06").unwrap();
+        stored.royalty_discount = BigDecimal::from_str("0.25").unwrap();
+        stored.market_fee = BigDecimal::from_str("0.03").unwrap();
+        stored.source = "sa-trades_0_0".to_string();
+
+        let diff = diff_trades(decoded.clone(), vec![stored.clone()]);
+        let repaired = repair_trade(&diff.mismatched[0].stored, &diff.mismatched[0].decoded);
+
+        assert_eq!(repaired.market_fee, decoded[0].market_fee);
+        assert_eq!(repaired.symbol, stored.symbol);
+        assert_eq!(repaired.royalty, stored.royalty);
+        assert_eq!(repaired.royalty_discount, stored.royalty_discount);
+        assert_eq!(repaired.source, stored.source);
+    }
+
+    #[test]
+    fn test_sample_ranges() {
+        let ranges = sample_ranges(1000, 100000, 5, 100, 42);
+
+        assert!(!ranges.is_empty() && ranges.len() <= 5);
+        assert_eq!(ranges, sample_ranges(1000, 100000, 5, 100, 42));
+        for (index, (start, end)) in ranges.iter().enumerate() {
+            assert!(*start >= 1000 && *end <= 100000 && start <= end);
+            if index > 0 {
+                // newest first and not overlapping
+                assert!(*end < ranges[index - 1].0);
+            }
+        }
+
+        assert_eq!(sample_ranges(1000, 1200, 5, 100, 42), vec![(1000, 1200)]);
+        assert_eq!(sample_ranges(1000, 999, 5, 100, 42), vec![]);
+    }
+}
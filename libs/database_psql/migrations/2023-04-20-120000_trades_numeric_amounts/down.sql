@@ -0,0 +1,6 @@
+ALTER TABLE trades
+    ALTER COLUMN asset_change TYPE float8,
+    ALTER COLUMN currency_change TYPE float8,
+    ALTER COLUMN market_fee TYPE float8,
+    ALTER COLUMN total_cost TYPE float8,
+    ALTER COLUMN price TYPE float8
@@ -0,0 +1,6 @@
+ALTER TABLE trades
+    ALTER COLUMN asset_change TYPE NUMERIC USING asset_change::NUMERIC,
+    ALTER COLUMN currency_change TYPE NUMERIC USING currency_change::NUMERIC,
+    ALTER COLUMN market_fee TYPE NUMERIC USING market_fee::NUMERIC,
+    ALTER COLUMN total_cost TYPE NUMERIC USING total_cost::NUMERIC,
+    ALTER COLUMN price TYPE NUMERIC USING price::NUMERIC
@@ -0,0 +1,5 @@
+ALTER TABLE trades
+    DROP COLUMN fee_payer,
+    DROP COLUMN fee_rate,
+    DROP COLUMN royalty,
+    DROP COLUMN royalty_discount
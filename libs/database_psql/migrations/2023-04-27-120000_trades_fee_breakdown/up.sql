@@ -0,0 +1,5 @@
+ALTER TABLE trades
+    ADD COLUMN fee_payer        TEXT    NOT NULL DEFAULT 'unknown',
+    ADD COLUMN fee_rate         NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN royalty          NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN royalty_discount NUMERIC NOT NULL DEFAULT 0
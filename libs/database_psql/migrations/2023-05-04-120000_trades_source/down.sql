@@ -0,0 +1,4 @@
+DROP INDEX trades_source_block_idx;
+
+ALTER TABLE trades
+    DROP COLUMN source;
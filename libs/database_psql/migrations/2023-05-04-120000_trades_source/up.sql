@@ -0,0 +1,5 @@
+-- cursor id of the stream which wrote the trade, an undo only reverts the trades of its own stream
+ALTER TABLE trades
+    ADD COLUMN source TEXT NOT NULL DEFAULT '';
+
+CREATE INDEX trades_source_block_idx ON trades (source, block);
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::Cursor;
+
+/// Half open block interval `[start, end)`.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInterval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl BlockInterval {
+    pub fn new(start: u64, end: u64) -> BlockInterval {
+        BlockInterval { start, end }
+    }
+
+    pub fn blocks(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct CoverageInterval {
+    pub start: u64,
+    pub end: u64,
+    pub blocks: u64,
+    pub percent: f64,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct Coverage {
+    pub start: u64,
+    pub end: u64,
+    pub covered_percent: f64,
+    pub missing_percent: f64,
+    pub covered: Vec<CoverageInterval>,
+    pub missing: Vec<CoverageInterval>,
+}
+
+/// Whether the cursor was written by a substream of `module_name`, its id is `{module}_{start}_{end}`.
+///
+/// Cursors of other modules and the `backfill_rpc_*` cursors of the RPC backfill cover blocks this module never
+/// streamed.
+pub fn is_module_cursor(cursor: &Cursor, module_name: &str) -> bool {
+    cursor.id.strip_prefix(module_name)
+        .and_then(|rest| rest.strip_prefix('_'))
+        .and_then(|range| range.split_once('_'))
+        .map_or(false, |(start, end)| [start, end].iter().all(|bound| !bound.is_empty() && bound.chars().all(|char| char.is_ascii_digit())))
+}
+
+/// The cursors of `module_name`, see [`is_module_cursor`].
+pub fn module_cursors(cursors: &[Cursor], module_name: &str) -> Vec<Cursor> {
+    cursors.iter().filter(|cursor| is_module_cursor(cursor, module_name)).cloned().collect()
+}
+
+/// Blocks a cursor has processed, from its `start_block` up to and including its last `block`.
+///
+/// A cursor without a `block` has not covered anything yet, for a bounded cursor the interval ends at `end_block`.
+pub fn cursor_interval(cursor: &Cursor) -> Option<BlockInterval> {
+    let start = cursor.start_block? as u64;
+    let mut end = cursor.block? as u64 + 1;
+    if let Some(end_block) = cursor.end_block.filter(|end_block| *end_block > 0) {
+        end = end.min(end_block as u64);
+    }
+    (end > start).then(|| BlockInterval::new(start, end))
+}
+
+/// Sorts the intervals and merges overlapping or adjacent ones.
+pub fn merge_intervals(mut intervals: Vec<BlockInterval>) -> Vec<BlockInterval> {
+    intervals.retain(|interval| interval.blocks() > 0);
+    intervals.sort_by_key(|interval| interval.start);
+
+    let mut merged: Vec<BlockInterval> = vec![];
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+/// Covered intervals of all cursors, merged.
+pub fn covered_intervals(cursors: &[Cursor]) -> Vec<BlockInterval> {
+    merge_intervals(cursors.iter().filter_map(cursor_interval).collect())
+}
+
+/// Parts of `window` not within the (merged) `covered` intervals.
+pub fn missing_intervals(covered: &[BlockInterval], window: BlockInterval) -> Vec<BlockInterval> {
+    let mut missing = vec![];
+    let mut position = window.start;
+    for interval in covered {
+        if interval.end <= position || interval.start >= window.end {
+            continue;
+        }
+        if interval.start > position {
+            missing.push(BlockInterval::new(position, interval.start));
+        }
+        position = position.max(interval.end);
+    }
+    if position < window.end {
+        missing.push(BlockInterval::new(position, window.end));
+    }
+    missing
+}
+
+/// From the first cursor start to the last covered block, `None` if nothing was covered yet.
+pub fn coverage_window(cursors: &[Cursor]) -> Option<BlockInterval> {
+    let start = cursors.iter().filter_map(|cursor| cursor.start_block).min()? as u64;
+    let end = covered_intervals(cursors).last()?.end;
+    (end > start).then(|| BlockInterval::new(start, end))
+}
+
+/// Covered and missing intervals within `window` with their share of the window in percent.
+pub fn compute_coverage(cursors: &[Cursor], window: BlockInterval) -> Coverage {
+    let total = window.blocks();
+    let to_coverage = |interval: BlockInterval| CoverageInterval {
+        start: interval.start,
+        end: interval.end,
+        blocks: interval.blocks(),
+        percent: percent(interval.blocks(), total),
+    };
+
+    let missing = missing_intervals(&covered_intervals(cursors), window);
+    let covered: Vec<BlockInterval> = covered_intervals(cursors)
+        .into_iter()
+        .filter(|interval| interval.end > window.start && interval.start < window.end)
+        .map(|interval| BlockInterval::new(interval.start.max(window.start), interval.end.min(window.end)))
+        .collect();
+
+    let covered_blocks: u64 = covered.iter().map(BlockInterval::blocks).sum();
+    let missing_blocks: u64 = missing.iter().map(BlockInterval::blocks).sum();
+    Coverage {
+        start: window.start,
+        end: window.end,
+        covered_percent: percent(covered_blocks, total),
+        missing_percent: percent(missing_blocks, total),
+        covered: covered.into_iter().map(to_coverage).collect(),
+        missing: missing.into_iter().map(to_coverage).collect(),
+    }
+}
+
+fn percent(blocks: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    blocks as f64 * 100.0 / total as f64
+}
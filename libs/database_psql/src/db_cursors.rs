@@ -36,3 +36,12 @@ pub fn update_cursor(connection: &mut PgConnection, name_id: String, new_cursor:
         .expect("Error updating Cursor!");
     return cursor_db;
 }
+
+pub fn get_cursors(connection: &mut PgConnection) -> Vec<Cursor> {
+    use crate::schema::cursors::dsl::*;
+    let cursor_db: Vec<Cursor> = cursors
+        .order(id.asc())
+        .load::<Cursor>(connection)
+        .expect("Error loading cursors");
+    return cursor_db;
+}
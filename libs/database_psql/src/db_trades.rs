@@ -25,4 +25,63 @@ pub fn create_or_update_trade_table(connection: &mut PgConnection, data: Trade)
             .get_result::<Trade>(connection)
             .expect("Error inserting trade into DB!");
     }
-}
\ No newline at end of file
+}
+
+/// Inserts the trade or overwrites the stored row with the same `pk`.
+pub fn upsert_trade(connection: &mut PgConnection, data: Trade) -> QueryResult<Trade> {
+    use crate::schema::trades::dsl::*;
+    use crate::schema::trades;
+
+    diesel::insert_into(trades::table)
+        .values(&data)
+        .on_conflict(pk)
+        .do_update()
+        .set(&data)
+        .get_result::<Trade>(connection)
+}
+
+/// Trades stored for the blocks `[start_block, end_block]`.
+pub fn get_trades_between_blocks(connection: &mut PgConnection, start_block: i64, end_block: i64) -> Vec<Trade> {
+    use crate::schema::trades::dsl::*;
+
+    trades
+        .filter(block.ge(start_block))
+        .filter(block.le(end_block))
+        .load::<Trade>(connection)
+        .expect("Unable to load trades from db!")
+}
+
+/// Removes the trades `stream` wrote for reverted blocks, `block >= from_block`, returns the number of deleted trades.
+///
+/// Trades of other streams, e.g. backfill segments above the undo, are kept.
+pub fn delete_trades_from_block(connection: &mut PgConnection, stream: &str, from_block: i64) -> QueryResult<usize> {
+    use crate::schema::trades::dsl::*;
+
+    diesel::delete(trades
+        .filter(source.eq(stream))
+        .filter(block.ge(from_block)))
+        .execute(connection)
+}
+
+/// Distinct (asset_mint, currency_mint) pairs of trades stored with an `UNKNOWN:<mint>` symbol.
+pub fn get_unknown_symbol_pairs(connection: &mut PgConnection) -> QueryResult<Vec<(String, String)>> {
+    use crate::schema::trades::dsl::*;
+
+    trades
+        .filter(symbol.like("UNKNOWN:%"))
+        .select((asset_mint, currency_mint))
+        .distinct()
+        .load::<(String, String)>(connection)
+}
+
+/// Replaces the `UNKNOWN:<mint>` symbol of all trades of the pair, returns the number of updated trades.
+pub fn update_unknown_symbol(connection: &mut PgConnection, asset: &str, currency: &str, new_symbol: &str) -> QueryResult<usize> {
+    use crate::schema::trades::dsl::*;
+
+    diesel::update(trades
+        .filter(asset_mint.eq(asset))
+        .filter(currency_mint.eq(currency))
+        .filter(symbol.like("UNKNOWN:%")))
+        .set(symbol.eq(new_symbol))
+        .execute(connection)
+}
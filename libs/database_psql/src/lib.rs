@@ -3,3 +3,4 @@ pub mod model;
 pub mod connection;
 pub mod db_cursors;
 pub mod db_trades;
+pub mod coverage;
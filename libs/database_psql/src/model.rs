@@ -1,3 +1,4 @@
+use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use chrono::NaiveDateTime;
 use diesel::{prelude::*, sql_query};
@@ -8,8 +9,8 @@ use utoipa::ToSchema;
 use crate::schema::cursors;
 use crate::schema::trades;
 
-#[table_name = "cursors"]
-#[derive(Queryable, Insertable, Serialize, Deserialize, ToSchema, Debug)]
+#[derive(Queryable, Insertable, Serialize, Deserialize, ToSchema, Debug, Clone)]
+#[diesel(table_name = cursors)]
 pub struct Cursor {
     pub id: String,
     pub value: Option<String>,
@@ -18,8 +19,8 @@ pub struct Cursor {
     pub end_block: Option<i64>,
 }
 
-#[table_name = "trades"]
-#[derive(Queryable, QueryableByName, Insertable, Serialize, Deserialize, ToSchema, Debug)]
+#[derive(Queryable, QueryableByName, Insertable, AsChangeset, Serialize, Deserialize, ToSchema, Debug, Clone)]
+#[diesel(table_name = trades)]
 pub struct Trade {
     pub pk: String,
     pub signature: String,
@@ -32,11 +33,25 @@ pub struct Trade {
     pub currency_mint: String,
     pub asset_mint: String,
     pub asset_receiving_wallet: String,
-    pub asset_change: f64,
-    pub currency_change: f64,
-    pub market_fee: f64,
-    pub total_cost: f64,
-    pub price: f64,
+    #[schema(value_type = String)]
+    pub asset_change: BigDecimal,
+    #[schema(value_type = String)]
+    pub currency_change: BigDecimal,
+    #[schema(value_type = String)]
+    pub market_fee: BigDecimal,
+    #[schema(value_type = String)]
+    pub total_cost: BigDecimal,
+    #[schema(value_type = String)]
+    pub price: BigDecimal,
+    pub fee_payer: String,
+    #[schema(value_type = String)]
+    pub fee_rate: BigDecimal,
+    #[schema(value_type = String)]
+    pub royalty: BigDecimal,
+    #[schema(value_type = String)]
+    pub royalty_discount: BigDecimal,
+    /// Cursor id of the stream which wrote the trade.
+    pub source: String,
 }
 
 
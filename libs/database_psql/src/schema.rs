@@ -23,11 +23,16 @@ diesel::table! {
         currency_mint -> Text,
         asset_mint -> Text,
         asset_receiving_wallet -> Text,
-        asset_change -> Float8,
-        currency_change -> Float8,
-        market_fee -> Float8,
-        total_cost -> Float8,
-        price -> Float8,
+        asset_change -> Numeric,
+        currency_change -> Numeric,
+        market_fee -> Numeric,
+        total_cost -> Numeric,
+        price -> Numeric,
+        fee_payer -> Text,
+        fee_rate -> Numeric,
+        royalty -> Numeric,
+        royalty_discount -> Numeric,
+        source -> Text,
     }
 }
 
@@ -26,3 +26,22 @@ pub fn write_file_slim<T: serde::Serialize>(path: &str, data: &T) {
     let mut file = File::create(path).unwrap();
     let _ = write!(file, "{}", serde_json::to_string(data).unwrap());
 }
+
+/// Same format as `read_file`, but returns the error instead of panicking.
+pub fn try_read_file<T>(path: &str) -> std::io::Result<T>
+    where
+        T: DeserializeOwned,
+{
+    let contents = std::fs::read_to_string(path)?;
+    let data = serde_json::from_str::<T>(contents.as_str())?;
+
+    Ok(data)
+}
+
+/// Same format as `write_file`, the file is written next to `path` first and then renamed, so a
+/// crash never leaves a truncated file behind.
+pub fn try_write_file<T: serde::Serialize>(path: &str, data: &T) -> std::io::Result<()> {
+    let tmp_path = format!("{}.tmp", path);
+    std::fs::write(tmp_path.as_str(), serde_json::to_string_pretty(data)?)?;
+    std::fs::rename(tmp_path.as_str(), path)
+}
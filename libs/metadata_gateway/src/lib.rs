@@ -1,35 +1,173 @@
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Mutex;
 
 use borsh::de::BorshDeserialize;
+use log::warn;
 use mpl_token_metadata::solana_program::pubkey::Pubkey;
 use mpl_token_metadata::state::Metadata;
-use solana_client::rpc_client::RpcClient;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use thiserror::Error;
 
+use helper::filehelper::{try_read_file, try_write_file};
+
+mod mock_rpc;
 mod test;
 
+/// getMultipleAccounts accepts at most 100 keys per call.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
 
-pub fn request_metadata_symbol(rpc: String, mint: String) -> String {
-    let client = RpcClient::new(rpc);
-    let mint = Pubkey::from_str(mint.as_str()).unwrap();
+#[derive(Error, Debug)]
+pub enum MetadataError {
+    #[error("invalid mint {0}")]
+    InvalidMint(String),
+    #[error("rpc request failed: {0}")]
+    Rpc(String),
+    #[error("no metadata account for mint {0}")]
+    NotFound(String),
+    #[error("metadata of {0} could not be decoded: {1}")]
+    Decode(String, std::io::Error),
+}
 
-    let metadata = get_metadata(&client, &mint).unwrap();
-    let symbol = parse_symbol(metadata);
+/// The parts of the Metaplex `Metadata` account we use, with the padding of the strings removed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadata {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub update_authority: String,
+    /// Collection mint, only set for verified collections.
+    pub collection: Option<String>,
+}
 
-    symbol
+/// Resolves Metaplex token metadata of mints.
+///
+/// Mints are fetched in batches with `getMultipleAccounts`, results are kept in memory and, when a
+/// cache path is set, written to disk so a restart does not fetch them again.
+pub struct MetadataGateway {
+    client: RpcClient,
+    cache: Mutex<HashMap<String, TokenMetadata>>,
+    cache_path: Option<String>,
 }
 
-fn get_metadata(
-    rpc: &RpcClient,
-    mint_address: &Pubkey,
-) -> Result<Metadata, Box<dyn std::error::Error>> {
-    let (meta_addr, _) = mpl_token_metadata::pda::find_metadata_account(&mint_address);
-    let metadata_account = rpc.get_account(&meta_addr).unwrap();
-    let acct = &mut &metadata_account.data[..];
+impl MetadataGateway {
+    pub fn new(rpc_url: &str) -> MetadataGateway {
+        MetadataGateway {
+            client: RpcClient::new(rpc_url.to_string()),
+            cache: Mutex::new(HashMap::new()),
+            cache_path: None,
+        }
+    }
+
+    /// Same as `new`, the cache is read from and written to `cache_path`. A missing or unreadable
+    /// file starts with an empty cache.
+    pub fn cached(rpc_url: &str, cache_path: &str) -> MetadataGateway {
+        let cache = try_read_file::<HashMap<String, TokenMetadata>>(cache_path).unwrap_or_default();
+
+        MetadataGateway {
+            client: RpcClient::new(rpc_url.to_string()),
+            cache: Mutex::new(cache),
+            cache_path: Some(cache_path.to_string()),
+        }
+    }
+
+    pub async fn metadata(&self, mint: &str) -> Result<TokenMetadata, MetadataError> {
+        let mut results = self.metadata_batch(&[mint.to_string()]).await;
+        results.remove(mint).unwrap_or_else(|| Err(MetadataError::Rpc(format!("no result for mint {}", mint))))
+    }
+
+    pub async fn symbol(&self, mint: &str) -> Result<String, MetadataError> {
+        self.metadata(mint).await.map(|metadata| metadata.symbol)
+    }
+
+    /// Metadata of every mint in `mints`, only mints missing in the cache are requested.
+    pub async fn metadata_batch(&self, mints: &[String]) -> HashMap<String, Result<TokenMetadata, MetadataError>> {
+        let mut results = HashMap::new();
+        let mut missing: Vec<(String, Pubkey)> = vec![];
+        {
+            let cache = self.cache.lock().unwrap();
+            for mint in mints {
+                if results.contains_key(mint) || missing.iter().any(|(missing_mint, _)| missing_mint == mint) {
+                    continue;
+                }
+                if let Some(metadata) = cache.get(mint) {
+                    results.insert(mint.clone(), Ok(metadata.clone()));
+                    continue;
+                }
+                match Pubkey::from_str(mint.as_str()) {
+                    Ok(pubkey) => missing.push((mint.clone(), pubkey)),
+                    Err(_) => {
+                        results.insert(mint.clone(), Err(MetadataError::InvalidMint(mint.clone())));
+                    }
+                }
+            }
+        }
 
-    Metadata::deserialize(acct).map_err(|e| e.into())
+        let mut fetched = 0;
+        for chunk in missing.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+            let addresses: Vec<Pubkey> = chunk
+                .iter()
+                .map(|(_, mint)| mpl_token_metadata::pda::find_metadata_account(mint).0)
+                .collect();
+
+            match self.client.get_multiple_accounts(addresses.as_slice()).await {
+                Ok(accounts) => {
+                    for (position, (mint, _)) in chunk.iter().enumerate() {
+                        // a response with fewer accounts than requested leaves the remaining mints unresolved
+                        let result = match accounts.get(position) {
+                            Some(Some(account)) => parse_metadata(mint, account.data.as_slice()),
+                            Some(None) => Err(MetadataError::NotFound(mint.clone())),
+                            None => Err(MetadataError::Rpc(format!("getMultipleAccounts returned {} of {} accounts", accounts.len(), chunk.len()))),
+                        };
+                        if let Ok(metadata) = &result {
+                            self.cache.lock().unwrap().insert(mint.clone(), metadata.clone());
+                            fetched += 1;
+                        }
+                        results.insert(mint.clone(), result);
+                    }
+                }
+                Err(err) => {
+                    for (mint, _) in chunk {
+                        results.insert(mint.clone(), Err(MetadataError::Rpc(err.to_string())));
+                    }
+                }
+            }
+        }
+
+        if fetched > 0 {
+            self.write_cache();
+        }
+        results
+    }
+
+    fn write_cache(&self) {
+        if let Some(cache_path) = &self.cache_path {
+            let cache = self.cache.lock().unwrap().clone();
+            if let Err(err) = try_write_file(cache_path.as_str(), &cache) {
+                warn!("Unable to write metadata cache {}: {}", cache_path, err);
+            }
+        }
+    }
 }
 
-fn parse_symbol(metadata: Metadata) -> String {
-    let symbol_str = metadata.data.symbol.trim_matches(char::from(0));
-    symbol_str.to_string()
-}
\ No newline at end of file
+/// Decodes the Metaplex `Metadata` account `data` of `mint`.
+pub fn parse_metadata(mint: &str, data: &[u8]) -> Result<TokenMetadata, MetadataError> {
+    let metadata = Metadata::deserialize(&mut &data[..]).map_err(|err| MetadataError::Decode(mint.to_string(), err))?;
+
+    Ok(TokenMetadata {
+        mint: mint.to_string(),
+        name: trim_padding(metadata.data.name.as_str()),
+        symbol: trim_padding(metadata.data.symbol.as_str()),
+        uri: trim_padding(metadata.data.uri.as_str()),
+        update_authority: metadata.update_authority.to_string(),
+        collection: metadata.collection
+            .filter(|collection| collection.verified)
+            .map(|collection| collection.key.to_string()),
+    })
+}
+
+fn trim_padding(value: &str) -> String {
+    value.trim_matches(char::from(0)).to_string()
+}
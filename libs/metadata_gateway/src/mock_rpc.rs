@@ -0,0 +1,110 @@
+//! In-process JSON-RPC server answering `getMultipleAccounts` from a fixed set of accounts.
+//!
+//! Only the methods called by `MetadataGateway` are implemented, every other method fails.
+#![cfg(test)]
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+use hyper::{Body, Request, Response, Server};
+use hyper::service::{make_service_fn, service_fn};
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+
+struct MockState {
+    accounts: HashMap<String, Vec<u8>>,
+    /// Number of keys of every `getMultipleAccounts` call.
+    batches: Mutex<Vec<usize>>,
+    /// Answers with at most this many accounts, like a misbehaving node.
+    max_accounts: Mutex<Option<usize>>,
+}
+
+pub struct MockRpc {
+    pub url: String,
+    state: Arc<MockState>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockRpc {
+    /// Starts the server on a random local port with `accounts` keyed by address, it is stopped when dropped.
+    pub fn start(accounts: HashMap<String, Vec<u8>>) -> MockRpc {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(MockState { accounts, batches: Mutex::new(vec![]), max_accounts: Mutex::new(None) });
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request)))
+            }
+        });
+
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+        tokio::spawn(
+            Server::from_tcp(listener).unwrap()
+                .serve(make_service)
+                .with_graceful_shutdown(async {
+                    shutdown_signal.await.ok();
+                })
+        );
+
+        MockRpc { url, state, shutdown: Some(shutdown) }
+    }
+
+    pub fn batches(&self) -> Vec<usize> {
+        self.state.batches.lock().unwrap().clone()
+    }
+
+    pub fn truncate_responses(&self, max_accounts: usize) {
+        *self.state.max_accounts.lock().unwrap() = Some(max_accounts);
+    }
+}
+
+impl Drop for MockRpc {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+async fn handle(state: Arc<MockState>, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let request: Value = serde_json::from_slice(&body).unwrap();
+
+    let response = match request["method"].as_str() {
+        Some("getMultipleAccounts") => {
+            let keys = request["params"][0].as_array().unwrap();
+            state.batches.lock().unwrap().push(keys.len());
+
+            let max_accounts = state.max_accounts.lock().unwrap().unwrap_or(keys.len());
+            let accounts: Vec<Value> = keys
+                .iter()
+                .take(max_accounts)
+                .map(|key| match state.accounts.get(key.as_str().unwrap()) {
+                    Some(data) => json!({
+                        "lamports": 5616720,
+                        "data": [base64::encode(data), "base64"],
+                        "owner": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+                        "executable": false,
+                        "rentEpoch": 361,
+                        "space": data.len(),
+                    }),
+                    None => Value::Null,
+                })
+                .collect();
+            json!({ "jsonrpc": "2.0", "id": request["id"], "result": { "context": { "slot": 190000000 }, "value": accounts } })
+        }
+        // queried by the client to pick the account encoding
+        Some("getVersion") => json!({ "jsonrpc": "2.0", "id": request["id"], "result": { "solana-core": "1.14.17", "feature-set": 1879391783 } }),
+        _ => json!({ "jsonrpc": "2.0", "id": request["id"], "error": { "code": -32601, "message": "Method not found" } }),
+    };
+
+    Ok(Response::builder()
+        .header("content-type", "application/json")
+        .body(Body::from(response.to_string()))
+        .unwrap())
+}
@@ -1,43 +1,161 @@
-use mpl_token_metadata::state::Metadata;
-
 #[cfg(test)]
 mod tests {
-    use std::fs::metadata;
+    use std::collections::HashMap;
     use std::str::FromStr;
 
     use mpl_token_metadata::solana_program::pubkey::Pubkey;
-    use mpl_token_metadata::state::AuthorityType::Metadata;
-    use solana_client::rpc_client::RpcClient;
 
-    use crate::{get_metadata, parse_symbol, request_metadata_symbol};
+    use crate::{MetadataError, MetadataGateway, parse_metadata, TokenMetadata};
+    use crate::mock_rpc::MockRpc;
 
-    #[test]
-    fn is_AMMO() {
-        let symbol = request_metadata_symbol(
-            "https://api.mainnet-beta.solana.com".to_string(),
-            "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK".to_string());
+    const AMMO: &str = "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK";
+    const LSTAND: &str = "DB8CSxoakPRtXhHcc2cA3iETWfGaYY6zE2T8huJTE2Nw";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const NO_METADATA: &str = "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx";
+    const UPDATE_AUTHORITY: &str = "NPCxfjPxh6pvRJbGbWZjxfkqWfGBvKkqPbtiJar3mom";
+    const COLLECTION: &str = "SAcoLLxbqDkE1rkKtbSYn9onUdYaiPhP2fiT3jDLsmU";
 
-        assert_eq!(symbol, "AMMO");
+    fn borsh_string(data: &mut Vec<u8>, value: &str, padded: usize) {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.resize(padded, 0);
+        data.extend((bytes.len() as u32).to_le_bytes());
+        data.extend(bytes);
     }
 
-    #[test]
-    fn is_LSTAND() {
-        let symbol = request_metadata_symbol(
-            "https://api.mainnet-beta.solana.com".to_string(),
-            "DB8CSxoakPRtXhHcc2cA3iETWfGaYY6zE2T8huJTE2Nw".to_string());
+    /// Metaplex `Metadata` account as stored on-chain, strings padded to their max length.
+    fn metadata_account(mint: &str, name: &str, symbol: &str, collection: Option<(&str, bool)>) -> Vec<u8> {
+        let mut data = vec![4u8];
+        data.extend(Pubkey::from_str(UPDATE_AUTHORITY).unwrap().to_bytes());
+        data.extend(Pubkey::from_str(mint).unwrap().to_bytes());
+        borsh_string(&mut data, name, 32);
+        borsh_string(&mut data, symbol, 10);
+        borsh_string(&mut data, format!("https://galaxy.staratlas.com/nfts/{}", mint).as_str(), 200);
+        data.extend(500u16.to_le_bytes());
+        // creators, primary sale happened, is mutable, edition nonce, token standard
+        data.extend([0u8, 1, 1, 0, 0]);
+        match collection {
+            Some((key, verified)) => {
+                data.extend([1u8, verified as u8]);
+                data.extend(Pubkey::from_str(key).unwrap().to_bytes());
+            }
+            None => data.push(0),
+        }
+        // uses, collection details, programmable config
+        data.extend([0u8, 0, 0]);
+        data
+    }
 
-        assert_eq!(symbol, "LSTAND");
+    fn metadata_address(mint: &str) -> String {
+        mpl_token_metadata::pda::find_metadata_account(&Pubkey::from_str(mint).unwrap()).0.to_string()
     }
 
+    fn mock_rpc() -> MockRpc {
+        MockRpc::start(HashMap::from([
+            (metadata_address(AMMO), metadata_account(AMMO, "Ammunition", "AMMO", Some((COLLECTION, true)))),
+            (metadata_address(LSTAND), metadata_account(LSTAND, "Landing Stand", "LSTAND", Some((COLLECTION, false)))),
+            (metadata_address(USDC), metadata_account(USDC, "USD Coin", "USDC", None)),
+        ]))
+    }
+
+    fn temp_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("metadata_gateway_{}_{}.json", name, std::process::id()));
+        path.to_str().unwrap().to_string()
+    }
 
     #[test]
-    fn is_USDC() {
-        let symbol = request_metadata_symbol(
-            "https://api.mainnet-beta.solana.com".to_string(),
-            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string());
+    fn parses_metadata() {
+        let metadata = parse_metadata(AMMO, metadata_account(AMMO, "Ammunition", "AMMO", Some((COLLECTION, true))).as_slice()).unwrap();
 
-        assert_eq!(symbol, "USDC");
+        assert_eq!(metadata, TokenMetadata {
+            mint: AMMO.to_string(),
+            name: "Ammunition".to_string(),
+            symbol: "AMMO".to_string(),
+            uri: format!("https://galaxy.staratlas.com/nfts/{}", AMMO),
+            update_authority: UPDATE_AUTHORITY.to_string(),
+            collection: Some(COLLECTION.to_string()),
+        });
+        assert!(matches!(parse_metadata(AMMO, &[4u8, 1, 2]), Err(MetadataError::Decode(_, _))));
     }
-}
 
+    #[tokio::test]
+    async fn is_AMMO() {
+        let rpc = mock_rpc();
+        let gateway = MetadataGateway::new(rpc.url.as_str());
+
+        assert_eq!(gateway.symbol(AMMO).await.unwrap(), "AMMO");
+    }
+
+    #[tokio::test]
+    async fn is_LSTAND() {
+        let rpc = mock_rpc();
+        let gateway = MetadataGateway::new(rpc.url.as_str());
+        let metadata = gateway.metadata(LSTAND).await.unwrap();
+
+        assert_eq!(metadata.symbol, "LSTAND");
+        // the collection is not verified
+        assert_eq!(metadata.collection, None);
+    }
+
+    #[tokio::test]
+    async fn is_USDC() {
+        let rpc = mock_rpc();
+        let gateway = MetadataGateway::new(rpc.url.as_str());
+
+        assert_eq!(gateway.symbol(USDC).await.unwrap(), "USDC");
+    }
 
+    #[tokio::test]
+    async fn batches_and_caches() {
+        let rpc = mock_rpc();
+        let gateway = MetadataGateway::new(rpc.url.as_str());
+        let mints = vec![AMMO.to_string(), LSTAND.to_string(), USDC.to_string(), NO_METADATA.to_string(), "not a mint".to_string()];
+
+        let results = gateway.metadata_batch(mints.as_slice()).await;
+        assert_eq!(results[AMMO].as_ref().unwrap().symbol, "AMMO");
+        assert_eq!(results[USDC].as_ref().unwrap().name, "USD Coin");
+        assert!(matches!(results[NO_METADATA], Err(MetadataError::NotFound(_))));
+        assert!(matches!(results["not a mint"], Err(MetadataError::InvalidMint(_))));
+
+        // cached mints are not requested again, the mint without metadata is
+        gateway.metadata_batch(mints.as_slice()).await;
+        assert_eq!(rpc.batches(), vec![4, 1]);
+    }
+
+    #[tokio::test]
+    async fn cache_survives_restart() {
+        let cache_path = temp_path("cache");
+        {
+            let rpc = mock_rpc();
+            MetadataGateway::cached(rpc.url.as_str(), cache_path.as_str()).metadata(AMMO).await.unwrap();
+        }
+
+        let rpc = mock_rpc();
+        let gateway = MetadataGateway::cached(rpc.url.as_str(), cache_path.as_str());
+        let symbol = gateway.symbol(AMMO).await.unwrap();
+        std::fs::remove_file(cache_path.as_str()).unwrap();
+
+        assert_eq!(symbol, "AMMO");
+        assert!(rpc.batches().is_empty());
+    }
+
+    #[tokio::test]
+    async fn short_responses_are_errors() {
+        let rpc = mock_rpc();
+        rpc.truncate_responses(1);
+        let gateway = MetadataGateway::new(rpc.url.as_str());
+
+        let results = gateway.metadata_batch(&[AMMO.to_string(), USDC.to_string()]).await;
+        assert_eq!(results[AMMO].as_ref().unwrap().symbol, "AMMO");
+        assert!(matches!(results[USDC], Err(MetadataError::Rpc(_))));
+
+        rpc.truncate_responses(0);
+        assert!(matches!(gateway.metadata(LSTAND).await, Err(MetadataError::Rpc(_))));
+    }
+
+    #[tokio::test]
+    async fn rpc_errors_are_returned() {
+        let gateway = MetadataGateway::new("http://127.0.0.1:9");
+
+        assert!(matches!(gateway.metadata(AMMO).await, Err(MetadataError::Rpc(_))));
+    }
+}
@@ -0,0 +1,47 @@
+use thiserror::Error;
+
+/// Reasons why a marketplace instruction could not be turned into a `ProcessExchange`.
+///
+/// These errors are scoped to a single instruction, the block is still processed and the
+/// instruction is reported as a `ProcessingWarning` instead.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ProcessingError {
+    #[error("instruction data is too short ({0} bytes)")]
+    InvalidInstruction(usize),
+    #[error("unknown marketplace instruction tag={0}")]
+    UnknownInstruction(u8),
+    #[error("invalid ProcessExchange args len={0}")]
+    InvalidArgsLength(usize),
+    #[error("unable to decode ProcessExchange args: {0}")]
+    InvalidArgs(String),
+    #[error("decimals of currency mint {0} not found in token balances")]
+    UnknownCurrencyMint(String),
+    #[error("no inner instructions found for instruction index={0}")]
+    MissingInnerInstructions(usize),
+    #[error("asset mint not found in inner instructions of instruction index={0}")]
+    AssetMintNotFound(usize),
+    #[error("account at position {0} is missing")]
+    MissingAccount(usize),
+    #[error("transaction has no signature")]
+    MissingSignature,
+    #[error("invalid amount: {0}")]
+    InvalidAmount(String),
+}
+
+impl ProcessingError {
+    /// Stable short name of the error, used to group warnings when monitoring decoding coverage.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ProcessingError::InvalidInstruction(_) => "invalid_instruction",
+            ProcessingError::UnknownInstruction(_) => "unknown_instruction",
+            ProcessingError::InvalidArgsLength(_) => "invalid_args_length",
+            ProcessingError::InvalidArgs(_) => "invalid_args",
+            ProcessingError::UnknownCurrencyMint(_) => "unknown_currency_mint",
+            ProcessingError::MissingInnerInstructions(_) => "missing_inner_instructions",
+            ProcessingError::AssetMintNotFound(_) => "asset_mint_not_found",
+            ProcessingError::MissingAccount(_) => "missing_account",
+            ProcessingError::MissingSignature => "missing_signature",
+            ProcessingError::InvalidAmount(_) => "invalid_amount",
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use substreams_solana::pb::sol::v1::{CompiledInstruction, Message, TokenBalance, TransactionStatusMeta};
+
+use crate::errors::ProcessingError;
+
+/// Digits a unit price derived from `total / quantity` keeps beyond the decimals of the currency.
+pub const PRICE_EXTRA_DECIMALS: u32 = 8;
+
+/// Formats a raw base-unit amount as an exact decimal string, e.g. `(150000000, 8)` -> `"1.5"`.
+pub fn format_amount(raw: u64, decimals: u32) -> String {
+    format_digits(raw.to_string(), decimals)
+}
+
+/// Formats `total_raw / quantity` as a decimal string, truncated after `decimals + PRICE_EXTRA_DECIMALS` digits.
+///
+/// A unit price below one base unit of the currency, e.g. 3 base units for 1000 assets, stays visible instead of
+/// being cut to 0 by an integer division.
+pub fn format_unit_price(total_raw: u64, quantity: u64, decimals: u32) -> String {
+    let scaled = total_raw as u128 * 10u128.pow(PRICE_EXTRA_DECIMALS) / quantity.max(1) as u128;
+    format_digits(scaled.to_string(), decimals + PRICE_EXTRA_DECIMALS)
+}
+
+fn format_digits(digits: String, decimals: u32) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        return integer.to_string();
+    }
+    return format!("{}.{}", integer, fraction);
+}
+
+/// Reads the decimals of `mint` from the token balances of the transaction.
+///
+/// Every currency moved by the marketplace shows up in the pre/post token balances, so this works for
+/// any currency registered with `RegisterCurrency`, not only ATLAS and USDC.
+pub fn find_token_decimals(meta: &TransactionStatusMeta, mint: &str) -> Result<u32, ProcessingError> {
+    meta.post_token_balances.iter()
+        .chain(meta.pre_token_balances.iter())
+        .filter(|tb| tb.mint == mint)
+        .find_map(|tb| tb.ui_token_amount.as_ref().map(|amount| amount.decimals))
+        .ok_or(ProcessingError::UnknownCurrencyMint(mint.to_string()))
+}
+
+/// Absolute change of the `authority` balance of `currency_mint` in base units.
+///
+/// Reads the raw `amount` of the token balances, so the result is exact for any amount a u64 can hold.
+pub fn calc_token_balance_change_raw(meta: &TransactionStatusMeta, currency_mint: &str, authority: &str) -> Result<u64, ProcessingError> {
+    let post = find_token_balance_raw(&meta.post_token_balances, currency_mint, authority)?;
+    let pre = find_token_balance_raw(&meta.pre_token_balances, currency_mint, authority)?;
+    log::info!("{} pre={} post={}", authority, pre, post);
+
+    return Ok(if post > pre { post - pre } else { pre - post });
+}
+
+/// Absolute change of the token account at `account_index` of the message in base units.
+pub fn calc_token_account_change_raw(meta: &TransactionStatusMeta, account_index: u8) -> Result<u64, ProcessingError> {
+    let post = find_token_account_raw(&meta.post_token_balances, account_index)?;
+    let pre = find_token_account_raw(&meta.pre_token_balances, account_index)?;
+
+    return Ok(if post > pre { post - pre } else { pre - post });
+}
+
+fn find_token_account_raw(balances: &[TokenBalance], account_index: u8) -> Result<u64, ProcessingError> {
+    match balances.iter()
+        .find(|tb| tb.account_index == account_index as u32)
+        .and_then(|tb| tb.ui_token_amount.as_ref()) {
+        None => Ok(0),
+        Some(amount) => amount.amount.parse::<u64>()
+            .map_err(|err| ProcessingError::InvalidAmount(format!("{} ({})", amount.amount, err))),
+    }
+}
+
+fn find_token_balance_raw(balances: &[TokenBalance], currency_mint: &str, authority: &str) -> Result<u64, ProcessingError> {
+    match balances.iter()
+        .find(|tb| { tb.mint == currency_mint && tb.owner == authority })
+        .and_then(|tb| tb.ui_token_amount.as_ref()) {
+        None => Ok(0),
+        Some(amount) => amount.amount.parse::<u64>()
+            .map_err(|err| ProcessingError::InvalidAmount(format!("{} ({})", amount.amount, err))),
+    }
+}
+
+pub fn find_asset_mint_in_inner_instruction_get_index(inner_instructions: Vec<CompiledInstruction>, asset_mint_account: u8) -> Option<usize> {
+    log::info!("{:?}", inner_instructions);
+    for (idx, instruction) in inner_instructions.clone().into_iter().enumerate() {
+        if instruction.accounts.contains(&asset_mint_account) {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+
+/// Resolves the account at `position` of an instruction into its base58 address.
+pub fn instruction_account(msg: &Message, inst: &CompiledInstruction, position: usize) -> Result<String, ProcessingError> {
+    let account_index = *inst.accounts.get(position).ok_or(ProcessingError::MissingAccount(position))?;
+    let account_key = msg.account_keys.get(account_index as usize).ok_or(ProcessingError::MissingAccount(position))?;
+    Ok(bs58::encode(account_key).into_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_amount, format_unit_price};
+
+    #[test]
+    fn format_amount_is_exact() {
+        assert_eq!(format_amount(0, 8), "0");
+        assert_eq!(format_amount(1, 8), "0.00000001");
+        assert_eq!(format_amount(150000000, 8), "1.5");
+        assert_eq!(format_amount(525000, 6), "0.525");
+        assert_eq!(format_amount(15000, 0), "15000");
+        assert_eq!(format_amount(u64::MAX, 8), "184467440737.09551615");
+        assert_eq!(format_amount(u64::MAX, 0), "18446744073709551615");
+        assert_eq!(format_amount(u64::MAX, 20), "0.18446744073709551615");
+    }
+
+    #[test]
+    fn format_amount_round_trips_through_decimal() {
+        use std::str::FromStr;
+        use bigdecimal::BigDecimal;
+
+        for (raw, decimals) in [(u64::MAX, 8), (9007199254740993, 8), (123456789, 6), (10, 1)] {
+            let value = BigDecimal::from_str(format_amount(raw, decimals).as_str()).unwrap();
+            let back = (value * BigDecimal::from_str(format!("1{}", "0".repeat(decimals as usize)).as_str()).unwrap()).with_scale(0);
+            assert_eq!(back.to_string(), raw.to_string());
+        }
+    }
+
+    #[test]
+    fn format_unit_price_keeps_the_fraction() {
+        assert_eq!(format_unit_price(300000000, 2, 8), "1.5");
+        assert_eq!(format_unit_price(300000000, 7, 8), "0.4285714285714285");
+        assert_eq!(format_unit_price(3, 1000, 6), "0.000000003");
+        assert_eq!(format_unit_price(0, 5, 6), "0");
+        assert_eq!(format_unit_price(u64::MAX, 1, 0), "18446744073709551615");
+    }
+}
@@ -1,8 +1,7 @@
 use borsh::BorshDeserialize;
-use substreams::errors::Error;
-use substreams::log;
 
-use crate::sa_instruction::MarketplaceInstruction::ProcessExchange;
+use crate::errors::ProcessingError;
+use crate::instruction::MarketplaceInstruction::ProcessExchange;
 
 //use borsh::BorshDeserialize;
 
@@ -18,10 +17,14 @@ pub enum MarketplaceInstruction<'a> {
     ProcessInitializeSell,
     InitializeOpenOrdersCounter,
     InitializeMarketplace,
-    RegisterCurrency,
+    RegisterCurrency {
+        royalty: u64,
+    },
     UpdateAtlasRate,
     DeregisterCurrency,
-    UpdateCurrencyRoyalty,
+    UpdateCurrencyRoyalty {
+        royalty: u64,
+    },
     ProcessCancel,
     ProcessExchange {
         purchase_quantity: u64,
@@ -47,6 +50,11 @@ struct ProcessExchangeArgNoPubkey {
     expected_price: u64,
 }
 
+#[derive(BorshDeserialize, Debug)]
+struct CurrencyRoyaltyArgs {
+    royalty: u64,
+}
+
 #[derive(BorshDeserialize, Debug)]
 struct ProcessExchangeArgsWithPubkey {
     pub purchase_quantity: u64,
@@ -56,13 +64,16 @@ struct ProcessExchangeArgsWithPubkey {
 
 
 impl<'a> MarketplaceInstruction<'a> {
-    pub fn unpack(input: &'a [u8]) -> Result<Self, Error> {
+    pub fn unpack(input: &'a [u8]) -> Result<Self, ProcessingError> {
         //Example
         //[112, 194, 63, 99, 52, 147, 85, 48, 1, 0, 0, 0, 0, 0, 0, 0, 128, 139, 121, 2, 0, 0, 0, 0]
         //[112, 194, 63, 99, 52, 147, 85, 48] //PROCESS_EXCHANGE_IX_DISCM
         //                                   [ ARGS                                               ]
 
-        let (&tag, rest) = input.split_first().ok_or(Error::Unexpected(format!("Invalid Instruction")))?;
+        if input.len() < 8 {
+            return Err(ProcessingError::InvalidInstruction(input.len()));
+        }
+        let (&tag, rest) = input.split_first().ok_or(ProcessingError::InvalidInstruction(input.len()))?;
         let (_dump, exchange_args) = rest.split_at(7);
 
         Ok(match tag {
@@ -110,7 +121,8 @@ impl<'a> MarketplaceInstruction<'a> {
 
                 match exchange_args.len() {
                     8 => {
-                        let data = ProcessExchangeArgNoPubkeyAndPrice::try_from_slice(exchange_args).unwrap();
+                        let data = ProcessExchangeArgNoPubkeyAndPrice::try_from_slice(exchange_args)
+                            .map_err(|err| ProcessingError::InvalidArgs(err.to_string()))?;
                         log::info!("ProcessExchangeArgNoPubkeyAndPrice={:?}", data);
                         ProcessExchange {
                             purchase_quantity: data.purchase_quantity,
@@ -118,7 +130,8 @@ impl<'a> MarketplaceInstruction<'a> {
                         }
                     }
                     16 => {
-                        let data = ProcessExchangeArgNoPubkey::try_from_slice(exchange_args).unwrap();
+                        let data = ProcessExchangeArgNoPubkey::try_from_slice(exchange_args)
+                            .map_err(|err| ProcessingError::InvalidArgs(err.to_string()))?;
                         log::info!("ProcessExchangeArgNoPubkey={:?}", data);
                         ProcessExchange {
                             purchase_quantity: data.purchase_quantity,
@@ -126,7 +139,8 @@ impl<'a> MarketplaceInstruction<'a> {
                         }
                     }
                     48 => {
-                        let data = ProcessExchangeArgsWithPubkey::try_from_slice(exchange_args).unwrap();
+                        let data = ProcessExchangeArgsWithPubkey::try_from_slice(exchange_args)
+                            .map_err(|err| ProcessingError::InvalidArgs(err.to_string()))?;
                         log::info!("ProcessExchangeArgsWithPubkey={:?}", data);
                         ProcessExchange {
                             purchase_quantity: data.purchase_quantity,
@@ -134,7 +148,7 @@ impl<'a> MarketplaceInstruction<'a> {
                         }
                     }
                     _ => {
-                        return Err(Error::Unexpected(format!("Invalid args len={:?}", exchange_args.len())));
+                        return Err(ProcessingError::InvalidArgsLength(exchange_args.len()));
                     }
                 }
             }
@@ -148,7 +162,9 @@ impl<'a> MarketplaceInstruction<'a> {
             }
             179 => {
                 log::info!("[Instruction] UpdateCurrencyRoyalty");
-                Self::UpdateCurrencyRoyalty {}
+                let data = CurrencyRoyaltyArgs::try_from_slice(exchange_args)
+                    .map_err(|err| ProcessingError::InvalidArgs(err.to_string()))?;
+                Self::UpdateCurrencyRoyalty { royalty: data.royalty }
             }
             221 => {
                 log::info!("[Instruction] InitializeOpenOrdersCounter");
@@ -160,7 +176,9 @@ impl<'a> MarketplaceInstruction<'a> {
             }
             247 => {
                 log::info!("[Instruction] RegisterCurrency");
-                Self::RegisterCurrency {}
+                let data = CurrencyRoyaltyArgs::try_from_slice(exchange_args)
+                    .map_err(|err| ProcessingError::InvalidArgs(err.to_string()))?;
+                Self::RegisterCurrency { royalty: data.royalty }
             }
             248 => {
                 log::info!("[Instruction] UpdateAtlasRate");
@@ -169,17 +187,17 @@ impl<'a> MarketplaceInstruction<'a> {
             _ => {
                 log::info!("tag={:?}", tag);
                 log::info!("invalid marketplace instruction");
-                return Err(Error::Unexpected(format!("Invalid marketplace instruction")));
+                return Err(ProcessingError::UnknownInstruction(tag));
             }
         })
     }
-    fn unpack_pubkey(input: &[u8]) -> Result<(Vec<u8>, &[u8]), Error> {
+    fn unpack_pubkey(input: &[u8]) -> Result<(Vec<u8>, &[u8]), ProcessingError> {
         if input.len() >= 32 {
             let (key, rest) = input.split_at(32);
             let pk = key.to_vec();
             Ok((pk, rest))
         } else {
-            Err(Error::Unexpected(format!("Invalid Instruction")))
+            Err(ProcessingError::InvalidInstruction(input.len()))
         }
     }
 }
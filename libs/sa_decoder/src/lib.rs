@@ -0,0 +1,232 @@
+//! Decoding of StarAtlas GalacticMarketplace instructions into `ProcessExchange` trades.
+//!
+//! The decoder works on the substreams `Block` model, so `substream-sa` and the RPC backfill
+//! produce exactly the same trades for the same transactions.
+//!
+//! The royalty discount is not read from the `RoyaltyTier` account of the fee payer, the transaction
+//! does not carry its data. [`apply_royalty`] infers it from the fee actually charged instead:
+//! `(royalty - fee_rate) / royalty`. A fee rate at or above the registered royalty, e.g. right after
+//! an `UpdateCurrencyRoyalty`, or a currency without royalty yields no discount.
+
+use substreams_solana::pb::sol::v1::{Block, CompiledInstruction, Message, TransactionStatusMeta};
+
+use crate::errors::ProcessingError;
+use crate::help::{calc_token_account_change_raw, calc_token_balance_change_raw, find_asset_mint_in_inner_instruction_get_index, find_token_decimals, format_amount, format_unit_price, instruction_account};
+use crate::instruction::MarketplaceInstruction;
+use crate::pb::sa::trade::v1::{CurrencyRoyalty, ProcessExchange, ProcessingWarning};
+
+pub mod pb;
+pub mod help;
+pub mod instruction;
+pub mod errors;
+#[cfg(feature = "trade")]
+pub mod trade;
+#[cfg(test)]
+mod tests;
+
+/// Address of the GalacticMarketplace program.
+pub const PROGRAM_ID: &str = "traderDnaR5w6Tcoi3NFm53i48FTDNbGjBSZwWXDRrg";
+
+/// Precision of royalties, discounts and fee rates, `1_000_000` is 100%.
+pub const ROYALTY_PRECISION: u64 = 1_000_000;
+
+/// Decodes the marketplace instructions of all successful transactions of the block.
+///
+/// Exchanges go into `process_exchanges`, instructions which can't be decoded into `warnings` and
+/// currency royalty changes into `royalties`.
+pub fn process_blocks(blk: Block, process_exchanges: &mut Vec<ProcessExchange>, warnings: &mut Vec<ProcessingWarning>, royalties: &mut Vec<CurrencyRoyalty>) {
+    for trx in blk.transactions.iter() {
+        if let Some(meta) = trx.clone().meta {
+            if let Some(_) = meta.err {
+                continue;
+            }
+            if let Some(transaction) = trx.clone().transaction {
+                if let Some(msg) = transaction.clone().message {
+                    for (inst_idx, inst) in msg.instructions.clone().into_iter().enumerate() {
+                        let program_id = match msg.account_keys.get(inst.program_id_index as usize) {
+                            None => { continue; }
+                            Some(program_id) => program_id,
+                        };
+                        if bs58::encode(program_id).into_string() != PROGRAM_ID {
+                            continue;
+                        }
+                        //Continue Parsing
+                        let sig = match transaction.signatures.first() {
+                            None => {
+                                push_warning(warnings, "".to_string(), blk.slot, inst_idx, ProcessingError::MissingSignature);
+                                continue;
+                            }
+                            Some(signature) => bs58::encode(signature.as_slice()).into_string(),
+                        };
+                        log::info!("{}", sig.clone());
+
+                        let instruction = match MarketplaceInstruction::unpack(&inst.data) {
+                            Ok(instruction) => instruction,
+                            Err(err) => {
+                                push_warning(warnings, sig, blk.slot, inst_idx, err);
+                                continue;
+                            }
+                        };
+                        match instruction {
+                            MarketplaceInstruction::ProcessExchange { purchase_quantity, expected_price } => {
+                                match process_exchange(&blk, &meta, &msg, &inst, inst_idx, sig.clone(), purchase_quantity, expected_price) {
+                                    Ok(exchange) => process_exchanges.push(exchange),
+                                    Err(err) => push_warning(warnings, sig, blk.slot, inst_idx, err),
+                                }
+                            }
+                            MarketplaceInstruction::RegisterCurrency { royalty } | MarketplaceInstruction::UpdateCurrencyRoyalty { royalty } => {
+                                match instruction_account(&msg, &inst, 3) {
+                                    Ok(currency_mint) => royalties.push(CurrencyRoyalty { currency_mint, royalty, block: blk.slot, signature: sig }),
+                                    Err(err) => push_warning(warnings, sig, blk.slot, inst_idx, err),
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn process_exchange(
+    blk: &Block,
+    meta: &TransactionStatusMeta,
+    msg: &Message,
+    inst: &CompiledInstruction,
+    inst_idx: usize,
+    sig: String,
+    purchase_quantity: u64,
+    expected_price: Option<u64>) -> Result<ProcessExchange, ProcessingError> {
+    let order_taker = instruction_account(msg, inst, 0)?;
+    let currency_mint = instruction_account(msg, inst, 3)?;
+    let order_initializer = instruction_account(msg, inst, 5)?;
+    let asset_mint = instruction_account(msg, inst, 4)?;
+
+
+    log::info!("instruction none={:?}", meta.inner_instructions_none);
+    meta.inner_instructions.iter().for_each(|e| {
+        log::info!("instruction inner={:?}", e.index);
+    });
+    log::info!("instruction index={:?}", inst_idx);
+
+
+    let asset_receiving_wallet_index;
+    if meta.inner_instructions_none {
+        asset_receiving_wallet_index = 4
+    } else {
+        let inner_instructions = meta.inner_instructions.iter()
+            .find(|i| i.index == inst_idx as u32)
+            .ok_or(ProcessingError::MissingInnerInstructions(inst_idx))?;
+        asset_receiving_wallet_index =
+            find_asset_mint_in_inner_instruction_get_index(
+                inner_instructions.instructions.clone(),
+                *inst.accounts.get(4).ok_or(ProcessingError::MissingAccount(4))?)
+                .ok_or(ProcessingError::AssetMintNotFound(inst_idx))?;
+    }
+
+    log::info!("asset_receiving_wallet_index={:?}", asset_receiving_wallet_index);
+    let asset_receiving_wallet = match asset_receiving_wallet_index {
+        1 => { order_initializer.clone() }
+        2 => { order_taker.clone() }
+        3 => { order_initializer.clone() }
+        4 => { order_taker.clone() }
+        _ => { order_taker.clone() }
+    };
+
+
+    let decimals = find_token_decimals(meta, currency_mint.as_str())?;
+    let currency_change_raw = calc_token_balance_change_raw(meta, currency_mint.as_str(), order_taker.as_str())?;
+    // protocol fees are paid into the sa_vault of the registered currency
+    let market_fee_raw = calc_token_account_change_raw(meta, *inst.accounts.get(11).ok_or(ProcessingError::MissingAccount(11))?)?;
+
+    if purchase_quantity == 0 {
+        return Err(ProcessingError::InvalidAmount("purchase_quantity=0".to_string()));
+    }
+    // without an expected price the unit price is derived from the paid total, `price_raw` is then truncated to
+    // whole base units while `price` keeps the fraction
+    let (price_raw, price, total_cost_raw) = match expected_price {
+        None => {
+            let price = format_unit_price(currency_change_raw, purchase_quantity, decimals);
+            (currency_change_raw / purchase_quantity, price, currency_change_raw)
+        }
+        Some(value) => {
+            let total_cost_raw = value.checked_mul(purchase_quantity)
+                .ok_or(ProcessingError::InvalidAmount(format!("total cost overflow price={} quantity={}", value, purchase_quantity)))?;
+            (value, format_amount(value, decimals), total_cost_raw)
+        }
+    };
+
+    // the seller receives the currency on the order_taker_receive_token_account when the taker sells and on the
+    // initializer_receive_token_account when the taker buys
+    let seller_receive_account = if asset_receiving_wallet == order_taker { 7 } else { 2 };
+    let seller_received_raw = calc_token_account_change_raw(meta, *inst.accounts.get(seller_receive_account).ok_or(ProcessingError::MissingAccount(seller_receive_account))?)?;
+    let fee_payer = if market_fee_raw == 0 {
+        "none"
+    } else if seller_received_raw.checked_add(market_fee_raw) == Some(total_cost_raw) {
+        "seller"
+    } else if seller_received_raw == total_cost_raw {
+        "buyer"
+    } else {
+        "unknown"
+    };
+    let fee_rate = if total_cost_raw == 0 { 0 } else { (market_fee_raw as u128 * ROYALTY_PRECISION as u128 / total_cost_raw as u128) as u64 };
+
+    Ok(ProcessExchange {
+        pk: exchange_pk(sig.as_str(), order_taker.as_str(), order_initializer.as_str(), inst_idx, None),
+        signature: sig,
+        block: blk.slot,
+        timestamp: blk.block_time.clone().unwrap_or_default().timestamp,
+        order_taker,
+        order_initializer,
+        currency_mint: currency_mint.clone(),
+        asset_mint,
+        asset_receiving_wallet,
+        currency_change: format_amount(currency_change_raw, decimals),
+        market_fee: format_amount(market_fee_raw, decimals),
+        asset_change: purchase_quantity.to_string(),
+        price,
+        total_cost: format_amount(total_cost_raw, decimals),
+        currency_decimals: decimals,
+        currency_change_raw,
+        market_fee_raw,
+        total_cost_raw,
+        price_raw,
+        fee_payer: fee_payer.to_string(),
+        fee_rate,
+        royalty: 0,
+        royalty_discount: 0,
+    })
+}
+
+/// Primary key of a trade, unique per instruction so several exchanges of one transaction don't merge.
+///
+/// `outer` is the index of the instruction in the message, `inner` the index within its inner instructions for an
+/// exchange invoked by CPI. Only top level exchanges are decoded so far, they use `None`.
+pub fn exchange_pk(signature: &str, order_taker: &str, order_initializer: &str, outer: usize, inner: Option<usize>) -> String {
+    match inner {
+        None => format!("{}_{}_{}_{}", signature, order_taker, order_initializer, outer),
+        Some(inner) => format!("{}_{}_{}_{}_{}", signature, order_taker, order_initializer, outer, inner),
+    }
+}
+
+/// Adds the registered `royalty` of the currency and the discount the fee payer got on it, e.g. from a staking tier.
+///
+/// The discount is approximated from the charged `fee_rate`, see the module documentation.
+pub fn apply_royalty(exchange: &mut ProcessExchange, royalty: u64) {
+    exchange.royalty = royalty;
+    if royalty > 0 && exchange.fee_rate <= royalty {
+        exchange.royalty_discount = ((royalty - exchange.fee_rate) as u128 * ROYALTY_PRECISION as u128 / royalty as u128) as u64;
+    }
+}
+
+fn push_warning(warnings: &mut Vec<ProcessingWarning>, signature: String, block: u64, inst_idx: usize, err: ProcessingError) {
+    log::info!("skipping instruction {} of {}: {}", inst_idx, signature, err);
+    warnings.push(ProcessingWarning {
+        signature,
+        block,
+        instruction_index: inst_idx as u32,
+        kind: err.kind().to_string(),
+        reason: err.to_string(),
+    });
+}
@@ -0,0 +1,259 @@
+//! Block fixtures in `tests/fixtures`.
+//!
+//! [`MAINNET_FIXTURES`] are mainnet blocks reduced to their marketplace transactions, as `Fetcher::fetch_blocks`
+//! returns them. solana_tools' `capture_decoder_fixtures` fetches them again, they are checked in as captured.
+//!
+//! No captured block covers the cases of [`SYNTHETIC_FIXTURES`] yet, they are built below after mainnet
+//! exchanges, only the parts read by `process_blocks` are filled. Run
+//! `cargo test regenerate_synthetic_fixtures -- --ignored` after changing them.
+
+use substreams_solana::pb::sol::v1::{Block, CompiledInstruction, ConfirmedTransaction, InnerInstructions, Message, TokenBalance, Transaction, TransactionError, TransactionStatusMeta, UiTokenAmount, UnixTimestamp};
+
+pub const PROGRAM_ID: &str = "traderDnaR5w6Tcoi3NFm53i48FTDNbGjBSZwWXDRrg";
+pub const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const ATLAS: &str = "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx";
+pub const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub const AMMO: &str = "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK";
+pub const FOOD: &str = "foodQJAztMzX1DKpLaiounNe2BDMds5RNuPC6jsNrDG";
+pub const ORDER_TAKER: &str = "FnuG4ZMtLvassRvFmmSuH3HKBdfVVWkfrA3GtJrfeQax";
+pub const ORDER_INITIALIZER: &str = "9Bu7dde6pJtSpgUzcZxKbnXK1qoPbyi7ANDxw62rwUjB";
+
+const PROCESS_EXCHANGE_DISCM: [u8; 8] = [112, 194, 63, 99, 52, 147, 85, 48];
+
+/// Captured mainnet blocks, each one is stored as `<name>.binpb` with the expected `<name>.json` next to it.
+///
+/// `exchange_args_48` holds tx_001 of the rpc samples (slot 153357697), `exchanges_per_block` two exchanges of
+/// slot 153415352 and `failed_transaction` a failed and a successful exchange of slot 153360044.
+pub const MAINNET_FIXTURES: [&str; 3] = [
+    "exchange_args_48",
+    "exchanges_per_block",
+    "failed_transaction",
+];
+
+/// Fixtures built by [`build_fixture`], stored like the mainnet ones.
+pub const SYNTHETIC_FIXTURES: [&str; 4] = [
+    "exchange_args_8",
+    "exchange_args_16",
+    "inner_instructions_none",
+    "multiple_exchanges_per_tx",
+];
+
+pub fn build_fixture(name: &str) -> Block {
+    match name {
+        "exchange_args_8" => block(151993612, vec![
+            exchange_tx(1, vec![Exchange::taker_buys(AMMO, ATLAS, 8, 2, 150000000, 0, Args::Quantity)]),
+        ]),
+        "exchange_args_16" => block(181695702, vec![
+            exchange_tx(2, vec![Exchange::taker_sells(FOOD, USDC, 6, 10, 525000, 315000, Args::QuantityAndPrice)]),
+        ]),
+        "inner_instructions_none" => {
+            let mut tx = exchange_tx(4, vec![Exchange::taker_buys(AMMO, ATLAS, 8, 3, 100000000, 18000000, Args::QuantityAndPrice)]);
+            let meta = tx.meta.as_mut().unwrap();
+            meta.inner_instructions = vec![];
+            meta.inner_instructions_none = true;
+            block(185686863, vec![tx])
+        }
+        "multiple_exchanges_per_tx" => {
+            let mut failed = exchange_tx(6, vec![Exchange::taker_buys(AMMO, ATLAS, 8, 1, 150000000, 9000000, Args::QuantityAndPrice)]);
+            failed.meta.as_mut().unwrap().err = Some(TransactionError { err: vec![1] });
+            block(194492072, vec![
+                failed,
+                exchange_tx(5, vec![
+                    Exchange::taker_buys(AMMO, ATLAS, 8, 4, 150000000, 36000000, Args::QuantityAndPrice),
+                    Exchange::taker_sells(FOOD, USDC, 6, 1000, 1200, 72000, Args::QuantityAndPrice),
+                ]),
+            ])
+        }
+        _ => panic!("unknown fixture {}", name),
+    }
+}
+
+enum Args {
+    Quantity,
+    QuantityAndPrice,
+}
+
+struct Exchange {
+    asset_mint: &'static str,
+    currency_mint: &'static str,
+    decimals: u32,
+    quantity: u64,
+    price: u64,
+    fee: u64,
+    taker_buys: bool,
+    args: Args,
+}
+
+impl Exchange {
+    fn taker_buys(asset_mint: &'static str, currency_mint: &'static str, decimals: u32, quantity: u64, price: u64, fee: u64, args: Args) -> Exchange {
+        Exchange { asset_mint, currency_mint, decimals, quantity, price, fee, taker_buys: true, args }
+    }
+
+    fn taker_sells(asset_mint: &'static str, currency_mint: &'static str, decimals: u32, quantity: u64, price: u64, fee: u64, args: Args) -> Exchange {
+        Exchange { asset_mint, currency_mint, decimals, quantity, price, fee, taker_buys: false, args }
+    }
+
+    fn data(&self) -> Vec<u8> {
+        let mut data = PROCESS_EXCHANGE_DISCM.to_vec();
+        data.extend_from_slice(&self.quantity.to_le_bytes());
+        match self.args {
+            Args::Quantity => {}
+            Args::QuantityAndPrice => {
+                data.extend_from_slice(&self.price.to_le_bytes());
+            }
+        }
+        data
+    }
+}
+
+/// Builds one transaction with a ProcessExchange instruction per exchange.
+///
+/// Every exchange gets its own 18 accounts in ProcessExchange order, followed by the program ids.
+fn exchange_tx(seed: u8, exchanges: Vec<Exchange>) -> ConfirmedTransaction {
+    let mut account_keys: Vec<Vec<u8>> = vec![];
+    let mut instructions = vec![];
+    let mut inner_instructions = vec![];
+    let mut pre_token_balances = vec![];
+    let mut post_token_balances = vec![];
+
+    for (inst_idx, exchange) in exchanges.iter().enumerate() {
+        let base = account_keys.len() as u8;
+        let account = |position: u8| base + position;
+        let accounts: Vec<Vec<u8>> = vec![
+            key(ORDER_TAKER),
+            generated_key(seed, inst_idx, 1),
+            generated_key(seed, inst_idx, 2),
+            key(exchange.currency_mint),
+            key(exchange.asset_mint),
+            key(ORDER_INITIALIZER),
+            generated_key(seed, inst_idx, 6),
+            generated_key(seed, inst_idx, 7),
+            generated_key(seed, inst_idx, 8),
+            generated_key(seed, inst_idx, 9),
+            generated_key(seed, inst_idx, 10),
+            generated_key(seed, inst_idx, 11),
+            generated_key(seed, inst_idx, 12),
+            generated_key(seed, inst_idx, 13),
+            key(TOKEN_PROGRAM),
+            generated_key(seed, inst_idx, 15),
+            generated_key(seed, inst_idx, 16),
+            generated_key(seed, inst_idx, 17),
+        ];
+        account_keys.extend(accounts);
+
+        let total_cost = exchange.quantity * exchange.price;
+        // (position, owner, mint, decimals, pre, post)
+        let balances: Vec<(u8, &str, &str, u32, u64, u64)> = if exchange.taker_buys {
+            // taker pays price and fee from the deposit account, asset comes out of the order vault
+            vec![
+                (1, ORDER_TAKER, exchange.currency_mint, exchange.decimals, total_cost + exchange.fee + 1000, 1000),
+                (2, ORDER_TAKER, exchange.asset_mint, 0, 0, exchange.quantity),
+                (7, ORDER_INITIALIZER, exchange.currency_mint, exchange.decimals, 0, total_cost),
+                (8, ORDER_INITIALIZER, exchange.asset_mint, 0, exchange.quantity, 0),
+                (11, PROGRAM_ID, exchange.currency_mint, exchange.decimals, 5000, 5000 + exchange.fee),
+            ]
+        } else {
+            // currency comes out of the order vault, the fee is deducted from what the taker receives
+            vec![
+                (1, ORDER_TAKER, exchange.asset_mint, 0, exchange.quantity, 0),
+                (2, ORDER_TAKER, exchange.currency_mint, exchange.decimals, 0, total_cost - exchange.fee),
+                (7, ORDER_INITIALIZER, exchange.asset_mint, 0, 0, exchange.quantity),
+                (8, ORDER_INITIALIZER, exchange.currency_mint, exchange.decimals, total_cost, 0),
+                (11, PROGRAM_ID, exchange.currency_mint, exchange.decimals, 5000, 5000 + exchange.fee),
+            ]
+        };
+        for (position, owner, mint, decimals, pre, post) in balances {
+            pre_token_balances.push(token_balance(account(position), owner, mint, decimals, pre));
+            post_token_balances.push(token_balance(account(position), owner, mint, decimals, post));
+        }
+
+        // fee transfer, currency transfer, asset transfer
+        let (currency_from, currency_to, asset_from, asset_to) = if exchange.taker_buys { (1, 7, 8, 2) } else { (8, 2, 1, 7) };
+        let asset_transfer = CompiledInstruction {
+            program_id_index: 0,
+            accounts: vec![account(asset_from), account(4), account(asset_to), account(9)],
+            data: vec![12],
+        };
+        let mut inner = vec![
+            CompiledInstruction { program_id_index: 0, accounts: vec![account(currency_from), account(3), account(11), account(0)], data: vec![12] },
+            CompiledInstruction { program_id_index: 0, accounts: vec![account(currency_from), account(3), account(currency_to), account(0)], data: vec![12] },
+        ];
+        if exchange.taker_buys {
+            inner.push(asset_transfer);
+        } else {
+            inner.insert(1, asset_transfer);
+        }
+        inner_instructions.push(InnerInstructions { index: inst_idx as u32, instructions: inner });
+
+        instructions.push(CompiledInstruction {
+            program_id_index: 0,
+            accounts: (0..18).map(account).collect(),
+            data: exchange.data(),
+        });
+    }
+
+    let program_id_index = account_keys.len() as u32;
+    account_keys.push(key(PROGRAM_ID));
+    account_keys.push(key(TOKEN_PROGRAM));
+    for instruction in instructions.iter_mut() {
+        instruction.program_id_index = program_id_index;
+    }
+    for inner in inner_instructions.iter_mut() {
+        for instruction in inner.instructions.iter_mut() {
+            instruction.program_id_index = program_id_index + 1;
+        }
+    }
+
+    ConfirmedTransaction {
+        transaction: Some(Transaction {
+            signatures: vec![[seed; 64].to_vec()],
+            message: Some(Message {
+                account_keys,
+                instructions,
+                ..Default::default()
+            }),
+        }),
+        meta: Some(TransactionStatusMeta {
+            inner_instructions,
+            pre_token_balances,
+            post_token_balances,
+            ..Default::default()
+        }),
+    }
+}
+
+fn block(slot: u64, transactions: Vec<ConfirmedTransaction>) -> Block {
+    Block {
+        slot,
+        parent_slot: slot - 1,
+        transactions,
+        block_time: Some(UnixTimestamp { timestamp: 1658823438 + (slot as i64 % 1000) }),
+        ..Default::default()
+    }
+}
+
+fn token_balance(account_index: u8, owner: &str, mint: &str, decimals: u32, amount: u64) -> TokenBalance {
+    TokenBalance {
+        account_index: account_index as u32,
+        mint: mint.to_string(),
+        owner: owner.to_string(),
+        ui_token_amount: Some(UiTokenAmount {
+            ui_amount: amount as f64 / 10f64.powi(decimals as i32),
+            decimals,
+            amount: amount.to_string(),
+            ui_amount_string: crate::help::format_amount(amount, decimals),
+        }),
+        ..Default::default()
+    }
+}
+
+fn key(address: &str) -> Vec<u8> {
+    bs58::decode(address).into_vec().unwrap()
+}
+
+fn generated_key(seed: u8, inst_idx: usize, position: u8) -> Vec<u8> {
+    let mut key = [seed; 32];
+    key[0] = inst_idx as u8;
+    key[1] = position;
+    key.to_vec()
+}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use prost::Message;
+use serde_json::{json, Value};
+use substreams_solana::pb::sol::v1::Block;
+
+use crate::pb::sa::trade::v1::{ProcessExchange, ProcessingWarning};
+use crate::{apply_royalty, process_blocks};
+use crate::tests::fixtures::{build_fixture, MAINNET_FIXTURES, SYNTHETIC_FIXTURES};
+use crate::tests::rpc_samples::{assert_matches_stored_trade, read_sample};
+
+mod fixtures;
+mod rpc_samples;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+
+fn fixture_path(name: &str, extension: &str) -> PathBuf {
+    fixtures_dir().join(format!("{}.{}", name, extension))
+}
+
+fn read_block(name: &str) -> Block {
+    let data = fs::read(fixture_path(name, "binpb")).expect("missing block fixture");
+    Block::decode(data.as_slice()).expect("invalid block fixture")
+}
+
+fn exchange_to_json(exchange: &ProcessExchange) -> Value {
+    json!({
+        "pk": exchange.pk,
+        "signature": exchange.signature,
+        "block": exchange.block,
+        "timestamp": exchange.timestamp,
+        "order_taker": exchange.order_taker,
+        "order_initializer": exchange.order_initializer,
+        "currency_mint": exchange.currency_mint,
+        "asset_mint": exchange.asset_mint,
+        "asset_receiving_wallet": exchange.asset_receiving_wallet,
+        "asset_change": exchange.asset_change,
+        "currency_change": exchange.currency_change,
+        "market_fee": exchange.market_fee,
+        "total_cost": exchange.total_cost,
+        "price": exchange.price,
+        "currency_decimals": exchange.currency_decimals,
+        "currency_change_raw": exchange.currency_change_raw,
+        "market_fee_raw": exchange.market_fee_raw,
+        "total_cost_raw": exchange.total_cost_raw,
+        "price_raw": exchange.price_raw,
+        "fee_payer": exchange.fee_payer,
+        "fee_rate": exchange.fee_rate,
+    })
+}
+
+fn replay(block: Block) -> (Value, Vec<ProcessingWarning>) {
+    let mut exchanges = vec![];
+    let mut warnings = vec![];
+    process_blocks(block, &mut exchanges, &mut warnings, &mut vec![]);
+    (Value::Array(exchanges.iter().map(exchange_to_json).collect()), warnings)
+}
+
+#[test]
+fn replay_fixtures() {
+    for name in MAINNET_FIXTURES.iter().chain(SYNTHETIC_FIXTURES.iter()) {
+        let (exchanges, warnings) = replay(read_block(name));
+        let expected: Value = serde_json::from_str(fs::read_to_string(fixture_path(name, "json")).unwrap().as_str()).unwrap();
+
+        assert!(warnings.is_empty(), "{}: unexpected warnings {:?}", name, warnings);
+        assert_eq!(exchanges, expected, "{}: exchanges differ from golden file", name);
+    }
+}
+
+#[test]
+fn synthetic_fixtures_are_up_to_date() {
+    for name in SYNTHETIC_FIXTURES {
+        assert_eq!(read_block(name), build_fixture(name), "{}: fixture is outdated, run regenerate_synthetic_fixtures", name);
+    }
+}
+
+#[test]
+fn mainnet_fixtures_match_the_stored_trades() {
+    // (fixture, trades the former RPC fetcher stored for its transactions)
+    for (name, output) in [("exchange_args_48", "db_001.out"), ("exchanges_per_block", "transactions-filtered.out")] {
+        let mut exchanges = vec![];
+        process_blocks(read_block(name), &mut exchanges, &mut vec![], &mut vec![]);
+        let trades = read_sample(output);
+
+        assert!(!exchanges.is_empty(), "{}: no exchange decoded", name);
+        for exchange in exchanges.iter() {
+            let trade = trades.as_array().unwrap().iter().find(|trade| trade["signature"] == exchange.signature.as_str())
+                .unwrap_or_else(|| panic!("{}: no stored trade for {}", name, exchange.signature));
+            assert_matches_stored_trade(exchange, trade);
+        }
+    }
+}
+
+#[test]
+fn failed_transactions_are_skipped() {
+    let block = read_block("failed_transaction");
+    assert_eq!(block.transactions.len(), 2);
+    assert!(block.transactions[0].meta.as_ref().unwrap().err.is_some());
+
+    let (exchanges, warnings) = replay(block);
+    assert!(warnings.is_empty(), "unexpected warnings {:?}", warnings);
+    assert_eq!(exchanges.as_array().unwrap().len(), 1);
+    assert_eq!(exchanges[0]["signature"], json!("437i1FtzuUn6NJkm7YXdoXtBtQKfxHDmYxpqRumkZ3C8jZEVxwBF43n33wJ8EDwRf2vyzsUTb189vs1yXRejh8EL"));
+}
+
+#[test]
+fn exchanges_of_one_transaction_have_unique_pks() {
+    let (exchanges, _) = replay(read_block("multiple_exchanges_per_tx"));
+    let pks: HashSet<&str> = exchanges.as_array().unwrap().iter().map(|e| e["pk"].as_str().unwrap()).collect();
+
+    assert_eq!(pks.len(), 2, "exchanges share a pk: {:?}", pks);
+    for (inst_idx, exchange) in exchanges.as_array().unwrap().iter().enumerate() {
+        assert!(exchange["pk"].as_str().unwrap().ends_with(format!("_{}", inst_idx).as_str()));
+    }
+}
+
+#[test]
+fn truncated_args_produce_a_warning() {
+    let mut block = read_block("exchange_args_16");
+    let tx = block.transactions[0].transaction.as_mut().unwrap();
+    tx.message.as_mut().unwrap().instructions[0].data.truncate(12);
+
+    let (exchanges, warnings) = replay(block);
+    assert_eq!(exchanges, json!([]));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, "invalid_args_length");
+}
+
+#[test]
+fn price_without_expected_price_keeps_the_fraction() {
+    // exchange_args_8 pays 3 ATLAS without an expected price, the quantity is replaced by non-divisible ones
+    for (quantity, price, price_raw) in [(7u64, "0.4285714285714285", 42857142u64), (1000000000, "0.000000003", 0)] {
+        let mut block = read_block("exchange_args_8");
+        let tx = block.transactions[0].transaction.as_mut().unwrap();
+        tx.message.as_mut().unwrap().instructions[0].data[8..16].copy_from_slice(&quantity.to_le_bytes());
+
+        let (exchanges, warnings) = replay(block);
+        assert!(warnings.is_empty(), "unexpected warnings {:?}", warnings);
+        assert_eq!(exchanges[0]["currency_change_raw"], json!(300000000));
+        assert_eq!(exchanges[0]["price"], json!(price), "quantity {}", quantity);
+        assert_eq!(exchanges[0]["price_raw"], json!(price_raw), "quantity {}", quantity);
+    }
+}
+
+#[test]
+fn royalty_discount_is_inferred_from_the_fee_rate() {
+    let exchange = |fee_rate: u64| ProcessExchange { fee_rate, ..Default::default() };
+    // (royalty, fee_rate, expected discount)
+    for (royalty, fee_rate, discount) in [
+        (60000, 60000, 0),
+        (60000, 45000, 250000),
+        (60000, 0, 1000000),
+        // fee above the registered royalty, e.g. charged before an UpdateCurrencyRoyalty of the same block range
+        (50000, 60000, 0),
+        // currency without royalty
+        (0, 0, 0),
+        (0, 60000, 0),
+    ] {
+        let mut trade = exchange(fee_rate);
+        apply_royalty(&mut trade, royalty);
+        assert_eq!(trade.royalty, royalty);
+        assert_eq!(trade.royalty_discount, discount, "royalty {} fee_rate {}", royalty, fee_rate);
+    }
+}
+
+/// Writes the synthetic block fixtures.
+///
+/// The golden files are not written, they are maintained by hand: check a changed exchange against the amounts of
+/// the transaction instead of copying the decoder output.
+#[test]
+#[ignore]
+fn regenerate_synthetic_fixtures() {
+    fs::create_dir_all(fixtures_dir()).unwrap();
+    for name in SYNTHETIC_FIXTURES {
+        fs::write(fixture_path(name, "binpb"), build_fixture(name).encode_to_vec()).unwrap();
+    }
+}
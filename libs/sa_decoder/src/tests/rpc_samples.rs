@@ -0,0 +1,158 @@
+//! Mainnet exchanges as returned by `getTransaction` with `jsonParsed` encoding, next to the trades the former
+//! RPC fetcher stored for them (`*.out.sample.json`) and its symbol store.
+//!
+//! The `jsonParsed` transactions are turned into the substreams `Block` model by [`to_block`]. Parsed
+//! instructions keep the accounts named in their info, the decoder only reads the account indices of inner
+//! instructions.
+
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use serde_json::Value;
+use substreams_solana::pb::sol::v1::{Block, CompiledInstruction, ConfirmedTransaction, InnerInstructions, Message, TokenBalance, Transaction, TransactionError, TransactionStatusMeta, UiTokenAmount, UnixTimestamp};
+
+use crate::help::format_amount;
+use crate::pb::sa::trade::v1::ProcessExchange;
+use crate::process_blocks;
+
+/// Transactions and the trades stored for them, as `(input, output)`.
+const SAMPLES: [(&str, &str); 2] = [
+    ("tx_001.in", "db_001.out"),
+    ("transactions-filtered.in", "transactions-filtered.out"),
+];
+
+pub(super) fn read_sample(name: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("samples").join(format!("{}.sample.json", name));
+    serde_json::from_str(fs::read_to_string(path).unwrap().as_str()).unwrap()
+}
+
+fn to_block(transaction: &Value) -> Block {
+    Block {
+        slot: transaction["slot"].as_u64().unwrap(),
+        block_time: transaction["blockTime"].as_i64().map(|timestamp| UnixTimestamp { timestamp }),
+        transactions: vec![to_confirmed_transaction(transaction)],
+        ..Default::default()
+    }
+}
+
+fn to_confirmed_transaction(transaction: &Value) -> ConfirmedTransaction {
+    let message = &transaction["transaction"]["message"];
+    let meta = &transaction["meta"];
+    let keys: Vec<&str> = message["accountKeys"].as_array().unwrap().iter().map(|key| key["pubkey"].as_str().unwrap()).collect();
+
+    ConfirmedTransaction {
+        transaction: Some(Transaction {
+            signatures: transaction["transaction"]["signatures"].as_array().unwrap().iter()
+                .map(|signature| bs58::decode(signature.as_str().unwrap()).into_vec().unwrap())
+                .collect(),
+            message: Some(Message {
+                account_keys: keys.iter().map(|key| bs58::decode(key).into_vec().unwrap()).collect(),
+                instructions: message["instructions"].as_array().unwrap().iter().map(|instruction| to_compiled_instruction(instruction, &keys)).collect(),
+                ..Default::default()
+            }),
+        }),
+        meta: Some(TransactionStatusMeta {
+            err: (!meta["err"].is_null()).then(|| TransactionError { err: meta["err"].to_string().into_bytes() }),
+            inner_instructions: meta["innerInstructions"].as_array().unwrap().iter()
+                .map(|inner| InnerInstructions {
+                    index: inner["index"].as_u64().unwrap() as u32,
+                    instructions: inner["instructions"].as_array().unwrap().iter().map(|instruction| to_compiled_instruction(instruction, &keys)).collect(),
+                })
+                .collect(),
+            pre_token_balances: meta["preTokenBalances"].as_array().unwrap().iter().map(to_token_balance).collect(),
+            post_token_balances: meta["postTokenBalances"].as_array().unwrap().iter().map(to_token_balance).collect(),
+            ..Default::default()
+        }),
+    }
+}
+
+fn to_compiled_instruction(instruction: &Value, keys: &[&str]) -> CompiledInstruction {
+    let index = |address: &Value| keys.iter().position(|key| Some(*key) == address.as_str()).map(|index| index as u8);
+    let program_id_index = index(&instruction["programId"]).unwrap() as u32;
+
+    match instruction["parsed"]["info"].as_object() {
+        Some(info) => CompiledInstruction { program_id_index, accounts: info.values().filter_map(index).collect(), data: vec![] },
+        None => CompiledInstruction {
+            program_id_index,
+            accounts: instruction["accounts"].as_array().unwrap().iter().map(|account| index(account).unwrap()).collect(),
+            data: bs58::decode(instruction["data"].as_str().unwrap()).into_vec().unwrap(),
+        },
+    }
+}
+
+fn to_token_balance(balance: &Value) -> TokenBalance {
+    let amount = &balance["uiTokenAmount"];
+    TokenBalance {
+        account_index: balance["accountIndex"].as_u64().unwrap() as u32,
+        mint: balance["mint"].as_str().unwrap().to_string(),
+        owner: balance["owner"].as_str().unwrap_or_default().to_string(),
+        ui_token_amount: Some(UiTokenAmount {
+            ui_amount: amount["uiAmount"].as_f64().unwrap_or_default(),
+            decimals: amount["decimals"].as_u64().unwrap() as u32,
+            amount: amount["amount"].as_str().unwrap().to_string(),
+            ui_amount_string: amount["uiAmountString"].as_str().unwrap().to_string(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn decode_sample(name: &str) -> Vec<ProcessExchange> {
+    let mut exchanges = vec![];
+    for transaction in read_sample(name).as_array().unwrap() {
+        let mut warnings = vec![];
+        process_blocks(to_block(transaction), &mut exchanges, &mut warnings, &mut vec![]);
+        assert!(warnings.is_empty(), "{}: unexpected warnings {:?}", name, warnings);
+    }
+    exchanges
+}
+
+#[test]
+fn rpc_samples_match_the_stored_trades() {
+    for (input, output) in SAMPLES {
+        let exchanges = decode_sample(input);
+        let trades = read_sample(output);
+        assert_eq!(exchanges.len(), trades.as_array().unwrap().len(), "{}: number of exchanges", input);
+
+        for trade in trades.as_array().unwrap() {
+            let signature = trade["signature"].as_str().unwrap();
+            let exchange = exchanges.iter().find(|exchange| exchange.signature == signature)
+                .unwrap_or_else(|| panic!("{}: {} not decoded", input, signature));
+            assert_matches_stored_trade(exchange, trade);
+        }
+    }
+}
+
+/// Compares the exchange with the trade the former RPC fetcher stored for its transaction.
+pub(super) fn assert_matches_stored_trade(exchange: &ProcessExchange, trade: &Value) {
+    let signature = exchange.signature.as_str();
+    let stored = &trade["exchange"][0];
+
+    assert_eq!(exchange.block, trade["slot"].as_u64().unwrap(), "{}", signature);
+    assert_eq!(exchange.timestamp, trade["timestamp"].as_i64().unwrap(), "{}", signature);
+    // `seller` of the former mapping is the order taker
+    assert_eq!(exchange.order_taker, stored["seller"].as_str().unwrap(), "{}", signature);
+    assert_eq!(exchange.asset_mint, stored["token_mint"].as_str().unwrap(), "{}", signature);
+    assert_eq!(exchange.currency_mint, stored["currency_mint"].as_str().unwrap(), "{}", signature);
+    assert_eq!(exchange.asset_change.parse::<f64>().unwrap(), stored["token_amount"].as_f64().unwrap(), "{}", signature);
+    // the former mapping stored what the order initializer received, the total cost without the market fee
+    let received = format_amount(exchange.total_cost_raw - exchange.market_fee_raw, exchange.currency_decimals);
+    assert_eq!(BigDecimal::from_str(received.as_str()).unwrap(), BigDecimal::from_str(stored["currency_amount"].to_string().as_str()).unwrap(), "{}", signature);
+}
+
+#[test]
+fn rpc_samples_resolve_to_the_stored_symbols() {
+    let store = read_sample("store");
+    let symbol = |asset_mint: &str, currency_mint: &str| store["assets"].as_array().unwrap().iter()
+        .find(|asset| asset["mint"] == asset_mint && asset["pair_mint"] == currency_mint)
+        .and_then(|asset| asset["symbol"].as_str().map(str::to_string));
+
+    for (input, output) in SAMPLES {
+        let exchanges = decode_sample(input);
+        for trade in read_sample(output).as_array().unwrap() {
+            let exchange = exchanges.iter().find(|exchange| exchange.signature == trade["signature"]).unwrap();
+            assert_eq!(symbol(exchange.asset_mint.as_str(), exchange.currency_mint.as_str()).as_deref(), trade["symbol"].as_str(), "{}", exchange.signature);
+        }
+    }
+}
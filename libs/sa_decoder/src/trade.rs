@@ -0,0 +1,52 @@
+//! Mapping of decoded exchanges into `trades` rows, shared by the substream-psql writer and the RPC backfill
+//! so both ingestion paths store identical rows.
+
+use std::str::FromStr;
+
+use anyhow::{Error, format_err};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use database_psql::model::Trade;
+use staratlas_symbols::symbol_store::{SymbolStore, unknown_symbol};
+
+use crate::pb::sa::trade::v1::ProcessExchange;
+
+/// Converts a rate of the decoder (1/1000000 units) into a fraction, e.g. `60000` -> `0.06`.
+pub fn rate_from_raw(rate: u64) -> BigDecimal {
+    BigDecimal::from_str(format!("{}e-6", rate).as_str()).unwrap_or_default().normalized()
+}
+
+/// Maps the exchange into a trade, the `source` is left empty for the writer to set.
+pub fn map_exchange_to_trade(exchange: ProcessExchange, symbol_store: &SymbolStore) -> Result<Trade, Error> {
+    let mut trade = Trade {
+        pk: exchange.pk,
+        symbol: "-none-".to_string(),
+        signature: exchange.signature,
+        block: exchange.block as i64,
+        timestamp: exchange.timestamp,
+        timestamp_ts: NaiveDateTime::from_timestamp_millis(exchange.timestamp * 1000)
+            .ok_or(format_err!("invalid timestamp {}", exchange.timestamp))?,
+        order_taker: exchange.order_taker,
+        currency_mint: exchange.currency_mint,
+        asset_mint: exchange.asset_mint,
+        order_initializer: exchange.order_initializer,
+        asset_receiving_wallet: exchange.asset_receiving_wallet,
+        asset_change: BigDecimal::from_str(exchange.asset_change.as_str())?,
+        currency_change: BigDecimal::from_str(exchange.currency_change.as_str())?,
+        market_fee: BigDecimal::from_str(exchange.market_fee.as_str())?,
+        price: BigDecimal::from_str(exchange.price.as_str())?,
+        total_cost: BigDecimal::from_str(exchange.total_cost.as_str())?,
+        fee_payer: exchange.fee_payer,
+        fee_rate: rate_from_raw(exchange.fee_rate),
+        royalty: rate_from_raw(exchange.royalty),
+        royalty_discount: rate_from_raw(exchange.royalty_discount),
+        source: String::new(),
+    };
+
+    // Pairs the store can't resolve are kept with a placeholder and fixed by substream-psql's `reconcile_unknown_symbols`
+    trade.symbol = symbol_store
+        .find_symbol(trade.asset_mint.as_str(), trade.currency_mint.as_str())
+        .unwrap_or_else(|| unknown_symbol(trade.asset_mint.as_str()));
+
+    return Ok(trade);
+}
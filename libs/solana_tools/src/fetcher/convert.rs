@@ -0,0 +1,103 @@
+//! Conversion of RPC transactions into the substreams `Block` model read by `sa_decoder`.
+
+use log::warn;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedTransactionWithStatusMeta, UiInstruction, UiTransactionStatusMeta, UiTransactionTokenBalance};
+use substreams_solana::pb::sol::v1::{CompiledInstruction, ConfirmedTransaction, InnerInstructions, Message, TokenBalance, Transaction, TransactionError, TransactionStatusMeta, UiTokenAmount};
+
+/// Converts a base64 encoded transaction, `None` if it isn't binary encoded or has no meta.
+///
+/// Like in the Firehose blocks, `account_keys` only holds the static keys of the message, addresses
+/// loaded from lookup tables are kept in the meta.
+pub fn to_confirmed_transaction(encoded: &EncodedTransactionWithStatusMeta) -> Option<ConfirmedTransaction> {
+    let transaction = encoded.transaction.decode()?;
+    let meta = encoded.meta.as_ref()?;
+
+    let message = Message {
+        account_keys: transaction.message.static_account_keys().iter().map(|key| key.to_bytes().to_vec()).collect(),
+        recent_blockhash: transaction.message.recent_blockhash().to_bytes().to_vec(),
+        instructions: transaction.message.instructions().iter()
+            .map(|instruction| CompiledInstruction {
+                program_id_index: instruction.program_id_index as u32,
+                accounts: instruction.accounts.clone(),
+                data: instruction.data.clone(),
+            })
+            .collect(),
+        versioned: transaction.message.address_table_lookups().is_some(),
+        ..Default::default()
+    };
+
+    Some(ConfirmedTransaction {
+        transaction: Some(Transaction {
+            signatures: transaction.signatures.iter().map(|signature| signature.as_ref().to_vec()).collect(),
+            message: Some(message),
+        }),
+        meta: Some(to_meta(meta)),
+    })
+}
+
+fn to_meta(meta: &UiTransactionStatusMeta) -> TransactionStatusMeta {
+    let inner_instructions = option(&meta.inner_instructions);
+    let loaded_addresses = option(&meta.loaded_addresses);
+
+    TransactionStatusMeta {
+        // only the presence of an error is read by the decoder
+        err: meta.err.as_ref().map(|err| TransactionError { err: err.to_string().into_bytes() }),
+        fee: meta.fee,
+        pre_balances: meta.pre_balances.clone(),
+        post_balances: meta.post_balances.clone(),
+        inner_instructions_none: inner_instructions.is_none(),
+        inner_instructions: inner_instructions.unwrap_or_default().iter()
+            .map(|inner| InnerInstructions {
+                index: inner.index as u32,
+                instructions: inner.instructions.iter().filter_map(to_compiled_instruction).collect(),
+            })
+            .collect(),
+        pre_token_balances: option(&meta.pre_token_balances).unwrap_or_default().iter().map(to_token_balance).collect(),
+        post_token_balances: option(&meta.post_token_balances).unwrap_or_default().iter().map(to_token_balance).collect(),
+        loaded_writable_addresses: loaded_addresses.as_ref().map(|loaded| decode_addresses(&loaded.writable)).unwrap_or_default(),
+        loaded_readonly_addresses: loaded_addresses.as_ref().map(|loaded| decode_addresses(&loaded.readonly)).unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+fn to_compiled_instruction(instruction: &UiInstruction) -> Option<CompiledInstruction> {
+    match instruction {
+        UiInstruction::Compiled(compiled) => Some(CompiledInstruction {
+            program_id_index: compiled.program_id_index as u32,
+            accounts: compiled.accounts.clone(),
+            data: bs58::decode(compiled.data.as_str()).into_vec().ok()?,
+        }),
+        UiInstruction::Parsed(_) => {
+            warn!("Skipping parsed inner instruction, fetch the transaction base64 encoded");
+            None
+        }
+    }
+}
+
+fn to_token_balance(balance: &UiTransactionTokenBalance) -> TokenBalance {
+    TokenBalance {
+        account_index: balance.account_index as u32,
+        mint: balance.mint.clone(),
+        owner: option(&balance.owner).unwrap_or_default(),
+        ui_token_amount: Some(UiTokenAmount {
+            ui_amount: balance.ui_token_amount.ui_amount.unwrap_or_default(),
+            decimals: balance.ui_token_amount.decimals as u32,
+            amount: balance.ui_token_amount.amount.clone(),
+            ui_amount_string: balance.ui_token_amount.ui_amount_string.clone(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn decode_addresses(addresses: &[String]) -> Vec<Vec<u8>> {
+    addresses.iter().filter_map(|address| bs58::decode(address).into_vec().ok()).collect()
+}
+
+fn option<T: Clone>(value: &OptionSerializer<T>) -> Option<T> {
+    match value {
+        OptionSerializer::Some(value) => Some(value.clone()),
+        OptionSerializer::None => None,
+        OptionSerializer::Skip => None,
+    }
+}
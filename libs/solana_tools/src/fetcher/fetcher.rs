@@ -1,103 +1,159 @@
-use log::{error, info, warn};
-use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use futures::stream::{self, StreamExt, TryStreamExt};
+use log::warn;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
 use solana_client::rpc_config::RpcTransactionConfig;
 use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
 use solana_program::pubkey::Pubkey;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::signature::Signature;
 use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use substreams_solana::pb::sol::v1::{Block, UnixTimestamp};
+
+use crate::fetcher::convert::to_confirmed_transaction;
+use crate::fetcher::rate_limit::TokenBucket;
+use crate::fetcher::retry::{FetcherError, RetryPolicy};
+
+/// getSignaturesForAddress returns at most 1000 signatures per call.
+const SIGNATURES_PER_REQUEST: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct FetcherConfig {
+    pub commitment: CommitmentConfig,
+    /// Requests per second across all concurrent requests, retries included.
+    pub requests_per_second: f64,
+    /// Requests which may be sent at once after an idle period.
+    pub burst: u32,
+    /// Transactions fetched concurrently.
+    pub max_concurrent: usize,
+    pub retry: RetryPolicy,
+}
 
-use solana_transaction_status::{
-    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
-    EncodedTransactionWithStatusMeta, UiInstruction, UiParsedInstruction, UiTransactionEncoding,
-};
-use staratlas::symbolstore::SymbolStore;
-use std::str::FromStr;
-use types::databasetrade::{DBTrade, Exchange};
-use types::tokentransafer::TokenTransfer;
+impl Default for FetcherConfig {
+    fn default() -> Self {
+        FetcherConfig {
+            commitment: CommitmentConfig::finalized(),
+            requests_per_second: 10.0,
+            burst: 10,
+            max_concurrent: 10,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
 
 pub struct Fetcher {
     pub client: RpcClient,
-    pub store: SymbolStore,
+    pub config: FetcherConfig,
+    limiter: TokenBucket,
 }
 
 impl Fetcher {
-    pub fn new(url: &str, store: SymbolStore) -> Fetcher {
+    pub fn new(url: &str) -> Fetcher {
+        Fetcher::with_config(url, FetcherConfig::default())
+    }
+
+    pub fn with_config(url: &str, config: FetcherConfig) -> Fetcher {
         Fetcher {
-            client: RpcClient::new(url),
-            store: store,
+            client: RpcClient::new_with_commitment(url.to_string(), config.commitment),
+            limiter: TokenBucket::new(config.requests_per_second, config.burst),
+            config,
         }
     }
 
-    pub fn fetch_signatures(
+    pub async fn fetch_signatures(
         &self,
         address: &str,
         limit: Option<usize>,
         before: Option<String>,
-    ) -> Vec<RpcConfirmedTransactionStatusWithSignature> {
-        let mut l_before = None;
-        if before != None {
-            l_before = Some(Signature::from_str(before.unwrap().as_str()).unwrap());
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>, FetcherError> {
+        let address = Pubkey::from_str(address).map_err(|err| FetcherError::InvalidInput(format!("address {}: {}", address, err)))?;
+        let before = match before {
+            None => None,
+            Some(before) => Some(parse_signature(before.as_str())?),
         };
 
-        let mut result: Option<Vec<RpcConfirmedTransactionStatusWithSignature>> = None;
-        while result == None {
-            result = match self.client.get_signatures_for_address_with_config(
-                &Pubkey::from_str(address).unwrap(),
+        self.config.retry.run("getSignaturesForAddress", &self.limiter, || {
+            self.client.get_signatures_for_address_with_config(
+                &address,
                 GetConfirmedSignaturesForAddress2Config {
-                    before: l_before,
+                    before,
                     until: None,
-                    limit: limit,
-                    commitment: Some(CommitmentConfig::finalized()),
+                    limit,
+                    commitment: Some(self.config.commitment),
                 },
-            ) {
-                Ok(data) => Some(data),
-                Err(err) => {
-                    warn!("{:?}", err.kind);
-                    None
-                }
+            )
+        }).await
+    }
+
+    /// Pages through the signatures of `address` until `start_slot` is reached and returns the ones
+    /// within `[start_slot, stop_slot]`, oldest first.
+    ///
+    /// Paging starts at the most recent signature, or at `before` when set, so pass the newest signature
+    /// of the range as `before` to skip the pages above `stop_slot` when backfilling old slots.
+    pub async fn fetch_signatures_between_slots(
+        &self,
+        address: &str,
+        start_slot: u64,
+        stop_slot: u64,
+        before: Option<String>,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>, FetcherError> {
+        let mut signatures = Vec::new();
+        let mut before = before;
+
+        loop {
+            let page = self.fetch_signatures(address, Some(SIGNATURES_PER_REQUEST), before.clone()).await?;
+            let last = match page.last() {
+                None => break,
+                Some(last) => last.clone(),
+            };
+
+            signatures.extend(
+                page.into_iter()
+                    .filter(|signature| signature.slot >= start_slot && signature.slot <= stop_slot),
+            );
+            if last.slot < start_slot {
+                break;
             }
+            before = Some(last.signature);
         }
-        return result.unwrap();
+
+        signatures.reverse();
+        Ok(signatures)
     }
 
-    pub fn fetch_transactions(
+    /// Fetches the successful transactions of `signatures`, up to `max_concurrent` at once, in the order of `signatures`.
+    pub async fn fetch_transactions(
         &self,
         signatures: &Vec<RpcConfirmedTransactionStatusWithSignature>,
-    ) -> Vec<EncodedConfirmedTransactionWithStatusMeta> {
-        let mut transactions = Vec::new();
-
-        signatures.into_iter().for_each(|signature| {
-            //Make sure to remove failed TXs
-            if signature.err == None {
-                transactions.push(self.fetch_transaction(signature.clone()));
-            }
-        });
-        return transactions;
+    ) -> Result<Vec<EncodedConfirmedTransactionWithStatusMeta>, FetcherError> {
+        stream::iter(signatures.iter().filter(|signature| signature.err == None))
+            .map(|signature| self.fetch_transaction(signature))
+            .buffered(self.config.max_concurrent.max(1))
+            .try_collect()
+            .await
     }
 
-    pub fn fetch_transaction(
+    /// Fetches the transaction base64 encoded, as the instruction data and account indexes are needed to decode it.
+    pub async fn fetch_transaction(
         &self,
-        signature: RpcConfirmedTransactionStatusWithSignature,
-    ) -> EncodedConfirmedTransactionWithStatusMeta {
-        let mut result: Option<EncodedConfirmedTransactionWithStatusMeta> = None;
-        while result == None {
-            result = match self.client.get_transaction_with_config(
-                &Signature::from_str(signature.signature.as_str()).unwrap(),
+        signature: &RpcConfirmedTransactionStatusWithSignature,
+    ) -> Result<EncodedConfirmedTransactionWithStatusMeta, FetcherError> {
+        let parsed = parse_signature(signature.signature.as_str())?;
+
+        self.config.retry.run("getTransaction", &self.limiter, || {
+            self.client.get_transaction_with_config(
+                &parsed,
                 RpcTransactionConfig {
-                    encoding: Some(UiTransactionEncoding::JsonParsed),
-                    commitment: Some(CommitmentConfig::finalized()),
-                    max_supported_transaction_version: None,
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(self.config.commitment),
+                    max_supported_transaction_version: Some(0),
                 },
-            ) {
-                Ok(data) => Some(data),
-                Err(err) => {
-                    warn!("{:?}", err.kind);
-                    None
-                }
-            }
-        }
-        return result.unwrap();
+            )
+        }).await
     }
 
     pub fn filter_transactions_for_exchange(
@@ -121,155 +177,27 @@ impl Fetcher {
             .collect::<Vec<_>>()
     }
 
-    pub fn map_transactions(
-        &self,
-        transactions: &Vec<EncodedConfirmedTransactionWithStatusMeta>,
-    ) -> Vec<DBTrade> {
-        let mut dbtrade: Vec<DBTrade> = Vec::new();
-
-        transactions.into_iter().for_each(|transaction| {
-            let signature = self
-                .find_signature(transaction.transaction.clone())
-                .unwrap_or("failed signature".to_string());
-
-            let mut exchanges = Vec::new();
-            match self.find_exchanges(transaction.transaction.clone()) {
-                None => {
-                    info!("{}", signature)
-                }
-                Some(ex) => exchanges = ex,
-            };
-            if exchanges.len() == 0 {
-                println!("{}", signature);
-            }
-
-            dbtrade.push(DBTrade {
-                signature: signature,
-                timestamp: transaction.block_time.clone().unwrap_or(0),
-                slot: transaction.slot.clone(),
-                symbol: self.find_symbol(exchanges.clone()),
-                exchange: Some(exchanges),
-            })
-        });
-
-        return dbtrade;
-    }
-
-    fn find_signature(&self, transaction: EncodedTransactionWithStatusMeta) -> Option<String> {
-        match transaction.transaction {
-            EncodedTransaction::LegacyBinary(_) => None,
-            EncodedTransaction::Binary(_, _) => None,
-            EncodedTransaction::Json(json) => {
-                return Some(json.signatures[0].clone());
-            }
-            EncodedTransaction::Accounts(_) => None,
-        }
-    }
-
-    fn find_exchanges(
-        &self,
-        transaction: EncodedTransactionWithStatusMeta,
-    ) -> Option<Vec<Exchange>> {
-        let mut exchange: Vec<Exchange> = Vec::new();
-
-        match transaction.meta.unwrap().inner_instructions.clone() {
-            OptionSerializer::Some(instructions) => {
-                for ins in instructions.iter() {
-                    if ins.instructions.len() == 3 {
-                        for i in (0..ins.clone().instructions.len()).step_by(3) {
-                            match self.to_parsed(Vec::from(&ins.instructions[i..(i + 3)])) {
-                                None => {
-                                    warn!("not added to array")
-                                }
-                                Some(parsed) => exchange.push(parsed),
-                            };
-                        }
-                    }
-                }
-            }
-            OptionSerializer::None => {}
-            OptionSerializer::Skip => {}
-        }
-        return Some(exchange);
-    }
-
-    fn to_parsed(&self, instructions: Vec<UiInstruction>) -> Option<Exchange> {
-        if instructions.len() == 3 {
-            let mut token_transfers: Vec<TokenTransfer> = Vec::new();
-
-            //Cast Some to Json(Vec<TokenTransfer>)
-            instructions.iter().for_each(|ins| match ins {
-                UiInstruction::Compiled(_) => {}
-                UiInstruction::Parsed(parsed) => match parsed {
-                    UiParsedInstruction::PartiallyDecoded(_) => {}
-                    UiParsedInstruction::Parsed(p) => {
-                        match serde_json::from_value(p.parsed.clone()) {
-                            Ok(data) => token_transfers.push(data),
-                            Err(err) => {
-                                warn!("{}", err)
-                            }
-                        };
-                    }
-                },
+    /// Fetches the transactions of `signatures` and groups them into blocks, ordered by slot.
+    ///
+    /// The blocks only contain the fetched transactions, which is all `sa_decoder::process_blocks` needs.
+    pub async fn fetch_blocks(&self, signatures: &Vec<RpcConfirmedTransactionStatusWithSignature>) -> Result<Vec<Block>, FetcherError> {
+        let mut blocks: BTreeMap<u64, Block> = BTreeMap::new();
+
+        for transaction in self.fetch_transactions(signatures).await? {
+            let block = blocks.entry(transaction.slot).or_insert_with(|| Block {
+                slot: transaction.slot,
+                block_time: transaction.block_time.map(|timestamp| UnixTimestamp { timestamp }),
+                ..Default::default()
             });
-            if token_transfers.len() == 3 {
-                //MAPPING
-                //1. Remove 0 Index not needed
-                token_transfers.remove(0);
-
-                //2. Find Index of Currency
-                let idx_currency = token_transfers
-                    .iter()
-                    .position(|transfer| {
-                        self.store
-                            .currencies
-                            .clone()
-                            .iter()
-                            .any(|currency| transfer.info.mint.contains(currency.mint.as_str()))
-                    })
-                    .unwrap();
-                let idx_token = token_transfers
-                    .iter()
-                    .position(|transfer| {
-                        self.store
-                            .assets
-                            .clone()
-                            .iter()
-                            .any(|asset| transfer.info.mint.contains(asset.mint.as_str()))
-                    })
-                    .unwrap();
-
-                return Some(Exchange {
-                    side: idx_token == 1,
-                    seller: token_transfers[idx_currency].info.authority.clone(),
-                    buyer: token_transfers[idx_token].info.authority.clone(),
-                    currency_mint: token_transfers[idx_currency].info.mint.clone(),
-                    token_mint: token_transfers[idx_token].info.mint.clone(),
-                    currency_amount: token_transfers[idx_currency]
-                        .info
-                        .token_amount
-                        .ui_amount
-                        .clone(),
-                    token_amount: token_transfers[idx_token]
-                        .info
-                        .token_amount
-                        .ui_amount
-                        .clone(),
-                });
-            } else {
-                warn!("token transfer not len 3");
-                return None;
+            match to_confirmed_transaction(&transaction.transaction) {
+                Some(confirmed) => block.transactions.push(confirmed),
+                None => warn!("Unable to decode transaction of slot {}", transaction.slot),
             }
-        } else {
-            warn!("Instructions length is not 3")
         }
-        return None;
+        Ok(blocks.into_values().collect())
     }
+}
 
-    fn find_symbol(&self, exchanges: Vec<Exchange>) -> String {
-        let symbol = self.store.assets.clone().into_iter().find(|asset| {
-            asset.mint == exchanges[0].token_mint && asset.pair_mint == exchanges[0].currency_mint
-        });
-        return symbol.unwrap().symbol;
-    }
+fn parse_signature(signature: &str) -> Result<Signature, FetcherError> {
+    Signature::from_str(signature).map_err(|err| FetcherError::InvalidInput(format!("signature {}: {}", signature, err)))
 }
@@ -1 +1,4 @@
-pub mod fetcher;
\ No newline at end of file
+pub mod convert;
+pub mod fetcher;
+pub mod rate_limit;
+pub mod retry;
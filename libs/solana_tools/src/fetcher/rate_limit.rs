@@ -0,0 +1,43 @@
+use std::sync::Mutex;
+
+use tokio::time::{Duration, Instant, sleep};
+
+/// Token bucket shared by all requests of a `Fetcher`.
+///
+/// Holds up to `capacity` tokens and refills `refill_per_sec` tokens per second, every request
+/// takes one token and waits when the bucket is empty.
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    pub fn new(refill_per_sec: f64, capacity: u32) -> TokenBucket {
+        let capacity = capacity.max(1) as f64;
+        TokenBucket {
+            capacity,
+            refill_per_sec: refill_per_sec.max(f64::MIN_POSITIVE),
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    /// Waits until a token is available and takes it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let (tokens, last_refill) = *state;
+                let now = Instant::now();
+                let tokens = (tokens + now.duration_since(last_refill).as_secs_f64() * self.refill_per_sec).min(self.capacity);
+                if tokens >= 1.0 {
+                    *state = (tokens - 1.0, now);
+                    return;
+                }
+                *state = (tokens, now);
+                Duration::from_secs_f64((1.0 - tokens) / self.refill_per_sec)
+            };
+            sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+use std::future::Future;
+
+use log::warn;
+use rand::Rng;
+use thiserror::Error;
+use tokio::time::{Duration, sleep};
+
+use crate::fetcher::rate_limit::TokenBucket;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum FetcherError {
+    #[error("{method} failed after {attempts} attempts: {reason}")]
+    MaxAttempts { method: String, attempts: u32, reason: String },
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+}
+
+/// Exponential backoff with full jitter, the n-th retry waits a random delay up to
+/// `min(max_delay, base_delay * 2^(n-1))`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Upper bound of the delay before the `attempt`-th retry, starting at 1.
+    pub fn max_backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let max = self.max_backoff(attempt).as_millis() as u64;
+        Duration::from_millis(rand::thread_rng().gen_range(0..=max))
+    }
+
+    /// Runs `request` until it succeeds or `max_attempts` is reached, every attempt takes a token of `limiter`.
+    pub async fn run<T, E, F, Fut>(&self, method: &str, limiter: &TokenBucket, request: F) -> Result<T, FetcherError>
+        where E: Display,
+              F: Fn() -> Fut,
+              Fut: Future<Output=Result<T, E>> {
+        let mut attempt = 0;
+        loop {
+            limiter.acquire().await;
+            attempt += 1;
+            match request().await {
+                Ok(result) => return Ok(result),
+                Err(err) if attempt >= self.max_attempts.max(1) => {
+                    return Err(FetcherError::MaxAttempts { method: method.to_string(), attempts: attempt, reason: err.to_string() });
+                }
+                Err(err) => {
+                    let delay = self.backoff(attempt);
+                    warn!("{} failed (attempt {}/{}), retrying in {:?}: {}", method, attempt, self.max_attempts, delay, err);
+                    sleep(delay).await;
+                }
+            }
+        }
+    }
+}
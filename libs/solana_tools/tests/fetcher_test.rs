@@ -1,54 +1,44 @@
 #[cfg(test)]
 mod fetcher_test {
-    use assert_json_diff::assert_json_eq;
-    use helper::filehelper::{read_file, write_file};
-    use solana_client::rpc_client::RpcClient;
+    use std::fs;
+
+    use helper::filehelper::read_file;
+    use prost::Message;
+    use serde_json::Value;
     use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+    use solana_tools::fetcher::convert::to_confirmed_transaction;
     use solana_tools::fetcher::fetcher::Fetcher;
     use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
-    use staratlas::symbolstore::SymbolStore;
-    use types::databasetrade::DBTrade;
+    use substreams_solana::pb::sol::v1::{Block, UnixTimestamp};
 
-    #[test]
-    fn get_signatures() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
-        };
+    #[tokio::test]
+    async fn get_signatures() {
+        let fetcher = Fetcher::new("https://ssc-dao.genesysgo.net/");
 
         let signatures = fetcher.fetch_signatures(
             "traderDnaR5w6Tcoi3NFm53i48FTDNbGjBSZwWXDRrg",
             Some(10),
             None,
-        );
+        ).await.unwrap();
         assert_eq!(signatures.len(), 10);
     }
 
-    #[test]
-    fn get_transactions() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
-        };
+    #[tokio::test]
+    async fn get_transactions() {
+        let fetcher = Fetcher::new("https://ssc-dao.genesysgo.net/");
 
         let signatures = fetcher.fetch_signatures(
             "traderDnaR5w6Tcoi3NFm53i48FTDNbGjBSZwWXDRrg",
             Some(10),
             None,
-        );
-        let transactions = fetcher.fetch_transactions(&signatures);
+        ).await.unwrap();
+        let transactions = fetcher.fetch_transactions(&signatures).await.unwrap();
         assert_eq!(transactions.len(), 10);
     }
 
     #[test]
     fn filter_transactions() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
-        };
+        let fetcher = Fetcher::new("https://ssc-dao.genesysgo.net/");
 
         let transactions = read_file::<Vec<EncodedConfirmedTransactionWithStatusMeta>>(
             "tests/samples/input/sig_list.in.sample.json",
@@ -65,108 +55,113 @@ mod fetcher_test {
         assert_eq!(less, true);
     }
 
-    #[test]
-    fn map_transaction_001() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
-        };
-
-        //Load sample file INPUT
-        let filtered_transactions = read_file::<Vec<EncodedConfirmedTransactionWithStatusMeta>>(
-            "tests/samples/input/tx_001.in.sample.json",
-        );
-
-        let mapped = fetcher.map_transactions(&filtered_transactions);
-
-        //Load sample file OUTPUT
-        let expected = read_file::<Vec<DBTrade>>("tests/samples/output/db_001.out.sample.json");
-
-        assert_json_eq!(mapped, expected);
-    }
-
-    #[test]
-    fn map_transaction_002() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
-        };
+    #[tokio::test]
+    async fn ignore_failed_transaction() {
+        let fetcher = Fetcher::new("https://ssc-dao.genesysgo.net/");
 
         //Load sample file INPUT
         let signatures = read_file::<Vec<RpcConfirmedTransactionStatusWithSignature>>(
-            "tests/samples/input/sig_002.in.sample.json",
+            "tests/samples/input/sig_failed.in.sample.json",
         );
 
-        let transactions = fetcher.fetch_transactions(&signatures);
-        let filtered_transactions = fetcher.filter_transactions_for_exchange(transactions);
-
-        let mapped = fetcher.map_transactions(&filtered_transactions);
-
-        //Load sample file OUTPUT
-        let expected = read_file::<Vec<DBTrade>>("tests/samples/output/db_002.out.sample.json");
+        let blocks = fetcher.fetch_blocks(&signatures).await.unwrap();
 
-        assert_json_eq!(mapped, expected);
+        assert_eq!(blocks.len(), 0);
     }
-    #[test]
-    fn map_transaction_003() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
-        };
-
-        //Load sample file INPUT
-        let signatures = read_file::<Vec<RpcConfirmedTransactionStatusWithSignature>>(
-            "tests/samples/input/sig_003.in.sample.json",
-        );
-        let transactions = fetcher.fetch_transactions(&signatures);
-        let filtered_transactions = fetcher.filter_transactions_for_exchange(transactions);
-        let mapped = fetcher.map_transactions(&filtered_transactions);
 
-        //Load sample file OUTPUT
-        let expected = read_file::<Vec<DBTrade>>("tests/samples/output/db_003.out.sample.json");
-
-        assert_json_eq!(mapped, expected);
+    #[tokio::test]
+    async fn decode_fetched_transactions() {
+        let fetcher = Fetcher::new("https://ssc-dao.genesysgo.net/");
+
+        for (input, output) in [("sig_002", "db_002"), ("sig_003", "db_003")] {
+            //Load sample file INPUT
+            let signatures = read_file::<Vec<RpcConfirmedTransactionStatusWithSignature>>(
+                format!("tests/samples/input/{}.in.sample.json", input).as_str(),
+            );
+            let blocks = fetcher.fetch_blocks(&signatures).await.unwrap();
+
+            let mut exchanges = vec![];
+            for block in blocks {
+                sa_decoder::process_blocks(block, &mut exchanges, &mut vec![], &mut vec![]);
+            }
+
+            //Load sample file OUTPUT
+            let expected = read_file::<Vec<Value>>(format!("tests/samples/output/{}.out.sample.json", output).as_str());
+            let trade = &expected[0]["exchange"][0];
+            assert_eq!(exchanges.len(), 1);
+            assert_eq!(expected[0]["signature"], exchanges[0].signature.as_str());
+            assert_eq!(expected[0]["slot"], exchanges[0].block);
+            assert_eq!(expected[0]["timestamp"], exchanges[0].timestamp);
+            assert_eq!(trade["token_mint"], exchanges[0].asset_mint.as_str());
+            assert_eq!(trade["currency_mint"], exchanges[0].currency_mint.as_str());
+            assert_eq!(trade["token_amount"].as_f64(), exchanges[0].asset_change.parse::<f64>().ok());
+        }
     }
 
     #[test]
-    fn map_transactions() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
-        };
-
-        let filtered_transactions = read_file::<Vec<EncodedConfirmedTransactionWithStatusMeta>>(
-            "tests/samples/input/transactions-filtered.in.sample.json",
+    fn decode_base64_transaction() {
+        //Load sample file INPUT, the mainnet transaction of tx_001 as returned with base64 encoding
+        let transactions = read_file::<Vec<EncodedConfirmedTransactionWithStatusMeta>>(
+            "tests/samples/input/exchange_base64.in.sample.json",
         );
-
-        let mapped = fetcher.map_transactions(&filtered_transactions);
-        let expected =
-            read_file::<Vec<DBTrade>>("tests/samples/output/transactions-filtered.out.sample.json");
-
-        assert_json_eq!(mapped, expected);
-    }
-
-    #[test]
-    fn ignore_failed_transaction() {
-        let store = read_file::<SymbolStore>("tests/samples/input/store.sample.json");
-        let fetcher = Fetcher {
-            client: RpcClient::new("https://ssc-dao.genesysgo.net/".to_string()),
-            store: store,
+        let block = Block {
+            slot: transactions[0].slot,
+            block_time: transactions[0].block_time.map(|timestamp| UnixTimestamp { timestamp }),
+            transactions: transactions.iter().filter_map(|transaction| to_confirmed_transaction(&transaction.transaction)).collect(),
+            ..Default::default()
         };
 
-        //Load sample file INPUT
-        let signatures = read_file::<Vec<RpcConfirmedTransactionStatusWithSignature>>(
-            "tests/samples/input/sig_failed.in.sample.json",
-        );
-
-        let transactions = fetcher.fetch_transactions(&signatures);
-        let filtered_transactions = fetcher.filter_transactions_for_exchange(transactions);
-        let mapped = fetcher.map_transactions(&filtered_transactions);
+        let mut exchanges = vec![];
+        let mut warnings = vec![];
+        sa_decoder::process_blocks(block, &mut exchanges, &mut warnings, &mut vec![]);
+
+        assert!(warnings.is_empty(), "unexpected warnings {:?}", warnings);
+        assert_eq!(exchanges.len(), 1);
+        assert_eq!(exchanges[0].signature, "HyKP1yrsHPu75ym8zVD9EKS4KAYPnRcB1HfazyP1Cvh32hFnvuP7gkQBQLhw7qZwTzcxfgpoVKqgaBn6DH7J6X3");
+        assert_eq!(exchanges[0].block, 153357697);
+        assert_eq!(exchanges[0].timestamp, 1664722193);
+        assert_eq!(exchanges[0].asset_mint, "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK");
+        assert_eq!(exchanges[0].currency_mint, "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx");
+        assert_eq!(exchanges[0].asset_receiving_wallet, "FsMRWqwGibZFiNgk7JBKKcAqPkNbgpDVf6jJHWsaEwNx");
+        assert_eq!(exchanges[0].asset_change, "250");
+        assert_eq!(exchanges[0].price, "0.00215039");
+        assert_eq!(exchanges[0].market_fee, "0.03225585");
+        assert_eq!(exchanges[0].fee_payer, "seller");
+    }
 
-        assert_json_eq!(mapped.len(), 0);
+    /// Writes the mainnet block fixtures of sa_decoder, the golden files next to them are maintained by hand.
+    #[tokio::test]
+    #[ignore]
+    async fn capture_decoder_fixtures() {
+        let fetcher = Fetcher::new("https://ssc-dao.genesysgo.net/");
+
+        // (fixture, transactions of the block), all of one slot
+        for (name, signatures) in [
+            ("exchange_args_48", vec!["HyKP1yrsHPu75ym8zVD9EKS4KAYPnRcB1HfazyP1Cvh32hFnvuP7gkQBQLhw7qZwTzcxfgpoVKqgaBn6DH7J6X3"]),
+            ("exchanges_per_block", vec![
+                "3vCN8pUuayAC3JTUspbqzHe7DLTGpXcEbEJQH3Y4p1Xtv2ZuQvPHyRQ2Vpj2DKsfhrL94cCi1HzBPTmSc1AQxDmv",
+                "3B33AAd7gcCz2dNX5FKBAhENcQ1PE19PCjy8vb4NjSPW687LfFtKAaujs72XjhGYppVun69F9Lm11FbraBNhqaYw",
+            ]),
+            ("failed_transaction", vec![
+                "4bPfTKtGPLfwnsVvQMR95Y7pc9aRGWbER1VzawiL6ZboR8YyYodRqACCE2YxN6Gufx1rbDSGNeg5WDhXESoC58Kh",
+                "437i1FtzuUn6NJkm7YXdoXtBtQKfxHDmYxpqRumkZ3C8jZEVxwBF43n33wJ8EDwRf2vyzsUTb189vs1yXRejh8EL",
+            ]),
+        ] {
+            // the status is left without error, `fetch_blocks` would skip the failed transaction otherwise
+            let signatures = signatures.into_iter()
+                .map(|signature| RpcConfirmedTransactionStatusWithSignature {
+                    signature: signature.to_string(),
+                    slot: 0,
+                    err: None,
+                    memo: None,
+                    block_time: None,
+                    confirmation_status: None,
+                })
+                .collect();
+            let blocks = fetcher.fetch_blocks(&signatures).await.unwrap();
+
+            assert_eq!(blocks.len(), 1, "{}: transactions of several slots", name);
+            fs::write(format!("../sa_decoder/tests/fixtures/{}.binpb", name), blocks[0].encode_to_vec()).unwrap();
+        }
     }
 }
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod retry_test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use solana_tools::fetcher::rate_limit::TokenBucket;
+    use solana_tools::fetcher::retry::{FetcherError, RetryPolicy};
+    use tokio::time::{Duration, Instant};
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+
+        assert_eq!(policy.max_backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.max_backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.max_backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.max_backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.max_backoff(64), Duration::from_secs(1));
+        for attempt in 1..10 {
+            assert!(policy.backoff(attempt) <= policy.max_backoff(attempt));
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let limiter = TokenBucket::new(1000.0, 10);
+
+        let result = policy(5).run("test", &limiter, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err("rate limited"),
+                _ => Ok(42),
+            }
+        }).await;
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let limiter = TokenBucket::new(1000.0, 10);
+
+        let result: Result<(), FetcherError> = policy(3).run("getTransaction", &limiter, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err("429 Too Many Requests")
+        }).await;
+
+        assert_eq!(result, Err(FetcherError::MaxAttempts {
+            method: "getTransaction".to_string(),
+            attempts: 3,
+            reason: "429 Too Many Requests".to_string(),
+        }));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn token_bucket_limits_the_rate() {
+        let limiter = TokenBucket::new(10.0, 2);
+        let started = Instant::now();
+
+        for _ in 0..12 {
+            limiter.acquire().await;
+        }
+
+        // the burst of 2 is free, the other 10 tokens refill at 10 per second
+        assert!(started.elapsed() >= Duration::from_millis(999));
+        assert!(started.elapsed() < Duration::from_millis(1100));
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use borsh::BorshDeserialize;
+use log::{info, warn};
+use marketplace_interface::{REGISTERED_CURRENCY_ACCOUNT_DISCM, RegisteredCurrency};
+use serde_json::{json, Value};
+use solana_program::pubkey::Pubkey;
+
+use helper::filehelper::{try_read_file, try_write_file};
+use staratlas_symbols::symbol_store::Currency;
+
+use crate::symbol_source::SymbolSourceError;
+
+pub const MAINNET_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+/// getMultipleAccounts accepts at most 100 keys per call.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Somewhere the currencies registered on the GalacticMarketplace can be loaded from.
+#[async_trait]
+pub trait CurrencySource: Send + Sync {
+    async fn load(&self) -> Result<Vec<Currency>, SymbolSourceError>;
+
+    fn name(&self) -> String;
+}
+
+/// Reads the `RegisteredCurrency` accounts of the marketplace program, the decimals come from the
+/// mint and the name from the token metadata symbol.
+pub struct RpcCurrencySource {
+    pub rpc_url: String,
+}
+
+impl Default for RpcCurrencySource {
+    fn default() -> Self {
+        RpcCurrencySource { rpc_url: MAINNET_RPC_URL.to_string() }
+    }
+}
+
+#[async_trait]
+impl CurrencySource for RpcCurrencySource {
+    async fn load(&self) -> Result<Vec<Currency>, SymbolSourceError> {
+        let mints: Vec<String> = self
+            .registered_currencies()
+            .await?
+            .iter()
+            .map(|currency| currency.token_mint.to_string())
+            .collect();
+        if mints.is_empty() {
+            return Err(SymbolSourceError::Rpc("no registered currencies found".to_string()));
+        }
+
+        let mut decimals = vec![];
+        let mut names = vec![];
+        for chunk in mints.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+            let mint_accounts = self.multiple_accounts(chunk.to_vec(), "jsonParsed").await?;
+            decimals.extend(mint_accounts.iter().map(|account| account["data"]["parsed"]["info"]["decimals"].as_u64()));
+
+            let metadata_keys = chunk.iter().map(|mint| metadata_address(mint.as_str())).collect::<Result<Vec<String>, SymbolSourceError>>()?;
+            let metadata_accounts = self.multiple_accounts(metadata_keys, "base64").await?;
+            names.extend(metadata_accounts.iter().map(|account| account_data(account).and_then(|data| parse_metadata_symbol(data.as_slice()))));
+        }
+
+        let mut currencies = vec![];
+        for ((mint, decimals), name) in mints.into_iter().zip(decimals).zip(names) {
+            match decimals {
+                Some(decimals) => currencies.push(Currency {
+                    name: name.unwrap_or_else(|| {
+                        warn!("No metadata symbol for currency {}, using the mint as name", mint);
+                        mint.clone()
+                    }),
+                    mint,
+                    decimals: decimals as u32,
+                }),
+                None => warn!("Skipping registered currency {}, mint account not found", mint),
+            }
+        }
+        info!("Loaded {} registered currencies", currencies.len());
+        Ok(currencies)
+    }
+
+    fn name(&self) -> String {
+        format!("rpc {}", self.rpc_url)
+    }
+}
+
+impl RpcCurrencySource {
+    /// Royalty currently registered per currency mint, in the 1/1000000 units of the decoder.
+    pub async fn royalties(&self) -> Result<HashMap<String, u64>, SymbolSourceError> {
+        Ok(self
+            .registered_currencies()
+            .await?
+            .into_iter()
+            .map(|currency| (currency.token_mint.to_string(), currency.royalty))
+            .collect())
+    }
+
+    async fn registered_currencies(&self) -> Result<Vec<RegisteredCurrency>, SymbolSourceError> {
+        let accounts = self.rpc("getProgramAccounts", json!([
+            marketplace_interface::id().to_string(),
+            {
+                "encoding": "base64",
+                "filters": [{ "memcmp": { "offset": 0, "bytes": bs58::encode(REGISTERED_CURRENCY_ACCOUNT_DISCM).into_string() } }]
+            }
+        ])).await?;
+
+        Ok(accounts
+            .as_array()
+            .ok_or_else(|| SymbolSourceError::Rpc("getProgramAccounts: unexpected response".to_string()))?
+            .iter()
+            .filter_map(|account| account_data(&account["account"]))
+            .filter_map(|data| decode_registered_currency(data.as_slice()))
+            .collect())
+    }
+
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value, SymbolSourceError> {
+        let response = reqwest::Client::new()
+            .post(self.rpc_url.as_str())
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+
+        if !response["error"].is_null() {
+            return Err(SymbolSourceError::Rpc(format!("{}: {}", method, response["error"])));
+        }
+        Ok(response["result"].clone())
+    }
+
+    async fn multiple_accounts(&self, keys: Vec<String>, encoding: &str) -> Result<Vec<Value>, SymbolSourceError> {
+        let result = self.rpc("getMultipleAccounts", json!([keys, { "encoding": encoding }])).await?;
+        result["value"]
+            .as_array()
+            .cloned()
+            .ok_or_else(|| SymbolSourceError::Rpc("getMultipleAccounts: unexpected response".to_string()))
+    }
+}
+
+/// Reads a currency snapshot written by `CachingCurrencySource`.
+pub struct FileCurrencySource {
+    pub path: String,
+}
+
+#[async_trait]
+impl CurrencySource for FileCurrencySource {
+    async fn load(&self) -> Result<Vec<Currency>, SymbolSourceError> {
+        try_read_file::<Vec<Currency>>(self.path.as_str()).map_err(|err| SymbolSourceError::File(self.path.clone(), err))
+    }
+
+    fn name(&self) -> String {
+        format!("file {}", self.path)
+    }
+}
+
+/// Persists the currencies loaded by `inner` to `path`, used as offline snapshot.
+pub struct CachingCurrencySource {
+    pub inner: Box<dyn CurrencySource>,
+    pub path: String,
+}
+
+#[async_trait]
+impl CurrencySource for CachingCurrencySource {
+    async fn load(&self) -> Result<Vec<Currency>, SymbolSourceError> {
+        let currencies = self.inner.load().await?;
+        if let Err(err) = try_write_file(self.path.as_str(), &currencies) {
+            warn!("Unable to write currency snapshot {}: {}", self.path, err);
+        }
+        Ok(currencies)
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+}
+
+/// Tries the sources in order and returns the first currencies that could be loaded.
+pub struct FallbackCurrencySource {
+    pub sources: Vec<Box<dyn CurrencySource>>,
+}
+
+#[async_trait]
+impl CurrencySource for FallbackCurrencySource {
+    async fn load(&self) -> Result<Vec<Currency>, SymbolSourceError> {
+        for source in self.sources.iter() {
+            match source.load().await {
+                Ok(currencies) => return Ok(currencies),
+                Err(err) => warn!("Unable to load currencies from {}: {}", source.name(), err),
+            }
+        }
+        Err(SymbolSourceError::Exhausted)
+    }
+
+    fn name(&self) -> String {
+        let names: Vec<String> = self.sources.iter().map(|source| source.name()).collect();
+        format!("fallback [{}]", names.join(", "))
+    }
+}
+
+/// Loads the registered currencies over rpc and keeps a snapshot in `snapshot_path` for offline use.
+pub fn cached_rpc_currency_source(rpc_url: &str, snapshot_path: &str) -> FallbackCurrencySource {
+    FallbackCurrencySource {
+        sources: vec![
+            Box::new(CachingCurrencySource {
+                inner: Box::new(RpcCurrencySource { rpc_url: rpc_url.to_string() }),
+                path: snapshot_path.to_string(),
+            }),
+            Box::new(FileCurrencySource { path: snapshot_path.to_string() }),
+        ],
+    }
+}
+
+/// Price scale of pairs traded in a currency with `decimals`, e.g. `6` -> `1000000`.
+pub fn pricescale(decimals: u32) -> i64 {
+    10i64.pow(decimals)
+}
+
+pub fn decode_registered_currency(data: &[u8]) -> Option<RegisteredCurrency> {
+    if data.len() < 8 || data[..8] != REGISTERED_CURRENCY_ACCOUNT_DISCM {
+        return None;
+    }
+    // accounts are allocated with spare room for more royalty tiers, so trailing bytes are expected
+    RegisteredCurrency::deserialize(&mut &data[8..]).ok()
+}
+
+/// Symbol of a token metadata account, `None` if the account is too short or the symbol empty.
+pub fn parse_metadata_symbol(data: &[u8]) -> Option<String> {
+    // key (1), update authority (32), mint (32), then the borsh strings name and symbol
+    let mut offset = 1 + 32 + 32;
+    let mut read_string = |data: &[u8]| -> Option<String> {
+        let length = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize;
+        let value = data.get(offset + 4..offset + 4 + length)?;
+        offset += 4 + length;
+        Some(String::from_utf8_lossy(value).trim_matches(char::from(0)).trim().to_string())
+    };
+
+    let _name = read_string(data)?;
+    let symbol = read_string(data)?;
+    if symbol.is_empty() {
+        return None;
+    }
+    Some(symbol)
+}
+
+/// Address of the token metadata account of `mint`.
+pub fn metadata_address(mint: &str) -> Result<String, SymbolSourceError> {
+    let program_id = Pubkey::from_str(METADATA_PROGRAM_ID)
+        .map_err(|err| SymbolSourceError::Rpc(format!("invalid metadata program id: {}", err)))?;
+    let mint = Pubkey::from_str(mint)
+        .map_err(|err| SymbolSourceError::Rpc(format!("invalid mint {}: {}", mint, err)))?;
+    let (address, _) = Pubkey::find_program_address(&[b"metadata", program_id.as_ref(), mint.as_ref()], &program_id);
+    Ok(address.to_string())
+}
+
+fn account_data(account: &Value) -> Option<Vec<u8>> {
+    BASE64.decode(account["data"][0].as_str()?).ok()
+}
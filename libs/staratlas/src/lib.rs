@@ -1 +1,3 @@
+pub mod currency_source;
+pub mod symbol_source;
 pub mod symbolstore;
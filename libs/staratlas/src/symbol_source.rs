@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use log::{info, warn};
+use thiserror::Error;
+
+use helper::filehelper::{try_read_file, try_write_file};
+use staratlas_nft::staratlasnft::parse_nfts;
+use staratlas_symbols::symbol_store::SymbolStore;
+
+use crate::currency_source::{CurrencySource, RpcCurrencySource};
+use crate::symbolstore::map_data;
+
+pub const GALAXY_NFTS_URL: &str = "https://galaxy.staratlas.com/nfts";
+
+#[derive(Error, Debug)]
+pub enum SymbolSourceError {
+    #[error("galaxy api request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("symbol file {0}: {1}")]
+    File(String, std::io::Error),
+    #[error("rpc request failed: {0}")]
+    Rpc(String),
+    #[error("no symbol source succeeded")]
+    Exhausted,
+}
+
+/// Somewhere a `SymbolStore` can be loaded from.
+#[async_trait]
+pub trait SymbolSource: Send + Sync {
+    async fn load(&self) -> Result<SymbolStore, SymbolSourceError>;
+
+    fn name(&self) -> String;
+}
+
+/// Builds the store from the nfts listed by the Star Atlas galaxy api, paired with every currency
+/// loaded from `currencies`.
+pub struct HttpSymbolSource {
+    pub url: String,
+    pub currencies: Box<dyn CurrencySource>,
+}
+
+impl HttpSymbolSource {
+    pub fn new<C: CurrencySource + 'static>(url: &str, currencies: C) -> HttpSymbolSource {
+        HttpSymbolSource { url: url.to_string(), currencies: Box::new(currencies) }
+    }
+}
+
+impl Default for HttpSymbolSource {
+    fn default() -> Self {
+        HttpSymbolSource::new(GALAXY_NFTS_URL, RpcCurrencySource::default())
+    }
+}
+
+#[async_trait]
+impl SymbolSource for HttpSymbolSource {
+    async fn load(&self) -> Result<SymbolStore, SymbolSourceError> {
+        let items = reqwest::get(self.url.as_str())
+            .await?
+            .error_for_status()?
+            .json::<Vec<serde_json::Value>>()
+            .await?;
+        let data = parse_nfts(items);
+        let currencies = self.currencies.load().await?;
+
+        Ok(map_data(data, currencies))
+    }
+
+    fn name(&self) -> String {
+        format!("http {}", self.url)
+    }
+}
+
+/// Reads a store written with `helper::filehelper`, e.g. `tests/store.sample.json`.
+pub struct FileSymbolSource {
+    pub path: String,
+}
+
+#[async_trait]
+impl SymbolSource for FileSymbolSource {
+    async fn load(&self) -> Result<SymbolStore, SymbolSourceError> {
+        try_read_file::<SymbolStore>(self.path.as_str()).map_err(|err| SymbolSourceError::File(self.path.clone(), err))
+    }
+
+    fn name(&self) -> String {
+        format!("file {}", self.path)
+    }
+}
+
+/// Persists every store loaded by `inner` to `path`, failing writes are only logged.
+pub struct CachingSymbolSource {
+    pub inner: Box<dyn SymbolSource>,
+    pub path: String,
+}
+
+#[async_trait]
+impl SymbolSource for CachingSymbolSource {
+    async fn load(&self) -> Result<SymbolStore, SymbolSourceError> {
+        let store = self.inner.load().await?;
+        match try_write_file(self.path.as_str(), &store) {
+            Ok(_) => info!("Symbol store cached to {}", self.path),
+            Err(err) => warn!("Unable to cache symbol store to {}: {}", self.path, err),
+        }
+        Ok(store)
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+}
+
+/// Tries the sources in order and returns the first store that could be loaded.
+pub struct FallbackSymbolSource {
+    pub sources: Vec<Box<dyn SymbolSource>>,
+}
+
+#[async_trait]
+impl SymbolSource for FallbackSymbolSource {
+    async fn load(&self) -> Result<SymbolStore, SymbolSourceError> {
+        for source in self.sources.iter() {
+            match source.load().await {
+                Ok(store) => {
+                    info!("Symbol store loaded from {}", source.name());
+                    return Ok(store);
+                }
+                Err(err) => warn!("Unable to load symbol store from {}: {}", source.name(), err),
+            }
+        }
+        Err(SymbolSourceError::Exhausted)
+    }
+
+    fn name(&self) -> String {
+        let names: Vec<String> = self.sources.iter().map(|source| source.name()).collect();
+        format!("fallback [{}]", names.join(", "))
+    }
+}
+
+/// Loads from the galaxy api and keeps the last good fetch in `cache_path`, which is used when the api
+/// is unreachable.
+pub fn cached_galaxy_source<C: CurrencySource + 'static>(cache_path: &str, currencies: C) -> FallbackSymbolSource {
+    FallbackSymbolSource {
+        sources: vec![
+            Box::new(CachingSymbolSource {
+                inner: Box::new(HttpSymbolSource::new(GALAXY_NFTS_URL, currencies)),
+                path: cache_path.to_string(),
+            }),
+            Box::new(FileSymbolSource { path: cache_path.to_string() }),
+        ],
+    }
+}
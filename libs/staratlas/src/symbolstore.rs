@@ -1,113 +1,106 @@
-use log::info;
-use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::Duration;
+
+use log::{info, warn};
+use tokio::task::JoinHandle;
 
 use staratlas_nft::staratlasnft::StarAtlasNft;
-use staratlas_symbols::symbol_store::{Asset, Currency, Exchange, SymbolStore};
+use staratlas_symbols::symbol_store::{Asset, AssetMetadata, Currency, Exchange, SymbolStore, SymbolStoreDiff};
 use staratlas_symbols::symbol_store_kv::SymbolKV;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BuilderSymbolStore {}
+use crate::currency_source::{cached_rpc_currency_source, pricescale};
+use crate::symbol_source::{cached_galaxy_source, HttpSymbolSource, SymbolSource, SymbolSourceError};
+
+/// Store shared between handlers and the refresh task, readers clone the inner `Arc` as snapshot.
+///
+/// The inner store is only swapped as a whole, so a poisoned lock still holds a complete store and is recovered.
+pub type SharedSymbolStore = Arc<RwLock<Arc<SymbolStore>>>;
+
+pub fn shared_symbol_store(store: SymbolStore) -> SharedSymbolStore {
+    Arc::new(RwLock::new(Arc::new(store)))
+}
+
+#[derive(Clone)]
+pub struct BuilderSymbolStore {
+    source: Arc<dyn SymbolSource>,
+    /// Assets added at runtime, e.g. resolved from token metadata, carried over into every refreshed store.
+    overlay: Arc<Mutex<Vec<Asset>>>,
+}
 
 impl BuilderSymbolStore {
+    /// Loads the store from the galaxy api, with the currencies registered on mainnet.
     pub fn new() -> BuilderSymbolStore {
-        BuilderSymbolStore {}
+        BuilderSymbolStore::with_source(HttpSymbolSource::default())
+    }
+
+    /// Loads the store from the galaxy api, falling back to the last good fetch persisted in `cache_path`.
+    /// The registered currencies are read from `rpc_url`, with `currency_snapshot` as offline copy.
+    pub fn cached(cache_path: &str, rpc_url: &str, currency_snapshot: &str) -> BuilderSymbolStore {
+        BuilderSymbolStore::with_source(cached_galaxy_source(cache_path, cached_rpc_currency_source(rpc_url, currency_snapshot)))
     }
+
+    pub fn with_source<S: SymbolSource + 'static>(source: S) -> BuilderSymbolStore {
+        BuilderSymbolStore { source: Arc::new(source), overlay: Arc::new(Mutex::new(vec![])) }
+    }
+
     pub async fn init(&self) -> SymbolStore {
-        let data = reqwest::get("https://galaxy.staratlas.com/nfts")
-            .await
-            .unwrap()
-            .json::<Vec<StarAtlasNft>>()
-            .await
-            .unwrap();
-        self.create_currencies();
+        self.try_init().await.unwrap()
+    }
+
+    /// Same as `init`, but returns the error instead of panicking when no source can be loaded.
+    pub async fn try_init(&self) -> Result<SymbolStore, SymbolSourceError> {
+        let store = self.source.load().await?;
 
         info!("SA Store initalized!");
-        self.map_data(data)
+        Ok(store)
     }
 
-    fn create_currencies(&self) -> Vec<Currency> {
-        let mut currencies: Vec<Currency> = Vec::new();
-        currencies.push(Currency {
-            name: "USDC".to_string(),
-            mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
-        });
-        currencies.push(Currency {
-            name: "ATLAS".to_string(),
-            mint: "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx".to_string(),
-        });
-        return currencies;
+    /// Adds an asset the source does not provide to `shared`.
+    ///
+    /// The asset survives refreshes until the source provides the pair itself.
+    pub fn add_asset(&self, shared: &SharedSymbolStore, asset: Asset) {
+        let mut store = shared.write().unwrap_or_else(PoisonError::into_inner);
+        let mut updated = (**store).clone();
+        updated.add_asset(asset.clone());
+        *store = Arc::new(updated);
+        self.overlay.lock().unwrap_or_else(PoisonError::into_inner).push(asset);
     }
 
-    fn map_data(&self, data: Vec<StarAtlasNft>) -> SymbolStore {
-        let mut symbol_store: SymbolStore = SymbolStore {
-            assets: vec![],
-            currencies: vec![],
-            exchange: Exchange {
-                symbol: "GM".to_string(),
-                name: "GalacticMarket".to_string(),
-                description: "StarAtlas GalacticMarket".to_string(),
-                asset_type: vec!["nft".to_string()],
-                sesstion: "24x7".to_string(),
-                timezone: "Etc/UTC".to_string(),
-                minmovement: 0.0,
-                minmov: 1.0,
-                minmovement2: 0.0,
-                minmov2: 0.0,
-                supported_resolutions: vec![
-                    "1".to_string(),
-                    "3".to_string(),
-                    "5".to_string(),
-                    "15".to_string(),
-                    "30".to_string(),
-                    "60".to_string(),
-                    "120".to_string(),
-                    "240".to_string(),
-                    "360".to_string(),
-                    "480".to_string(),
-                    "720".to_string(),
-                    "1D".to_string(),
-                    "3D".to_string(),
-                    "1W".to_string(),
-                    "1M".to_string(),
-                ],
-                has_intraday: true,
-                has_daily: true,
-                has_weekly_and_monthly: false,
-                data_status: "streaming".to_string(),
-                supports_search: true,
-                supports_group_request: false,
-                supports_marks: false,
-                supports_timescale_marks: false,
-                supports_time: true,
-            },
-        };
-        symbol_store.currencies = self.create_currencies();
-
-        let mut item_types = Vec::new();
-        data.iter().for_each(|asset| {
-            symbol_store.currencies.iter().for_each(|currency| {
-                if !item_types
-                    .clone()
-                    .into_iter()
-                    .any(|i| i == format!("{:?}", asset.attributes.item_type))
-                {
-                    item_types.push(format!("{:?}", asset.attributes.item_type))
-                }
+    /// Fetches a new store and swaps it into `shared`, added and removed assets are logged.
+    pub async fn refresh(&self, shared: &SharedSymbolStore) -> Result<SymbolStoreDiff, SymbolSourceError> {
+        let mut updated = self.try_init().await?;
 
-                symbol_store.assets.push(Asset {
-                    asset_name: asset.symbol.clone(),
-                    pair_name: currency.name.clone(),
-                    description: format!("{} [{}]", asset.name.clone(), currency.name.clone()),
-                    asset_type: format!("{:?}", asset.attributes.item_type),
-                    symbol: format!("{}{}", asset.symbol.clone(), currency.name.clone()),
-                    mint: asset.mint.clone(),
-                    pair_mint: currency.mint.clone(),
-                    pricescale: 1000000,
-                })
-            })
-        });
-        symbol_store.exchange.asset_type = item_types;
-        return symbol_store;
+        let mut store = shared.write().unwrap_or_else(PoisonError::into_inner);
+        self.apply_overlay(&mut updated);
+        let diff = store.diff(&updated);
+        *store = Arc::new(updated);
+
+        if diff.is_empty() {
+            info!("SA Store refreshed, no changes");
+        } else {
+            info!("SA Store refreshed, added assets: {:?}, removed assets: {:?}", diff.added, diff.removed);
+        }
+        Ok(diff)
+    }
+
+    /// Adds the overlay assets to `updated`, the ones now provided by the source are dropped from the overlay.
+    fn apply_overlay(&self, updated: &mut SymbolStore) {
+        let mut overlay = self.overlay.lock().unwrap_or_else(PoisonError::into_inner);
+        overlay.retain(|asset| updated.asset_by_pair(asset.mint.as_str(), asset.pair_mint.as_str()).is_none());
+        overlay.iter().for_each(|asset| updated.add_asset(asset.clone()));
+    }
+
+    /// Refreshes `shared` every `interval`, failed refreshes keep the previous store.
+    pub fn spawn_refresh(&self, shared: SharedSymbolStore, interval: Duration) -> JoinHandle<()> {
+        let builder = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(err) = builder.refresh(&shared).await {
+                    warn!("SA Store refresh failed: {}", err);
+                }
+            }
+        })
     }
 
     pub async fn simple_json_out(&self) -> Vec<SymbolKV> {
@@ -122,3 +115,48 @@ impl BuilderSymbolStore {
         simple_data
     }
 }
+
+/// Pairs every nft with every registered currency, priced in the currency's decimals.
+pub(crate) fn map_data(data: Vec<StarAtlasNft>, currencies: Vec<Currency>) -> SymbolStore {
+    let mut symbol_store: SymbolStore = SymbolStore::new(
+        vec![],
+        currencies,
+        Exchange::galactic_market(),
+    );
+    let currencies = symbol_store.currencies.clone();
+
+    let mut item_types = Vec::new();
+    data.iter().for_each(|asset| {
+        currencies.iter().for_each(|currency| {
+            if !item_types
+                .clone()
+                .into_iter()
+                .any(|i| i == asset.attributes.item_type.to_string())
+            {
+                item_types.push(asset.attributes.item_type.to_string())
+            }
+
+            symbol_store.add_asset(Asset {
+                asset_name: asset.symbol.clone(),
+                pair_name: currency.name.clone(),
+                description: format!("{} [{}]", asset.name.clone(), currency.name.clone()),
+                asset_type: asset.attributes.item_type.to_string(),
+                symbol: format!("{}{}", asset.symbol.clone(), currency.name.clone()),
+                mint: asset.mint.clone(),
+                pair_mint: currency.mint.clone(),
+                pricescale: pricescale(currency.decimals),
+                metadata: Some(AssetMetadata {
+                    name: asset.name.clone(),
+                    image: asset.image.clone(),
+                    rarity: asset.attributes.rarity.to_string(),
+                    class: asset.attributes.class.clone(),
+                    tier: asset.attributes.tier,
+                    make: asset.attributes.make.clone(),
+                    model: asset.attributes.model.clone(),
+                }),
+            })
+        })
+    });
+    symbol_store.exchange.asset_type = item_types;
+    return symbol_store;
+}
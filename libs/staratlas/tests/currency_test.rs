@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod currency_test {
+    use std::str::FromStr;
+
+    use borsh::BorshSerialize;
+    use marketplace_interface::{REGISTERED_CURRENCY_ACCOUNT_DISCM, RegisteredCurrency, RoyaltyTier};
+    use solana_program::pubkey::Pubkey;
+
+    use staratlas::currency_source::{CurrencySource, decode_registered_currency, FileCurrencySource, metadata_address, parse_metadata_symbol, pricescale};
+    use staratlas::symbol_source::{FileSymbolSource, SymbolSource};
+
+    const ATLAS_MINT: &str = "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx";
+
+    fn registered_currency_account() -> Vec<u8> {
+        let currency = RegisteredCurrency {
+            token_mint: Pubkey::from_str(ATLAS_MINT).unwrap(),
+            sa_currency_vault: Pubkey::new_unique(),
+            royalty: 60000,
+            bump: 254,
+            royalty_tiers: vec![RoyaltyTier { stake_amount: 1000, discount: 10000 }],
+        };
+        let mut data = REGISTERED_CURRENCY_ACCOUNT_DISCM.to_vec();
+        data.extend(currency.try_to_vec().unwrap());
+        // spare room reserved for more royalty tiers
+        data.extend([0u8; 64]);
+        data
+    }
+
+    fn metadata_account(name: &str, symbol: &str) -> Vec<u8> {
+        let mut data = vec![4u8];
+        data.extend([1u8; 64]);
+        for (value, padded) in [(name, 32), (symbol, 10)] {
+            let mut bytes = value.as_bytes().to_vec();
+            bytes.resize(padded, 0);
+            data.extend((bytes.len() as u32).to_le_bytes());
+            data.extend(bytes);
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_registered_currency() {
+        let currency = decode_registered_currency(registered_currency_account().as_slice()).unwrap();
+
+        assert_eq!(currency.token_mint.to_string(), ATLAS_MINT);
+        assert_eq!(currency.royalty, 60000);
+        assert_eq!(currency.royalty_tiers.len(), 1);
+    }
+
+    #[test]
+    fn skips_other_accounts() {
+        let mut data = registered_currency_account();
+        data[0] = 0;
+
+        assert!(decode_registered_currency(data.as_slice()).is_none());
+        assert!(decode_registered_currency(&[60, 114]).is_none());
+    }
+
+    #[test]
+    fn parses_metadata_symbol() {
+        assert_eq!(parse_metadata_symbol(metadata_account("Star Atlas", "ATLAS").as_slice()), Some("ATLAS".to_string()));
+        assert_eq!(parse_metadata_symbol(metadata_account("No Symbol", "").as_slice()), None);
+        assert_eq!(parse_metadata_symbol(&[4u8; 40]), None);
+    }
+
+    #[test]
+    fn pricescale_from_decimals() {
+        assert_eq!(pricescale(0), 1);
+        assert_eq!(pricescale(6), 1000000);
+        assert_eq!(pricescale(8), 100000000);
+    }
+
+    #[tokio::test]
+    async fn file_source() {
+        let currencies = FileCurrencySource { path: "tests/currencies.sample.json".to_string() }.load().await.unwrap();
+
+        assert_eq!(currencies.len(), 2);
+        assert_eq!(currencies[1].name, "ATLAS");
+        assert_eq!(currencies[1].decimals, 8);
+    }
+
+    #[test]
+    fn metadata_address_of_invalid_mint() {
+        assert!(metadata_address(ATLAS_MINT).is_ok());
+        assert!(metadata_address("not a mint").is_err());
+    }
+
+    #[tokio::test]
+    async fn caches_without_decimals_are_a_miss() {
+        let path = std::env::temp_dir().join(format!("staratlas_currencies_{}.json", std::process::id()));
+        std::fs::write(&path, r#"[{"name": "ATLAS", "mint": "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx"}]"#).unwrap();
+        let path = path.to_str().unwrap().to_string();
+
+        let currencies = FileCurrencySource { path: path.clone() }.load().await;
+        let store = FileSymbolSource { path: path.clone() }.load().await;
+        std::fs::remove_file(path.as_str()).unwrap();
+
+        assert!(currencies.is_err());
+        assert!(store.is_err());
+    }
+}
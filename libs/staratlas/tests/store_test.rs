@@ -2,9 +2,38 @@
 mod store_test {
     use helper::filehelper::{write_file, write_file_slim};
 
-    use staratlas::symbolstore::BuilderSymbolStore;
+    use staratlas::currency_source::FileCurrencySource;
+    use staratlas::symbol_source::{CachingSymbolSource, FallbackSymbolSource, FileSymbolSource, HttpSymbolSource, SymbolSource, SymbolSourceError};
+    use staratlas::symbolstore::{BuilderSymbolStore, shared_symbol_store};
+    use staratlas_symbols::symbol_store::Asset;
+
+    const SAMPLE: &str = "tests/store.sample.json";
+    const UNREACHABLE: &str = "http://127.0.0.1:9/nfts";
+    const CURRENCIES: &str = "tests/currencies.sample.json";
+
+    fn temp_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("staratlas_{}_{}.json", name, std::process::id()));
+        path.to_str().unwrap().to_string()
+    }
+
+    fn unreachable() -> HttpSymbolSource {
+        HttpSymbolSource::new(UNREACHABLE, FileCurrencySource { path: CURRENCIES.to_string() })
+    }
+
+    fn unreachable_with_cache(cache_path: &str) -> FallbackSymbolSource {
+        FallbackSymbolSource {
+            sources: vec![
+                Box::new(CachingSymbolSource {
+                    inner: Box::new(unreachable()),
+                    path: cache_path.to_string(),
+                }),
+                Box::new(FileSymbolSource { path: cache_path.to_string() }),
+            ],
+        }
+    }
 
     #[tokio::test]
+    #[ignore = "fetches the galaxy api and rewrites the sample"]
     async fn init_store() {
         let symbolStore = BuilderSymbolStore::new();
         let store = symbolStore.init().await;
@@ -16,6 +45,7 @@ mod store_test {
     }
 
     #[tokio::test]
+    #[ignore = "fetches the galaxy api"]
     async fn create_store() {
         let symbolStore = BuilderSymbolStore::new();
         let store = symbolStore.simple_json_out().await;
@@ -25,4 +55,103 @@ mod store_test {
 
         assert_eq!(store.len() > 10, true);
     }
+
+    #[tokio::test]
+    async fn file_source() {
+        let store = BuilderSymbolStore::with_source(FileSymbolSource { path: SAMPLE.to_string() }).init().await;
+
+        assert_eq!(store.assets.len() > 10, true);
+        assert_eq!(store.currencies.len(), 2)
+    }
+
+    #[tokio::test]
+    async fn fallback_source() {
+        let source = FallbackSymbolSource {
+            sources: vec![
+                Box::new(unreachable()),
+                Box::new(FileSymbolSource { path: "tests/missing.json".to_string() }),
+                Box::new(FileSymbolSource { path: SAMPLE.to_string() }),
+            ],
+        };
+        let store = source.load().await.unwrap();
+
+        assert_eq!(store.assets.len() > 10, true);
+    }
+
+    #[tokio::test]
+    async fn fallback_source_exhausted() {
+        let source = FallbackSymbolSource {
+            sources: vec![Box::new(FileSymbolSource { path: "tests/missing.json".to_string() })],
+        };
+
+        assert!(matches!(source.load().await, Err(SymbolSourceError::Exhausted)));
+    }
+
+    #[tokio::test]
+    async fn boots_from_cache_when_offline() {
+        let cache_path = temp_path("cache");
+        let sample = FileSymbolSource { path: SAMPLE.to_string() }.load().await.unwrap();
+
+        // a good load is persisted to the cache
+        let caching = CachingSymbolSource {
+            inner: Box::new(FileSymbolSource { path: SAMPLE.to_string() }),
+            path: cache_path.clone(),
+        };
+        caching.load().await.unwrap();
+
+        let store = BuilderSymbolStore::with_source(unreachable_with_cache(cache_path.as_str())).try_init().await.unwrap();
+        std::fs::remove_file(cache_path.as_str()).unwrap();
+
+        assert_eq!(store.assets.len(), sample.assets.len());
+        assert_eq!(store.find_symbol("BrzwWsG845VttbTsacZMLKhyc2jAZU12MaPkTYrJHoqm", "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx"), Some("STARATLAS".to_string()));
+    }
+
+    fn resolved_asset(mint: &str, pair_mint: &str) -> Asset {
+        Asset {
+            asset_name: "RESOLVED".to_string(),
+            pair_name: "ATLAS".to_string(),
+            description: "RESOLVED [ATLAS]".to_string(),
+            asset_type: "Unknown".to_string(),
+            symbol: "RESOLVEDATLAS".to_string(),
+            mint: mint.to_string(),
+            pair_mint: pair_mint.to_string(),
+            pricescale: 100000000,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_keeps_added_assets() {
+        let builder = BuilderSymbolStore::with_source(FileSymbolSource { path: SAMPLE.to_string() });
+        let shared = shared_symbol_store(builder.init().await);
+        builder.add_asset(&shared, resolved_asset("resoLvedMint1111111111111111111111111111111", "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx"));
+
+        let diff = builder.refresh(&shared).await.unwrap();
+
+        assert!(diff.is_empty(), "{:?}", diff);
+        let store = shared.read().unwrap().clone();
+        assert_eq!(store.find_symbol("resoLvedMint1111111111111111111111111111111", "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx"), Some("RESOLVEDATLAS".to_string()));
+    }
+
+    #[tokio::test]
+    async fn refresh_prefers_the_source_over_added_assets() {
+        let builder = BuilderSymbolStore::with_source(FileSymbolSource { path: SAMPLE.to_string() });
+        let shared = shared_symbol_store(builder.init().await);
+        let sample_assets = shared.read().unwrap().assets.len();
+        builder.add_asset(&shared, resolved_asset("BrzwWsG845VttbTsacZMLKhyc2jAZU12MaPkTYrJHoqm", "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx"));
+
+        builder.refresh(&shared).await.unwrap();
+
+        let store = shared.read().unwrap().clone();
+        assert_eq!(store.assets.len(), sample_assets);
+        assert_eq!(store.find_symbol("BrzwWsG845VttbTsacZMLKhyc2jAZU12MaPkTYrJHoqm", "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx"), Some("STARATLAS".to_string()));
+    }
+
+    #[tokio::test]
+    async fn no_cache_when_offline() {
+        let cache_path = temp_path("empty");
+
+        let result = BuilderSymbolStore::with_source(unreachable_with_cache(cache_path.as_str())).try_init().await;
+        assert!(result.is_err());
+    }
 }
@@ -1,4 +1,77 @@
-use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use log::warn;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Declares an enum of the values the galaxy api is known to return. Values added upstream
+/// deserialize to `Unknown` and serialize back unchanged, `Display` prints the variant name.
+macro_rules! tolerant_enum {
+    ($name:ident { $($variant:ident => $value:literal),* $(,)? }) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)*
+            Unknown(String),
+        }
+
+        impl $name {
+            /// The value as sent by the galaxy api.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value.as_str(),
+                }
+            }
+
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, $name::Unknown(_))
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                match value.as_str() {
+                    $($value => $name::$variant,)*
+                    _ => $name::Unknown(value),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $($name::$variant => f.write_str(stringify!($variant)),)*
+                    $name::Unknown(value) => f.write_str(value),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer).map($name::from)
+            }
+        }
+    };
+}
+
+/// Parses the items of a galaxy `/nfts` response one by one, items that do not match the model are
+/// logged and skipped instead of failing the whole list.
+pub fn parse_nfts(items: Vec<serde_json::Value>) -> Vec<StarAtlasNft> {
+    let mut nfts = Vec::with_capacity(items.len());
+    for item in items {
+        let mint = item["mint"].as_str().unwrap_or("<no mint>").to_string();
+        match serde_json::from_value::<StarAtlasNft>(item) {
+            Ok(nft) => nfts.push(nft),
+            Err(err) => warn!("Skipping nft {}: {}", mint, err),
+        }
+    }
+    nfts
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -72,35 +145,29 @@ pub enum Edition {
     AlternateCover,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum ItemType {
-    Access,
-    Collectible,
-    Currency,
-    Memories,
-    Resource,
-    Ship,
-    Story,
-    Structure,
-}
+tolerant_enum!(ItemType {
+    Access => "access",
+    Collectible => "collectible",
+    Currency => "currency",
+    Memories => "memories",
+    Resource => "resource",
+    Ship => "ship",
+    Story => "story",
+    Structure => "structure",
+});
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Rarity {
-    Anomaly,
-    Common,
-    Epic,
-    Legendary,
-    Rare,
-    Uncommon,
-}
+tolerant_enum!(Rarity {
+    Anomaly => "anomaly",
+    Common => "common",
+    Epic => "epic",
+    Legendary => "legendary",
+    Rare => "rare",
+    Uncommon => "uncommon",
+});
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum SeriesName {
-    Core,
-}
+tolerant_enum!(SeriesName {
+    Core => "core",
+});
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collection {
@@ -108,11 +175,9 @@ pub struct Collection {
     pub family: Family,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum Family {
-    #[serde(rename = "Star Atlas")]
-    StarAtlas,
-}
+tolerant_enum!(Family {
+    StarAtlas => "Star Atlas",
+});
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -125,15 +190,11 @@ pub struct Market {
     pub serum_program_id: Option<SerumProgramId>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum QuotePair {
-    #[serde(rename = "ATLAS")]
-    Atlas,
-    #[serde(rename = "SOL")]
-    Sol,
-    #[serde(rename = "USDC")]
-    Usdc,
-}
+tolerant_enum!(QuotePair {
+    Atlas => "ATLAS",
+    Sol => "SOL",
+    Usdc => "USDC",
+});
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -201,29 +262,21 @@ pub struct ComponentSlotElement {
     pub crew: Option<Crew>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
-pub enum Crew {
-    Capital,
-    #[serde(rename = "class 8")]
-    Class8,
-    Commander,
-    Crew,
-    #[serde(rename = "Class 8")]
-    CrewClass8,
-    #[serde(rename = "XX-Small")]
-    CrewXxSmall,
-    Large,
-    Medium,
-    Small,
-    Titan,
-    #[serde(rename = "x-small")]
-    XSmall,
-    #[serde(rename = "xx-small")]
-    XxSmall,
-    #[serde(rename = "xxx-small")]
-    XxxSmall,
-}
+tolerant_enum!(Crew {
+    Capital => "capital",
+    Class8 => "class 8",
+    Commander => "commander",
+    Crew => "crew",
+    CrewClass8 => "Class 8",
+    CrewXxSmall => "XX-Small",
+    Large => "large",
+    Medium => "medium",
+    Small => "small",
+    Titan => "titan",
+    XSmall => "x-small",
+    XxSmall => "xx-small",
+    XxxSmall => "xxx-small",
+});
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InteriorSlotElement {
@@ -231,11 +284,9 @@ pub struct InteriorSlotElement {
     pub slot_type: Type,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum Type {
-    #[serde(rename = "Coming Soon")]
-    ComingSoon,
-}
+tolerant_enum!(Type {
+    ComingSoon => "Coming Soon",
+});
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
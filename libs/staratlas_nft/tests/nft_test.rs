@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod nft_test {
+    use serde_json::Value;
+
+    use staratlas_nft::staratlasnft::{Crew, Family, ItemType, parse_nfts, QuotePair, Rarity, StarAtlasNft, Type};
+
+    const SAMPLE: &str = "tests/nfts.sample.json";
+
+    fn sample() -> Vec<Value> {
+        serde_json::from_str(std::fs::read_to_string(SAMPLE).unwrap().as_str()).unwrap()
+    }
+
+    #[test]
+    fn skips_malformed_nfts() {
+        let nfts = parse_nfts(sample());
+
+        let symbols: Vec<&str> = nfts.iter().map(|nft| nft.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["PX4", "FBLAIR"]);
+    }
+
+    #[test]
+    fn known_values() {
+        let nft = &parse_nfts(sample())[0];
+
+        assert_eq!(nft.attributes.item_type, ItemType::Ship);
+        assert_eq!(nft.attributes.rarity, Rarity::Common);
+        assert_eq!(nft.markets[1].quote_pair, QuotePair::Usdc);
+        assert_eq!(nft.collection.as_ref().unwrap().family, Family::StarAtlas);
+        assert_eq!(nft.attributes.item_type.to_string(), "Ship");
+        assert_eq!(nft.attributes.rarity.as_str(), "common");
+    }
+
+    #[test]
+    fn unknown_values() {
+        let nft = &parse_nfts(sample())[1];
+        let slots = nft.slots.as_ref().unwrap();
+
+        assert_eq!(nft.attributes.item_type, ItemType::Unknown("vehicle".to_string()));
+        assert_eq!(nft.attributes.rarity, Rarity::Unknown("mythic".to_string()));
+        assert!(nft.attributes.series_name.as_ref().unwrap().is_unknown());
+        assert_eq!(nft.markets[0].quote_pair, QuotePair::Unknown("USDT".to_string()));
+        assert_eq!(slots.crew_slots.as_ref().unwrap()[0].size, Some(Crew::Unknown("xxxx-small".to_string())));
+        assert_eq!(slots.interior_slots.as_ref().unwrap()[0].slot_type, Type::Unknown("Hangar".to_string()));
+        assert_eq!(nft.attributes.rarity.to_string(), "mythic");
+    }
+
+    #[test]
+    fn round_trip() {
+        let items = sample();
+        let nfts = parse_nfts(items.clone());
+
+        for (item, nft) in items.iter().zip(nfts.iter()) {
+            let json = serde_json::to_value(nft).unwrap();
+            assert_eq!(json["attributes"]["itemType"], item["attributes"]["itemType"]);
+            assert_eq!(json["attributes"]["rarity"], item["attributes"]["rarity"]);
+            assert_eq!(json["markets"], item["markets"]);
+            assert_eq!(json["slots"], item["slots"]);
+            assert_eq!(json["collection"], item["collection"]);
+
+            let again: StarAtlasNft = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&again).unwrap(), json);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Per-trade cost of resolving the symbol of a pair, run with `cargo bench`.
+//!
+//! Compares the indexed `SymbolStore::find_symbol` with the clone-and-scan lookup the worker used to
+//! run for every trade.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use staratlas_symbols::symbol_store::{Asset, Currency, Exchange, SymbolStore};
+
+const ASSETS: usize = 500;
+const TRADES: usize = 100_000;
+
+fn store() -> SymbolStore {
+    let currencies = vec![
+        Currency { name: "USDC".to_string(), mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(), decimals: 6 },
+        Currency { name: "ATLAS".to_string(), mint: "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx".to_string(), decimals: 8 },
+    ];
+    let mut assets = vec![];
+    for index in 0..ASSETS {
+        for currency in currencies.iter() {
+            assets.push(Asset {
+                asset_name: format!("ASSET{}", index),
+                pair_name: currency.name.clone(),
+                description: format!("Asset {} [{}]", index, currency.name),
+                asset_type: ["ship", "resource", "collectible"][index % 3].to_string(),
+                symbol: format!("ASSET{}{}", index, currency.name),
+                mint: format!("mint{:040}", index),
+                pair_mint: currency.mint.clone(),
+                pricescale: 1000000,
+                metadata: None,
+            });
+        }
+    }
+    let exchange = Exchange::galactic_market();
+    SymbolStore::new(assets, currencies, exchange)
+}
+
+fn trades(store: &SymbolStore) -> Vec<(String, String)> {
+    (0..TRADES)
+        .map(|trade| {
+            let asset = &store.assets[(trade * 7919) % store.assets.len()];
+            (asset.mint.clone(), asset.pair_mint.clone())
+        })
+        .collect()
+}
+
+fn bench<F: FnMut(&str, &str) -> Option<String>>(name: &str, trades: &[(String, String)], mut lookup: F) {
+    let start = Instant::now();
+    for (mint, pair_mint) in trades {
+        black_box(lookup(mint.as_str(), pair_mint.as_str()));
+    }
+    let elapsed = start.elapsed();
+    println!("{:<16} {:>10.0} ns/trade ({} trades in {:?})", name, elapsed.as_nanos() as f64 / trades.len() as f64, trades.len(), elapsed);
+}
+
+fn main() {
+    let store = store();
+    let trades = trades(&store);
+    println!("{} assets", store.assets.len());
+
+    bench("clone_and_scan", &trades[..TRADES / 100], |mint, pair_mint| {
+        store.assets
+            .clone()
+            .into_iter()
+            .find(|asset| asset.mint == mint && asset.pair_mint == pair_mint)
+            .map(|asset| asset.symbol)
+    });
+    bench("scan", &trades, |mint, pair_mint| {
+        store.assets
+            .iter()
+            .find(|asset| asset.mint == mint && asset.pair_mint == pair_mint)
+            .map(|asset| asset.symbol.clone())
+    });
+    bench("indexed", &trades, |mint, pair_mint| store.find_symbol(mint, pair_mint));
+}
@@ -1,10 +1,226 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+/// Prefix of the placeholder symbol stored for trades whose pair could not be resolved.
+pub const UNKNOWN_SYMBOL_PREFIX: &str = "UNKNOWN:";
+
+pub fn unknown_symbol(asset_mint: &str) -> String {
+    format!("{}{}", UNKNOWN_SYMBOL_PREFIX, asset_mint)
+}
+
+pub fn is_unknown_symbol(symbol: &str) -> bool {
+    symbol.starts_with(UNKNOWN_SYMBOL_PREFIX)
+}
+
+/// Assets and currencies of the exchange, indexed for lookups.
+///
+/// Build it with `SymbolStore::new` and add assets with `add_asset`, so the indexes stay in sync
+/// with `assets`.
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "SymbolStoreData")]
 pub struct SymbolStore {
     pub assets: Vec<Asset>,
     pub currencies: Vec<Currency>,
     pub exchange: Exchange,
+    #[serde(skip)]
+    index: SymbolIndex,
+}
+
+/// Serialized form of `SymbolStore`, the indexes are rebuilt when it is read.
+#[derive(Deserialize)]
+struct SymbolStoreData {
+    assets: Vec<Asset>,
+    currencies: Vec<Currency>,
+    exchange: Exchange,
+}
+
+impl From<SymbolStoreData> for SymbolStore {
+    fn from(data: SymbolStoreData) -> Self {
+        SymbolStore::new(data.assets, data.currencies, data.exchange)
+    }
+}
+
+/// Positions in `SymbolStore::assets`.
+#[derive(Debug, Clone, Default)]
+struct SymbolIndex {
+    by_symbol: HashMap<String, usize>,
+    by_mint: HashMap<String, HashMap<String, usize>>,
+    by_asset_type: HashMap<String, Vec<usize>>,
+    by_currency: HashMap<String, Vec<usize>>,
+}
+
+impl SymbolIndex {
+    fn insert(&mut self, position: usize, asset: &Asset) {
+        self.by_symbol.insert(asset.symbol.clone(), position);
+        self.by_mint.entry(asset.mint.clone()).or_default().insert(asset.pair_mint.clone(), position);
+        self.by_asset_type.entry(asset.asset_type.clone()).or_default().push(position);
+        self.by_currency.entry(asset.pair_mint.clone()).or_default().push(position);
+    }
+}
+
+impl SymbolStore {
+    pub fn new(assets: Vec<Asset>, currencies: Vec<Currency>, exchange: Exchange) -> SymbolStore {
+        let mut index = SymbolIndex::default();
+        assets.iter().enumerate().for_each(|(position, asset)| index.insert(position, asset));
+
+        SymbolStore { assets, currencies, exchange, index }
+    }
+
+    pub fn add_asset(&mut self, asset: Asset) {
+        self.index.insert(self.assets.len(), &asset);
+        self.assets.push(asset);
+    }
+
+    pub fn asset_by_symbol(&self, symbol: &str) -> Option<&Asset> {
+        self.index.by_symbol.get(symbol).map(|position| &self.assets[*position])
+    }
+
+    pub fn asset_by_pair(&self, asset_mint: &str, currency_mint: &str) -> Option<&Asset> {
+        self.index.by_mint
+            .get(asset_mint)
+            .and_then(|pairs| pairs.get(currency_mint))
+            .map(|position| &self.assets[*position])
+    }
+
+    pub fn assets_by_type(&self, asset_type: &str) -> Vec<&Asset> {
+        self.positions(self.index.by_asset_type.get(asset_type))
+    }
+
+    /// Assets traded against the currency with mint `currency_mint`.
+    pub fn assets_by_currency(&self, currency_mint: &str) -> Vec<&Asset> {
+        self.positions(self.index.by_currency.get(currency_mint))
+    }
+
+    fn positions(&self, positions: Option<&Vec<usize>>) -> Vec<&Asset> {
+        positions
+            .map(|positions| positions.iter().map(|position| &self.assets[*position]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn find_symbol(&self, asset_mint: &str, currency_mint: &str) -> Option<String> {
+        self.asset_by_pair(asset_mint, currency_mint).map(|asset| asset.symbol.clone())
+    }
+
+    pub fn find_currency(&self, currency_mint: &str) -> Option<&Currency> {
+        self.currencies.iter().find(|currency| currency.mint == currency_mint)
+    }
+
+    /// Assets matching `search`, best matches first. Matches of the same quality are ranked by their
+    /// recent traded `volumes`, keyed by symbol.
+    pub fn search(&self, search: &SymbolSearch, volumes: &HashMap<String, f64>) -> Vec<&Asset> {
+        let query = search.query.trim().to_lowercase();
+        let mut matches: Vec<(&Asset, u32, f64)> = self.assets
+            .iter()
+            .filter(|asset| search.matches_filters(asset))
+            .filter_map(|asset| {
+                let score = match_score(query.as_str(), asset)?;
+                let volume = volumes.get(&asset.symbol).copied().unwrap_or_default();
+                Some((asset, score, volume))
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then(b.2.total_cmp(&a.2))
+                .then(a.0.symbol.cmp(&b.0.symbol))
+        });
+        matches.into_iter().map(|(asset, _, _)| asset).collect()
+    }
+
+    /// Symbols of the assets only present in `updated` (added) or only present in `self` (removed).
+    pub fn diff(&self, updated: &SymbolStore) -> SymbolStoreDiff {
+        let added = updated.assets
+            .iter()
+            .filter(|asset| self.asset_by_symbol(asset.symbol.as_str()).is_none())
+            .map(|asset| asset.symbol.clone())
+            .collect();
+        let removed = self.assets
+            .iter()
+            .filter(|asset| updated.asset_by_symbol(asset.symbol.as_str()).is_none())
+            .map(|asset| asset.symbol.clone())
+            .collect();
+
+        SymbolStoreDiff { added, removed }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SymbolStoreDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl SymbolStoreDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Query and filters of `SymbolStore::search`, all comparisons ignore case.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolSearch {
+    /// Matched against symbol, name and description, an empty query matches every asset.
+    pub query: String,
+    pub asset_type: Option<String>,
+    pub rarity: Option<String>,
+    pub class: Option<String>,
+}
+
+impl SymbolSearch {
+    fn matches_filters(&self, asset: &Asset) -> bool {
+        let metadata = asset.metadata.as_ref();
+        matches_filter(self.asset_type.as_deref(), Some(asset.asset_type.as_str()))
+            && matches_filter(self.rarity.as_deref(), metadata.map(|metadata| metadata.rarity.as_str()))
+            && matches_filter(self.class.as_deref(), metadata.map(|metadata| metadata.class.as_str()))
+    }
+}
+
+fn matches_filter(filter: Option<&str>, value: Option<&str>) -> bool {
+    match filter.filter(|filter| !filter.is_empty()) {
+        Some(filter) => value.is_some_and(|value| value.eq_ignore_ascii_case(filter)),
+        None => true,
+    }
+}
+
+/// Quality of the best match of the lowercase `query` in the fields of `asset`, `None` if nothing matches.
+///
+/// Symbol and name also match fuzzy, when the query characters appear in order (`ammo` in `AMMOATLAS`,
+/// `pxt` in `PX4TATLAS`), the description only by substring.
+fn match_score(query: &str, asset: &Asset) -> Option<u32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let name = asset.metadata.as_ref().map(|metadata| metadata.name.as_str());
+    [
+        text_score(query, asset.symbol.as_str(), true).map(|score| score + 3),
+        text_score(query, asset.asset_name.as_str(), true).map(|score| score + 2),
+        name.and_then(|name| text_score(query, name, true)).map(|score| score + 2),
+        text_score(query, asset.description.as_str(), false),
+    ].into_iter().flatten().max()
+}
+
+fn text_score(query: &str, text: &str, fuzzy: bool) -> Option<u32> {
+    let text = text.to_lowercase();
+    if text == query {
+        return Some(40);
+    }
+    if text.starts_with(query) {
+        return Some(30);
+    }
+    if text.contains(query) {
+        return Some(20);
+    }
+    if fuzzy && is_subsequence(query, text.as_str()) {
+        return Some(10);
+    }
+    None
+}
+
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut text = text.chars();
+    query.chars().all(|char| text.any(|candidate| candidate == char))
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -17,12 +233,28 @@ pub struct Asset {
     pub mint: String,
     pub pair_mint: String,
     pub pricescale: i64,
+    /// Galaxy metadata of the nft, missing for assets resolved from token metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AssetMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AssetMetadata {
+    pub name: String,
+    pub image: String,
+    pub rarity: String,
+    pub class: String,
+    pub tier: Option<i64>,
+    pub make: Option<String>,
+    pub model: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Currency {
     pub name: String,
     pub mint: String,
+    /// Required, caches written before the decimals were stored fail to load and are fetched again.
+    pub decimals: u32,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -48,3 +280,34 @@ pub struct Exchange {
     pub supports_timescale_marks: bool,
     pub supports_time: bool,
 }
+
+impl Exchange {
+    /// The StarAtlas GalacticMarket, `asset_type` lists the item types of the loaded assets.
+    pub fn galactic_market() -> Exchange {
+        Exchange {
+            symbol: "GM".to_string(),
+            name: "GalacticMarket".to_string(),
+            description: "StarAtlas GalacticMarket".to_string(),
+            asset_type: vec!["nft".to_string()],
+            sesstion: "24x7".to_string(),
+            timezone: "Etc/UTC".to_string(),
+            minmovement: 0.0,
+            minmov: 1.0,
+            minmovement2: 0.0,
+            minmov2: 0.0,
+            supported_resolutions: ["1", "3", "5", "15", "30", "60", "120", "240", "360", "480", "720", "1D", "3D", "1W", "1M"]
+                .iter()
+                .map(|resolution| resolution.to_string())
+                .collect(),
+            has_intraday: true,
+            has_daily: true,
+            has_weekly_and_monthly: false,
+            data_status: "streaming".to_string(),
+            supports_search: true,
+            supports_group_request: false,
+            supports_marks: false,
+            supports_timescale_marks: false,
+            supports_time: true,
+        }
+    }
+}
@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod symbol_store_test {
+    use std::collections::HashMap;
+
+    use staratlas_symbols::symbol_store::{Asset, AssetMetadata, Currency, Exchange, is_unknown_symbol, SymbolSearch, SymbolStore, SymbolStoreDiff, unknown_symbol};
+
+    fn store() -> SymbolStore {
+        SymbolStore::new(
+            vec![Asset {
+                asset_name: "AMMO".to_string(),
+                pair_name: "ATLAS".to_string(),
+                description: "Ammunition [ATLAS]".to_string(),
+                asset_type: "Resource".to_string(),
+                symbol: "AMMOATLAS".to_string(),
+                mint: "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK".to_string(),
+                pair_mint: "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx".to_string(),
+                pricescale: 1000000,
+                metadata: None,
+            }],
+            vec![Currency {
+                name: "ATLAS".to_string(),
+                mint: "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx".to_string(),
+                decimals: 8,
+            }],
+            Exchange::galactic_market(),
+        )
+    }
+
+    #[test]
+    fn find_symbol() {
+        let store = store();
+
+        assert_eq!(store.find_symbol("ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK", "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx"), Some("AMMOATLAS".to_string()));
+        assert_eq!(store.find_symbol("ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), None);
+        assert_eq!(store.find_currency("ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx").unwrap().name, "ATLAS");
+    }
+
+    #[test]
+    fn indexed_lookups() {
+        let mut store = store();
+        store.add_asset(Asset {
+            asset_name: "AMMO".to_string(),
+            pair_name: "USDC".to_string(),
+            description: "Ammunition [USDC]".to_string(),
+            asset_type: "Resource".to_string(),
+            symbol: "AMMOUSDC".to_string(),
+            mint: "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK".to_string(),
+            pair_mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            pricescale: 1000000,
+            metadata: None,
+        });
+
+        assert_eq!(store.asset_by_symbol("AMMOUSDC").unwrap().pair_name, "USDC");
+        assert!(store.asset_by_symbol("FOODUSDC").is_none());
+        assert_eq!(store.asset_by_pair("ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap().symbol, "AMMOUSDC");
+        assert_eq!(store.assets_by_type("Resource").len(), 2);
+        assert!(store.assets_by_type("Ship").is_empty());
+        assert_eq!(store.assets_by_currency("ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx")[0].symbol, "AMMOATLAS");
+    }
+
+    #[test]
+    fn indexes_survive_json() {
+        let json = serde_json::to_string(&store()).unwrap();
+        let store: SymbolStore = serde_json::from_str(json.as_str()).unwrap();
+
+        assert!(!json.contains("index"));
+        assert_eq!(store.asset_by_symbol("AMMOATLAS").unwrap().mint, "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK");
+    }
+
+    #[test]
+    fn unknown_symbols() {
+        let symbol = unknown_symbol("DB8CSxoakPRtXhHcc2cA3iETWfGaYY6zE2T8huJTE2Nw");
+
+        assert_eq!(symbol, "UNKNOWN:DB8CSxoakPRtXhHcc2cA3iETWfGaYY6zE2T8huJTE2Nw");
+        assert!(is_unknown_symbol(symbol.as_str()));
+        assert!(!is_unknown_symbol("AMMOATLAS"));
+    }
+
+    #[test]
+    fn diff() {
+        let old = store();
+        let mut asset = old.assets[0].clone();
+        asset.symbol = "AMMOUSDC".to_string();
+        let updated = SymbolStore::new(vec![asset], old.currencies.clone(), old.exchange.clone());
+
+        assert!(old.diff(&store()).is_empty());
+        assert_eq!(old.diff(&updated), SymbolStoreDiff {
+            added: vec!["AMMOUSDC".to_string()],
+            removed: vec!["AMMOATLAS".to_string()],
+        });
+    }
+
+    fn ship(symbol: &str, name: &str, rarity: &str, class: &str) -> Asset {
+        Asset {
+            asset_name: symbol.to_string(),
+            pair_name: "ATLAS".to_string(),
+            description: format!("{} [ATLAS]", name),
+            asset_type: "Ship".to_string(),
+            symbol: format!("{}ATLAS", symbol),
+            mint: format!("{}mint", symbol),
+            pair_mint: "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx".to_string(),
+            pricescale: 100000000,
+            metadata: Some(AssetMetadata {
+                name: name.to_string(),
+                image: format!("https://storage.googleapis.com/nft-assets/items/{}.jpg", symbol),
+                rarity: rarity.to_string(),
+                class: class.to_string(),
+                tier: None,
+                make: None,
+                model: None,
+            }),
+        }
+    }
+
+    fn search_store() -> SymbolStore {
+        let mut store = store();
+        store.add_asset(ship("PX4", "Pearce X4", "Common", "XX-Small"));
+        store.add_asset(ship("PX5", "Pearce X5", "Uncommon", "X-Small"));
+        store.add_asset(ship("OPALJ", "Opal Jet", "Common", "XX-Small"));
+        store
+    }
+
+    fn symbols(assets: Vec<&Asset>) -> Vec<String> {
+        assets.into_iter().map(|asset| asset.symbol.clone()).collect()
+    }
+
+    #[test]
+    fn search_ignores_case() {
+        let store = search_store();
+        let search = SymbolSearch { query: "pearce".to_string(), ..Default::default() };
+
+        assert_eq!(symbols(store.search(&search, &HashMap::new())), vec!["PX4ATLAS", "PX5ATLAS"]);
+    }
+
+    #[test]
+    fn search_ranks_exact_before_fuzzy() {
+        let store = search_store();
+        let search = SymbolSearch { query: "px5atlas".to_string(), ..Default::default() };
+        assert_eq!(symbols(store.search(&search, &HashMap::new())), vec!["PX5ATLAS"]);
+
+        // `oat` is part of AMMOATLAS, but only a fuzzy match of OPALJATLAS
+        let search = SymbolSearch { query: "oat".to_string(), ..Default::default() };
+        assert_eq!(symbols(store.search(&search, &HashMap::new())), vec!["AMMOATLAS", "OPALJATLAS"]);
+    }
+
+    #[test]
+    fn search_filters() {
+        let store = search_store();
+        let search = SymbolSearch {
+            rarity: Some("common".to_string()),
+            class: Some("xx-small".to_string()),
+            asset_type: Some("ship".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(symbols(store.search(&search, &HashMap::new())), vec!["OPALJATLAS", "PX4ATLAS"]);
+
+        // assets without metadata never match a metadata filter
+        let search = SymbolSearch { rarity: Some("Common".to_string()), query: "ammo".to_string(), ..Default::default() };
+        assert!(store.search(&search, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn search_ranks_by_volume() {
+        let store = search_store();
+        let search = SymbolSearch { query: "px".to_string(), ..Default::default() };
+        let volumes = HashMap::from([("PX5ATLAS".to_string(), 120.0), ("PX4ATLAS".to_string(), 12.0)]);
+
+        assert_eq!(symbols(store.search(&search, &volumes)), vec!["PX5ATLAS", "PX4ATLAS"]);
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use anyhow::{format_err, Error};
+use database_psql::coverage::is_module_cursor;
+use database_psql::model::Cursor;
+use tokio_stream::StreamExt;
+
+use crate::pb::sf::substreams::v1::Modules;
+use crate::substreams::SubstreamsEndpoint;
+use crate::substreams_stream::{BlockResponse, SubstreamsStream};
+
+/// Start of the most recent live tail of the module, a cursor `{module}_{start}_0`.
+pub fn find_live_start(cursors: &[Cursor], module_name: &str) -> Option<u64> {
+    cursors.iter()
+        .filter(|cursor| cursor.end_block == Some(0) && is_module_cursor(cursor, module_name))
+        .filter_map(|cursor| cursor.start_block)
+        .max()
+        .map(|start_block| start_block as u64)
+}
+
+/// Most recent final block, read from the first block of a stream starting relative to the head.
+pub async fn fetch_head_block(endpoint: Arc<SubstreamsEndpoint>, modules: Option<Modules>, module_name: String) -> Result<u64, Error> {
+    let mut stream = SubstreamsStream::new(endpoint, None, modules, module_name, -1, 0, true);
+    while let Some(response) = stream.next().await {
+        if let BlockResponse::New(data) = response? {
+            return data.clock.map(|clock| clock.number).ok_or_else(|| format_err!("head block without clock"));
+        }
+    }
+    Err(format_err!("stream ended before the head block"))
+}
+
+/// Block where the live tail starts and the backfill stops, the backfill covers `[start_block, handoff)`.
+///
+/// A restarted process keeps the handoff of its live cursor, so the tail resumes from that cursor without a gap.
+pub async fn resolve_handoff(cursors: &[Cursor], endpoint: Arc<SubstreamsEndpoint>, modules: Option<Modules>, module_name: String) -> Result<u64, Error> {
+    match find_live_start(cursors, module_name.as_str()) {
+        Some(handoff) => Ok(handoff),
+        None => fetch_head_block(endpoint, modules, module_name).await,
+    }
+}
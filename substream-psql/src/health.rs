@@ -0,0 +1,90 @@
+use std::convert::Infallible;
+
+use database_psql::db_cursors::get_cursors;
+use database_psql::coverage::is_module_cursor;
+use database_psql::model::Cursor;
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use serde::Serialize;
+use warp::{Filter, hyper::StatusCode, Reply};
+
+use crate::metrics::{METRICS, unix_now};
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Unavailable,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct WorkerHealth {
+    pub status: HealthStatus,
+    pub connected_streams: i64,
+    pub seconds_since_last_block: Option<i64>,
+    pub head_block: Option<i64>,
+    pub cursor_block: Option<i64>,
+    pub cursor_lag_blocks: Option<i64>,
+}
+
+/// Last block of the module's live cursor, `None` without a live tail.
+pub fn live_cursor_block(cursors: &[Cursor], module_name: &str) -> Option<i64> {
+    cursors.iter()
+        .filter(|cursor| cursor.end_block == Some(0) && is_module_cursor(cursor, module_name))
+        .filter_map(|cursor| cursor.block)
+        .max()
+}
+
+/// Unavailable without a connected stream, degraded when the live cursor is more than `max_cursor_lag` blocks
+/// behind the head slot of the rpc node.
+///
+/// The cursor only moves with trades, so a quiet market shows up as lag as well.
+pub fn worker_health(connected_streams: i64, last_block_seconds: i64, head_block: i64, cursor_block: Option<i64>, max_cursor_lag: i64, now: i64) -> WorkerHealth {
+    let head_block = (head_block > 0).then_some(head_block);
+    let cursor_lag_blocks = head_block.zip(cursor_block).map(|(head_block, cursor_block)| (head_block - cursor_block).max(0));
+
+    let status = if connected_streams <= 0 {
+        HealthStatus::Unavailable
+    } else if cursor_lag_blocks.map_or(false, |lag| lag > max_cursor_lag) {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Ok
+    };
+
+    WorkerHealth {
+        status,
+        connected_streams,
+        seconds_since_last_block: (last_block_seconds > 0).then(|| now - last_block_seconds),
+        head_block,
+        cursor_block,
+        cursor_lag_blocks,
+    }
+}
+
+pub fn handlers(connection_pool: Pool<ConnectionManager<PgConnection>>, module_name: String, max_cursor_lag: i64) -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone {
+    warp::path!("health")
+        .and(warp::get())
+        .and(warp::any().map(move || connection_pool.clone()))
+        .and(warp::any().map(move || module_name.clone()))
+        .and(warp::any().map(move || max_cursor_lag))
+        .and_then(get_health)
+}
+
+async fn get_health(connection_pool: Pool<ConnectionManager<PgConnection>>, module_name: String, max_cursor_lag: i64) -> Result<impl Reply, Infallible> {
+    let cursor_block = match connection_pool.get() {
+        Ok(mut connection) => live_cursor_block(&get_cursors(&mut connection), module_name.as_str()),
+        Err(_) => None,
+    };
+
+    let health = worker_health(
+        METRICS.connected_streams.get(),
+        METRICS.last_block_seconds.get(),
+        METRICS.head_block.get(),
+        cursor_block,
+        max_cursor_lag,
+        unix_now(),
+    );
+    let code = if health.status == HealthStatus::Unavailable { StatusCode::SERVICE_UNAVAILABLE } else { StatusCode::OK };
+    Ok(warp::reply::with_status(warp::reply::json(&health), code))
+}
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
-use anyhow::{Error, format_err};
+use anyhow::Error;
 use chrono::NaiveDateTime;
+use database_psql::coverage::{BlockInterval, coverage_window, covered_intervals, merge_intervals, missing_intervals, module_cursors};
+use database_psql::model::Cursor;
 use database_psql::model::Trade;
 use database_psql::schema::trades::{asset_mint, symbol};
 use indicatif::ProgressBar;
@@ -9,10 +11,11 @@ use json::object;
 use log::info;
 use reqwest::header;
 
-use staratlas_symbols::symbol_store::SymbolStore;
+use sa_decoder::trade::rate_from_raw;
+use staratlas_symbols::symbol_store::{SymbolStore, unknown_symbol};
 
 use crate::pb::database::{DatabaseChanges, TableChange};
-use crate::pb::pb_sa_trade::{ProcessExchange, ProcessExchanges};
+use crate::pb::pb_sa_trade::ProcessExchanges;
 use crate::pb::sf::substreams::rpc::v2::BlockScopedData;
 
 #[derive(Debug)]
@@ -76,41 +79,6 @@ pub fn extract_database_changes_from_map(data: BlockScopedData, module_name: Str
     // }
 }
 
-pub fn map_exchange_to_trade(exchange: ProcessExchange, symbol_store: Arc<SymbolStore>) -> Result<Trade, Error> {
-    let mut trade = Trade {
-        pk: exchange.pk,
-        symbol: "-none-".to_string(),
-        signature: exchange.signature,
-        block: exchange.block as i64,
-        timestamp: exchange.timestamp,
-        timestamp_ts: NaiveDateTime::from_timestamp_millis(exchange.timestamp * 1000).unwrap(),
-        order_taker: exchange.order_taker,
-        currency_mint: exchange.currency_mint,
-        asset_mint: exchange.asset_mint,
-        order_initializer: exchange.order_initializer,
-        asset_receiving_wallet: exchange.asset_receiving_wallet,
-        asset_change: exchange.asset_change.parse::<f64>().unwrap(),
-        currency_change: exchange.currency_change.parse::<f64>().unwrap(),
-        market_fee: exchange.market_fee.parse::<f64>().unwrap(),
-        price: exchange.price.parse::<f64>().unwrap(),
-        total_cost: exchange.total_cost.parse::<f64>().unwrap(),
-    };
-
-    trade.symbol = match symbol_store
-        .assets
-        .clone()
-        .into_iter()
-        .find(|asset| { asset.mint == trade.asset_mint && asset.pair_mint == trade.currency_mint })
-    {
-        None => {
-            panic!("Error no symbol fetching implemented! - fail here and restart the service while fetching missing symbols!");
-        }
-        Some(asset) => { asset.symbol }
-    };
-
-    return Ok(trade);
-}
-
 pub fn map_trade_to_struct(table_change: TableChange, symbol_store: Arc<SymbolStore>) -> Result<Trade, Error> {
     let block_time = table_change.clone().fields.into_iter().find(|t| { t.name == "timestamp" }).ok_or("timestamp").unwrap().new_value.parse::<i64>().unwrap();
     let mut trade = Trade {
@@ -125,37 +93,39 @@ pub fn map_trade_to_struct(table_change: TableChange, symbol_store: Arc<SymbolSt
         asset_mint: table_change.clone().fields.into_iter().find(|t| { t.name == "asset_mint" }).ok_or("asset_mint").unwrap().new_value,
         order_initializer: table_change.clone().fields.into_iter().find(|t| { t.name == "order_initializer" }).ok_or("order_initializer").unwrap().new_value,
         asset_receiving_wallet: table_change.clone().fields.into_iter().find(|t| { t.name == "asset_receiving_wallet" }).ok_or("asset_receiving_wallet").unwrap().new_value,
-        asset_change: table_change.clone().fields.into_iter().find(|t| { t.name == "asset_change" }).ok_or("asset_change").unwrap().new_value.parse().unwrap_or(0.0),
-        currency_change: table_change.clone().fields.into_iter().find(|t| { t.name == "currency_change" }).ok_or("currency_change").unwrap().new_value.parse().unwrap_or(0.0),
-        market_fee: table_change.clone().fields.into_iter().find(|t| { t.name == "market_fee" }).ok_or("market_fee").unwrap().new_value.parse().unwrap_or(0.0),
-        price: table_change.clone().fields.into_iter().find(|t| { t.name == "price" }).ok_or("price").unwrap().new_value.parse().unwrap_or(0.0),
-        total_cost: table_change.clone().fields.into_iter().find(|t| { t.name == "total_cost" }).ok_or("total_cost").unwrap().new_value.parse().unwrap_or(0.0),
+        asset_change: table_change.clone().fields.into_iter().find(|t| { t.name == "asset_change" }).ok_or("asset_change").unwrap().new_value.parse().unwrap_or_default(),
+        currency_change: table_change.clone().fields.into_iter().find(|t| { t.name == "currency_change" }).ok_or("currency_change").unwrap().new_value.parse().unwrap_or_default(),
+        market_fee: table_change.clone().fields.into_iter().find(|t| { t.name == "market_fee" }).ok_or("market_fee").unwrap().new_value.parse().unwrap_or_default(),
+        price: table_change.clone().fields.into_iter().find(|t| { t.name == "price" }).ok_or("price").unwrap().new_value.parse().unwrap_or_default(),
+        total_cost: table_change.clone().fields.into_iter().find(|t| { t.name == "total_cost" }).ok_or("total_cost").unwrap().new_value.parse().unwrap_or_default(),
+        fee_payer: table_change.clone().fields.into_iter().find(|t| { t.name == "fee_payer" }).map(|t| t.new_value).unwrap_or("unknown".to_string()),
+        fee_rate: rate_from_raw(table_change.clone().fields.into_iter().find(|t| { t.name == "fee_rate" }).map(|t| t.new_value.parse().unwrap_or(0)).unwrap_or(0)),
+        royalty: rate_from_raw(table_change.clone().fields.into_iter().find(|t| { t.name == "royalty" }).map(|t| t.new_value.parse().unwrap_or(0)).unwrap_or(0)),
+        royalty_discount: rate_from_raw(table_change.clone().fields.into_iter().find(|t| { t.name == "royalty_discount" }).map(|t| t.new_value.parse().unwrap_or(0)).unwrap_or(0)),
+        source: String::new(),
     };
 
-    trade.symbol = match symbol_store
-        .assets
-        .clone()
-        .into_iter()
-        .find(|asset| { asset.mint == trade.asset_mint && asset.pair_mint == trade.currency_mint })
-    {
-        None => {
-            //TODO: Fetch metadata from onchain gain
-            // let asset_symbol = request_metadata_symbol(
-            //     "https://api.mainnet-beta.solana.com".to_string(),
-            //     trade.asset_mint.clone());
-            // let currency_symbol = request_metadata_symbol(
-            //     "https://api.mainnet-beta.solana.com".to_string(),
-            //     trade.currency_mint.clone());
-            panic!("Error no symbol fetching implemented! - fail here and restart the service while fetching missing symbols");
-
-            let asset_symbol = "unknown".to_string();
-            let currency_symbol = "unknown".to_string();
-
-
-            format!("{}/{}", asset_symbol, currency_symbol)
-        }
-        Some(asset) => { asset.symbol }
-    };
+    // Pairs the store can't resolve are kept with a placeholder and fixed by `reconcile_unknown_symbols`
+    trade.symbol = symbol_store
+        .find_symbol(trade.asset_mint.as_str(), trade.currency_mint.as_str())
+        .unwrap_or_else(|| unknown_symbol(trade.asset_mint.as_str()));
 
     return Ok(trade);
 }
+
+/// Block intervals between the first cursor start and the last covered block which no cursor of `module_name`
+/// covered yet, cursors of other streams are left out.
+///
+/// `planned_ranges` are the `[start, stop]` ranges about to be streamed and are not reported, a `stop` of 0
+/// streams to the chain head.
+pub fn detect_gaps(cursors: &[Cursor], module_name: &str, planned_ranges: &[Vec<u64>]) -> Vec<BlockInterval> {
+    let cursors = module_cursors(cursors, module_name);
+    let window = match coverage_window(&cursors) {
+        None => return vec![],
+        Some(window) => window,
+    };
+
+    let mut covered = covered_intervals(&cursors);
+    covered.extend(planned_ranges.iter().map(|range| BlockInterval::new(range[0], if range[1] > 0 { range[1] } else { u64::MAX })));
+    missing_intervals(&merge_intervals(covered), window)
+}
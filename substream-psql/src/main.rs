@@ -1,11 +1,11 @@
 use std::env;
+use std::net::Ipv4Addr;
 use std::process::exit;
 use std::sync::Arc;
 
 use anyhow::{Context, Error, format_err};
 use database_psql::connection::create_psql_pool_diesel;
-use database_psql::db_cursors::{create_cursor, get_cursor, update_cursor};
-use database_psql::db_trades::create_or_update_trade_table;
+use database_psql::db_cursors::{create_cursor, get_cursor, get_cursors, update_cursor};
 use database_psql::model::Cursor;
 use diesel::prelude::*;
 use diesel::r2d2::ConnectionManager;
@@ -17,24 +17,38 @@ use structopt::StructOpt;
 use tokio::task::JoinSet;
 use tokio::time::{Duration, sleep};
 use tokio_stream::StreamExt;
+use warp::Filter;
 
-use staratlas::symbolstore::BuilderSymbolStore;
-use staratlas_symbols::symbol_store::SymbolStore;
+use metadata_gateway::MetadataGateway;
+use sa_decoder::trade::map_exchange_to_trade;
+use staratlas_symbols::symbol_store::is_unknown_symbol;
+use staratlas::symbolstore::{BuilderSymbolStore, shared_symbol_store};
 
-use crate::helper::{extract_database_changes_from_map, extract_pb_sa_trades_from_map, map_exchange_to_trade, map_trade_to_struct, request_token, TaskStates, update_task_info};
+use crate::handoff::resolve_handoff;
+use crate::helper::{detect_gaps, extract_database_changes_from_map, extract_pb_sa_trades_from_map, map_trade_to_struct, request_token, TaskStates, update_task_info};
+use crate::metrics::{METRICS, poll_head_block, ROLE_BACKFILL, ROLE_LIVE};
 use crate::pb::database::DatabaseChanges;
 use crate::pb::database::table_change::Operation;
 use crate::pb::pb_sa_trade::ProcessExchanges;
 use crate::pb::sf::substreams::rpc::v2::BlockScopedData;
 use crate::pb::sf::substreams::v1::Package;
+use crate::scheduler::{pending_intervals, RangeScheduler, Segment};
 use crate::substreams::SubstreamsEndpoint;
 use crate::substreams_stream::{BlockResponse, SubstreamsStream};
+use crate::symbols::{reconcile_unknown_symbols, SymbolResolver};
+use crate::writer::{revert_stream_undo, write_stream_trade};
 
+mod handoff;
+mod health;
 mod helper;
+mod metrics;
 mod pb;
+mod scheduler;
 mod substreams_stream;
 mod substreams;
+mod symbols;
 mod tests;
+mod writer;
 
 #[derive(Debug, StructOpt)]
 struct Config {
@@ -44,135 +58,210 @@ struct Config {
     package_file: String,
     #[structopt(short = "x", long = "module-name")]
     module_name: String,
+    /// Backfill workers pulling segments from the scheduler.
     #[structopt(short = "t", long = "threads", default_value = "1")]
     threads_count: usize,
+    /// Blocks per backfill segment, each segment has its own cursor.
+    #[structopt(long = "segment-size", default_value = "10000")]
+    segment_size: u64,
+    /// Running segments with fewer blocks left than twice this size are not split for idle workers.
+    #[structopt(long = "min-split-blocks", default_value = "1000")]
+    min_split_blocks: u64,
     #[structopt(long = "start-block")]
     start_block: i64,
     #[structopt(long = "stop-block", default_value = "0")]
     stop_block: u64,
     #[structopt(long = "database-name", default_value = "rust-substreams-db-writer")]
     database_name: String,
+    #[structopt(long = "rpc-url", default_value = "https://api.mainnet-beta.solana.com")]
+    rpc_url: String,
+    #[structopt(long = "symbol-refresh-secs", default_value = "600")]
+    symbol_refresh_secs: u64,
+    #[structopt(long = "symbol-cache", default_value = "symbol_store.cache.json")]
+    symbol_cache: String,
+    #[structopt(long = "currency-cache", default_value = "currencies.cache.json")]
+    currency_cache: String,
+    #[structopt(long = "metadata-cache", default_value = "metadata.cache.json")]
+    metadata_cache: String,
+    /// With `--stop-block 0`: tail the chain head in one task while the workers backfill `[start-block, head)`.
+    #[structopt(long = "combined")]
+    combined: bool,
+    /// Port of `/metrics` and `/health`.
+    #[structopt(long = "metrics-port", default_value = "9102")]
+    metrics_port: u16,
+    /// `/health` is degraded once the live cursor is more blocks behind the head.
+    #[structopt(long = "max-cursor-lag", default_value = "1500")]
+    max_cursor_lag: i64,
+    /// Seconds between two head slot requests to the rpc node, the reference of the head lag.
+    #[structopt(long = "head-poll-secs", default_value = "10")]
+    head_poll_secs: u64,
+    /// Do not stream the gaps between the ranges recorded in `cursors`.
+    #[structopt(long = "skip-gap-backfill")]
+    skip_gap_backfill: bool,
 }
 
 
-const ITEMS: u64 = 10;
-const MAX_CONCURRENT: usize = 100;
 const STEPS: u64 = 100;
 
 #[tokio::main]
 async fn main() {
     //Start-up and init
     env_logger::init();
-    let mut config = Config::from_args();
+    let config = Config::from_args();
     info!("Config:\n {:?}", config);
 
 
     let database_pool = create_psql_pool_diesel();
-    let symbol_store = Arc::new(BuilderSymbolStore::new().init().await);
+    println!("Serving metrics and health on http://{}:{}/", Ipv4Addr::UNSPECIFIED, config.metrics_port);
+    tokio::spawn(warp::serve(metrics::handlers().or(health::handlers(database_pool.clone(), config.module_name.clone(), config.max_cursor_lag)))
+        .run((Ipv4Addr::UNSPECIFIED, config.metrics_port)));
+    tokio::spawn(poll_head_block(config.rpc_url.clone(), Duration::from_secs(config.head_poll_secs)));
+    let symbol_builder = BuilderSymbolStore::cached(config.symbol_cache.as_str(), config.rpc_url.as_str(), config.currency_cache.as_str());
+    let symbol_store = shared_symbol_store(symbol_builder.init().await);
+    symbol_builder.spawn_refresh(symbol_store.clone(), Duration::from_secs(config.symbol_refresh_secs));
+    let symbol_resolver = Arc::new(SymbolResolver::new(symbol_store, symbol_builder, MetadataGateway::cached(config.rpc_url.as_str(), config.metadata_cache.as_str())));
+    tokio::spawn(reconcile_unknown_symbols(database_pool.clone(), symbol_resolver.clone()));
     let token: Option<String> = request_token(env::var("STREAMINGFAST_KEY").expect("please set env with: STREAMINGFAST_KEY")).await;
     let endpoint = Arc::new(SubstreamsEndpoint::new(config.endpoint_url, token).await.unwrap());
 
-    let mut block_ranges = vec![];
-    if config.stop_block > 0 {
-        block_ranges = generate_block_ranges(config.start_block as u64, config.stop_block, config.threads_count);
-        info!("Block ranges are: {:?}", block_ranges)
+    let cursors = get_cursors(&mut database_pool.get().expect("Error getting connection"));
+    let mut intervals = vec![];
+    let planned_range = vec![config.start_block as u64, config.stop_block];
+    let live_segment = if config.stop_block > 0 {
+        intervals = pending_intervals(&cursors, config.module_name.as_str(), config.start_block as u64, config.stop_block);
+        info!("Pending block intervals are: {:?}", intervals);
+        None
+    } else if config.combined {
+        let modules = read_package(config.package_file.clone()).expect("Error reading package file!").modules;
+        let handoff = resolve_handoff(&cursors, endpoint.clone(), modules, config.module_name.clone()).await.expect("Error resolving the handoff block");
+        intervals = pending_intervals(&cursors, config.module_name.as_str(), config.start_block as u64, handoff);
+        info!("Live tail starts at block {}, backfilling {:?}", handoff, intervals);
+        Some(Arc::new(Segment::live(handoff)))
     } else {
-        warn!("Forcing single thread mode! - since we just sync the most recent blocks...!");
-        block_ranges.push(vec![config.start_block as u64, config.stop_block]);
-        config.threads_count = 1;
+        warn!("Streaming the most recent blocks in a single task, the workers only backfill gaps...!");
+        Some(Arc::new(Segment::live(config.start_block as u64)))
+    };
+
+    if !config.skip_gap_backfill {
+        let gaps = detect_gaps(&cursors, config.module_name.as_str(), &[planned_range]);
+        info!("Backfilling {} gaps: {:?}", gaps.len(), gaps);
+        intervals.extend(gaps);
     }
+    let scheduler = Arc::new(RangeScheduler::new(intervals, config.segment_size, config.min_split_blocks));
 
     //Config progress bars
     println!(
-        "\n Fetching substream: {} using {} threads limit is {}.\n",
-        config.module_name, config.threads_count, MAX_CONCURRENT,
+        "\n Fetching substream: {} using {} workers for {} segments.\n",
+        config.module_name, config.threads_count, scheduler.queued(),
     );
     let pb_style = ProgressStyle::with_template(
-        "[{elapsed_precise}] {bar:50.cyan/blue} {pos:>7}/{len:7} {msg} ",
+        "[{elapsed_precise}] {prefix:8} {bar:50.cyan/blue} {pos:>7}/{len:7} {msg} ",
     )
         .unwrap()
         .progress_chars("=>-");
     let multi_pg = MultiProgress::new();
-    let pb_main = multi_pg.add(ProgressBar::new(ITEMS));
+    let pb_main = multi_pg.add(ProgressBar::new(scheduler.queued() as u64));
     pb_main.set_style(pb_style.clone());
-    pb_main.set_message("total  ");
+    pb_main.set_prefix("backfill");
+    pb_main.set_message("segments");
     pb_main.tick();
 
     // setup the JoinSet to manage the join handles for our futures
     let mut set = JoinSet::new();
-    let mut last_item = false;
-
-
-    for (index, range) in block_ranges.iter().enumerate() {
-        if index == block_ranges.len() - 1 {
-            last_item = true;
-        }
-
-        let pb_task;
-
-        if range[1] > 0 {
-            pb_task = multi_pg.insert_before(&pb_main, ProgressBar::new(range[1] - range[0]));
-        } else {
-            pb_task = multi_pg.insert_before(&pb_main, ProgressBar::new(range[0]))
-        }
 
+    if let Some(segment) = live_segment {
+        let pb_task = multi_pg.insert_before(&pb_main, ProgressBar::new(segment.start));
         pb_task.set_style(pb_style.clone());
-
-        set.spawn(run_substream(index,
-                                range.clone(),
+        pb_task.set_prefix("live");
+        set.spawn(run_substream(0,
+                                segment,
                                 config.package_file.clone(),
                                 config.module_name.clone(),
                                 database_pool.clone(),
-                                symbol_store.clone(),
+                                symbol_resolver.clone(),
                                 endpoint.clone(),
                                 pb_task));
+    }
 
-        // when limit is reached, wait until a running task finishes
-        // await the future (join_next().await) and get the execution result
-        // here result would be a download id(u64), as you can see in signature of do_stuff
-        while set.len() >= MAX_CONCURRENT || last_item {
-            match set.join_next().await {
-                Some(_res) => {
-                    // let foo = res.unwrap()
-                    /* do something with foo */
-                }
-                None => {
-                    break;
-                }
-            };
-            pb_main.inc(1);
-        }
+    for worker_index in 0..config.threads_count {
+        let pb_task = multi_pg.insert_before(&pb_main, ProgressBar::new(0));
+        pb_task.set_style(pb_style.clone());
+        pb_task.set_prefix("backfill");
+        set.spawn(run_worker(worker_index + 1,
+                             scheduler.clone(),
+                             config.package_file.clone(),
+                             config.module_name.clone(),
+                             database_pool.clone(),
+                             symbol_resolver.clone(),
+                             endpoint.clone(),
+                             pb_task,
+                             pb_main.clone()));
     }
+
+    while set.join_next().await.is_some() {}
     pb_main.finish_with_message("All substreams finished!");
 }
 
+/// Pulls segments from the scheduler until every block is streamed.
+async fn run_worker(
+    task_index: usize,
+    scheduler: Arc<RangeScheduler>,
+    package_name: String,
+    module_name: String,
+    connection_pool: Pool<ConnectionManager<PgConnection>>,
+    symbol_resolver: Arc<SymbolResolver>,
+    endpoint: Arc<SubstreamsEndpoint>,
+    pb_task: ProgressBar,
+    pb_main: ProgressBar) -> usize {
+    while let Some(segment) = scheduler.next() {
+        pb_main.set_length(scheduler.total() as u64);
+        pb_task.reset();
+        pb_task.set_length(segment.stop - segment.start);
+        run_substream(task_index,
+                      segment.clone(),
+                      package_name.clone(),
+                      module_name.clone(),
+                      connection_pool.clone(),
+                      symbol_resolver.clone(),
+                      endpoint.clone(),
+                      pb_task.clone()).await;
+        scheduler.finish(&segment);
+        pb_main.inc(1);
+        info!("backfill: segment [{}, {}) done, {}/{} segments", segment.start, segment.end(), pb_main.position(), scheduler.total());
+    }
+    pb_task.finish_with_message(format!("Task_{} DONE, no segments left", task_index));
+    task_index
+}
+
 
 async fn run_substream(
     task_index: usize,
-    range: Vec<u64>,
+    segment: Arc<Segment>,
     package_name: String,
     module_name: String,
     connection_pool: Pool<ConnectionManager<PgConnection>>,
-    symbol_store: Arc<SymbolStore>,
+    symbol_resolver: Arc<SymbolResolver>,
     endpoint: Arc<SubstreamsEndpoint>,
     pb_task: ProgressBar) -> usize {
-    let cursor_db = get_cursor(&mut connection_pool.get().expect("Error getting connection"), format!("{}_{}_{}", module_name, range[0], range[1]));
+    let cursor_id = segment.cursor_id(module_name.as_str());
+    let role = if segment.stop > 0 { ROLE_BACKFILL } else { ROLE_LIVE };
+    let cursor_db = get_cursor(&mut connection_pool.get().expect("Error getting connection"), cursor_id.clone());
 
 
     let mut cursor: Option<String> = None;
     if cursor_db.len() > 0 {
         cursor = cursor_db[0].value.clone();
-        println!("Cursor (loaded from db) for [{:?}, {:?}]:\n{:?}", range[0], range[1], cursor);
+        println!("Cursor (loaded from db) for [{:?}, {:?}]:\n{:?}", segment.start, segment.stop, cursor);
     } else {
         let new_cursor = Cursor {
-            id: format!("{}_{}_{}", module_name, range[0], range[1]),
+            id: cursor_id.clone(),
             value: None,
             block: None,
-            start_block: Some(range[0] as i64),
-            end_block: Some(range[1] as i64),
+            start_block: Some(segment.start as i64),
+            end_block: Some(segment.stop as i64),
         };
         create_cursor(&mut connection_pool.get().expect("Error getting connection"), new_cursor);
-        println!("Cursor (created) for [{:?}, {:?}]", range[0], range[1]);
+        println!("Cursor (created) for [{:?}, {:?}]", segment.start, segment.stop);
     }
 
     sleep(Duration::from_millis(2000)).await;
@@ -186,8 +275,10 @@ async fn run_substream(
         cursor.clone(),
         package_store.modules,
         module_name.clone().to_string(),
-        range[0] as i64,
-        range[1],
+        segment.start as i64,
+        segment.stop,
+        // the live tail streams unfinalized blocks and reverts them on undo signals
+        segment.stop > 0,
     );
     sleep(Duration::from_millis(2000)).await;
 
@@ -199,11 +290,21 @@ async fn run_substream(
         match stream.next().await {
             None => {
                 update_task_info(pb_task.clone(), task_index, TaskStates::STREAM_CONSUMED);
+                complete_cursor(&connection_pool, cursor_id.as_str(), &segment);
                 sleep(Duration::from_secs(2)).await;
                 break;
             }
             Some(Ok(BlockResponse::New(data))) => {
+                // the upper part of the segment was handed to another worker
+                if !segment.advance(data.clock.as_ref().map(|clock| clock.number).unwrap_or_default()) {
+                    update_task_info(pb_task.clone(), task_index, TaskStates::STREAM_CONSUMED);
+                    complete_cursor(&connection_pool, cursor_id.as_str(), &segment);
+                    break;
+                }
                 pb_task.inc(1);
+                if let Some(clock) = data.clock.as_ref() {
+                    METRICS.observe_block(role, task_index, clock.number, clock.timestamp.as_ref().map(|timestamp| timestamp.seconds));
+                }
                 let cursor = Some(data.cursor.clone());
                 match extract_pb_sa_trades_from_map(data.clone()) {
                     Ok(ProcessExchanges { process_exchanges }) => {
@@ -211,22 +312,26 @@ async fn run_substream(
                             println!("{:?}", exchange);
                             update_task_info(pb_task.clone(), task_index, TaskStates::INSERTING_DB);
 
-                            let mapped = map_exchange_to_trade(exchange, symbol_store.clone()).expect("Error unwrapping db data");
-                            current_block = mapped.block as u64;
-                            create_or_update_trade_table(&mut connection_pool.get().expect("Error getting connection"), mapped);
-
-                            //Update cursor
-                            let new_cursor = Cursor {
-                                id: format!("{}_{}_{}", module_name, range[0].clone(), range[1].clone()),
-                                value: cursor.clone(),
-                                block: Some(current_block as i64),
-                                start_block: Some(range[0].clone() as i64),
-                                end_block: Some(range[1].clone() as i64),
+                            let symbol_store = symbol_resolver.resolve(exchange.asset_mint.as_str(), exchange.currency_mint.as_str()).await;
+                            let signature = exchange.signature.clone();
+                            let mapped = match map_exchange_to_trade(exchange, &symbol_store) {
+                                Ok(mapped) => mapped,
+                                Err(err) => {
+                                    error!("Task_{}: unable to map exchange {}: {}", task_index, signature, err);
+                                    continue;
+                                }
                             };
-                            update_cursor(&mut connection_pool.get().expect("Error getting connection"), format!("{}_{}_{}", module_name, range[0], range[1]), new_cursor);
+                            current_block = mapped.block as u64;
+                            if is_unknown_symbol(mapped.symbol.as_str()) {
+                                METRICS.unknown_symbol_trades.inc();
+                            }
+                            let write_timer = METRICS.db_write_seconds.start_timer();
+                            write_stream_trade(&connection_pool, cursor_id.as_str(), &segment, cursor.clone(), mapped);
+                            write_timer.observe_duration();
+                            METRICS.trades_inserted.with_label_values(&[role]).inc();
 
-                            if range[1] > 0 {
-                                pb_task.set_position(current_block - range[0]);
+                            if segment.stop > 0 {
+                                pb_task.set_position(current_block - segment.start);
                             } else {
                                 pb_task.set_position(current_block);
                             }
@@ -245,7 +350,7 @@ async fn run_substream(
                 //                 Operation::Create => {
                 //                     update_task_info(pb_task.clone(), task_index, TaskStates::INSERTING_DB);
                 //
-                //                     let mapped = map_trade_to_struct(table_changed, symbol_store.clone()).expect("Error unwrapping db data");
+                //                     let mapped = map_trade_to_struct(table_changed, symbol_resolver.store()).expect("Error unwrapping db data");
                 //                     current_block = mapped.block as u64;
                 //                     create_or_update_trade_table(&mut connection_pool.get().expect("Error getting connection"), mapped);
                 //
@@ -279,7 +384,16 @@ async fn run_substream(
                 // };
             }
             Some(Ok(BlockResponse::Undo(undo_signal))) => {
-                //unimplemented!("you must implement some kind of block undo handling, or request only final blocks (tweak substreams_stream.rs)")
+                METRICS.undos.inc();
+                let last_valid_block = undo_signal.last_valid_block.as_ref().map(|block_ref| block_ref.number).unwrap_or_default();
+                let deleted = match revert_stream_undo(&connection_pool, cursor_id.as_str(), &segment, &undo_signal) {
+                    Ok(deleted) => deleted,
+                    Err(err) => {
+                        error!("Task_{}: undo to block {} failed: {}", task_index, last_valid_block, err);
+                        exit(1);
+                    }
+                };
+                warn!("Task_{}: undo to block {}, deleted {} trades", task_index, last_valid_block, deleted);
             }
             Some(Err(err)) => {
                 println!();
@@ -292,7 +406,7 @@ async fn run_substream(
 
 
     // sleep(Duration::from_millis(env::args().nth(8).unwrap_or("0".to_string()).parse::<u64>().unwrap()));
-    pb_task.finish_with_message(format!("Task_{} DONE with range {}:{}", task_index, range[0], range[1]));
+    pb_task.set_message(format!("Task_{} DONE with range {}:{}", task_index, segment.start, segment_end_block(&segment)));
     task_index
 //    Ok(())
 }
@@ -309,14 +423,27 @@ fn read_package(file: String) -> Result<Package, anyhow::Error> {
     Package::decode(content.as_ref()).context("decode command")
 }
 
-fn generate_block_ranges(start_block: u64, stop_block: u64, thread_count: usize) -> Vec<Vec<u64>> {
-    let block_count_per_thread = (stop_block - start_block) / thread_count as u64;
-    let mut ranges = vec![];
-    for thread_num in 0..thread_count {
-        ranges.push(vec![
-            start_block + block_count_per_thread * thread_num as u64,
-            start_block + block_count_per_thread + block_count_per_thread * thread_num as u64,
-        ])
+/// Cursor end of a segment, 0 while streaming the chain head.
+fn segment_end_block(segment: &Segment) -> i64 {
+    if segment.stop > 0 {
+        segment.end() as i64
+    } else {
+        0
+    }
+}
+
+/// Marks a bounded segment as covered up to its (possibly shrunk) end, even when the last trade is in an earlier block.
+fn complete_cursor(connection_pool: &Pool<ConnectionManager<PgConnection>>, cursor_id: &str, segment: &Segment) {
+    if segment.stop == 0 {
+        return;
     }
-    return ranges;
-}
\ No newline at end of file
+    let cursor_db = get_cursor(&mut connection_pool.get().expect("Error getting connection"), cursor_id.to_string());
+    let completed_cursor = Cursor {
+        id: cursor_id.to_string(),
+        value: cursor_db.first().and_then(|cursor| cursor.value.clone()),
+        block: Some(segment.end() as i64 - 1),
+        start_block: Some(segment.start as i64),
+        end_block: Some(segment.end() as i64),
+    };
+    update_cursor(&mut connection_pool.get().expect("Error getting connection"), cursor_id.to_string(), completed_cursor);
+}
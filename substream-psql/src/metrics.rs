@@ -0,0 +1,173 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::warn;
+use once_cell::sync::Lazy;
+use prometheus::{Encoder, Gauge, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder};
+use serde_json::{json, Value};
+use tokio::time::{Duration, sleep};
+use warp::Filter;
+
+use crate::pb::sf::substreams::rpc::v2::module_progress::Type;
+use crate::pb::sf::substreams::rpc::v2::ModulesProgress;
+
+pub const ROLE_LIVE: &str = "live";
+pub const ROLE_BACKFILL: &str = "backfill";
+
+pub static METRICS: Lazy<WorkerMetrics> = Lazy::new(WorkerMetrics::new);
+
+/// Prometheus collectors of the worker, served as text on `/metrics`.
+pub struct WorkerMetrics {
+    registry: Registry,
+    pub current_block: IntGaugeVec,
+    pub head_block: IntGauge,
+    pub head_lag_blocks: IntGauge,
+    pub head_lag_seconds: Gauge,
+    pub trades_inserted: IntCounterVec,
+    pub db_write_seconds: Histogram,
+    pub reconnects: IntCounter,
+    pub undos: IntCounter,
+    pub unknown_symbol_trades: IntCounter,
+    pub module_processed_block: IntGaugeVec,
+    pub module_processed_ranges: IntGaugeVec,
+    pub connected_streams: IntGauge,
+    pub last_block_seconds: IntGauge,
+    /// Last block of the live tail, the reference of `head_lag_blocks`.
+    live_block: AtomicI64,
+}
+
+/// Counts a connected stream in `connected_streams` until dropped.
+pub struct StreamConnection;
+
+impl Drop for StreamConnection {
+    fn drop(&mut self) {
+        METRICS.connected_streams.dec();
+    }
+}
+
+impl WorkerMetrics {
+    pub fn new() -> WorkerMetrics {
+        let registry = Registry::new_custom(Some("roguemarket_worker".to_string()), None).unwrap();
+        let metrics = WorkerMetrics {
+            current_block: IntGaugeVec::new(Opts::new("current_block", "Last streamed block per role and task"), &["role", "task"]).unwrap(),
+            head_block: IntGauge::new("head_block", "Most recent slot of the rpc node").unwrap(),
+            head_lag_blocks: IntGauge::new("head_lag_blocks", "Blocks between the rpc head and the live tail").unwrap(),
+            head_lag_seconds: Gauge::new("head_lag_seconds", "Seconds since the last block of the live tail was produced").unwrap(),
+            trades_inserted: IntCounterVec::new(Opts::new("trades_inserted_total", "Trades written to the database per role"), &["role"]).unwrap(),
+            db_write_seconds: Histogram::with_opts(HistogramOpts::new("db_write_seconds", "Duration of writing a trade and its cursor")).unwrap(),
+            reconnects: IntCounter::new("stream_reconnects_total", "Reconnections of the substreams endpoint").unwrap(),
+            undos: IntCounter::new("undo_signals_total", "Received block undo signals").unwrap(),
+            unknown_symbol_trades: IntCounter::new("unknown_symbol_trades_total", "Trades written with an unknown symbol").unwrap(),
+            module_processed_block: IntGaugeVec::new(Opts::new("module_processed_block", "End of the last range processed by the substreams backend per module"), &["module"]).unwrap(),
+            module_processed_ranges: IntGaugeVec::new(Opts::new("module_processed_ranges", "Ranges processed by the substreams backend per module"), &["module"]).unwrap(),
+            connected_streams: IntGauge::new("connected_streams", "Substreams connections currently open").unwrap(),
+            last_block_seconds: IntGauge::new("last_block_seconds", "Unix time the last block was received").unwrap(),
+            live_block: AtomicI64::new(0),
+            registry,
+        };
+
+        metrics.registry.register(Box::new(metrics.current_block.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.head_block.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.head_lag_blocks.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.head_lag_seconds.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.trades_inserted.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.db_write_seconds.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.reconnects.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.undos.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.unknown_symbol_trades.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.module_processed_block.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.module_processed_ranges.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.connected_streams.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.last_block_seconds.clone())).unwrap();
+        metrics
+    }
+
+    /// Text exposition of all collectors.
+    pub fn gather(&self) -> String {
+        let mut buffer = vec![];
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    /// Records a block streamed by the task `task`, for the live tail also the lag behind the chain head.
+    ///
+    /// Backfill workers stream different segments in parallel, each task keeps its own series.
+    pub fn observe_block(&self, role: &str, task: usize, block: u64, block_timestamp: Option<i64>) {
+        self.current_block.with_label_values(&[role, task.to_string().as_str()]).set(block as i64);
+        self.last_block_seconds.set(unix_now());
+        if role != ROLE_LIVE {
+            return;
+        }
+        self.live_block.store(block as i64, Ordering::Relaxed);
+        if let Some(block_timestamp) = block_timestamp {
+            self.head_lag_seconds.set((unix_now() - block_timestamp).max(0) as f64);
+        }
+        self.update_lag_blocks();
+    }
+
+    pub fn observe_head(&self, head: u64) {
+        self.head_block.set(head as i64);
+        self.update_lag_blocks();
+    }
+
+    /// Reads the processed ranges of the `ModulesProgress` messages.
+    pub fn observe_progress(&self, progress: &ModulesProgress) {
+        for module in &progress.modules {
+            if let Some(Type::ProcessedRanges(ranges)) = module.r#type.as_ref() {
+                let label = [module.name.as_str()];
+                self.module_processed_ranges.with_label_values(&label).set(ranges.processed_ranges.len() as i64);
+                if let Some(end_block) = ranges.processed_ranges.iter().map(|range| range.end_block).max() {
+                    self.module_processed_block.with_label_values(&label).set(end_block as i64);
+                }
+            }
+        }
+    }
+
+    /// Call once a stream is connected, the returned guard marks it disconnected when dropped.
+    pub fn stream_connected(&self) -> StreamConnection {
+        self.connected_streams.inc();
+        StreamConnection
+    }
+
+    fn update_lag_blocks(&self) {
+        let live_block = self.live_block.load(Ordering::Relaxed);
+        if live_block > 0 && self.head_block.get() > 0 {
+            self.head_lag_blocks.set((self.head_block.get() - live_block).max(0));
+        }
+    }
+}
+
+impl Default for WorkerMetrics {
+    fn default() -> Self {
+        WorkerMetrics::new()
+    }
+}
+
+pub fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+pub fn handlers() -> impl Filter<Extract=impl warp::Reply, Error=warp::Rejection> + Clone {
+    warp::path!("metrics")
+        .and(warp::get())
+        .map(|| warp::reply::with_header(METRICS.gather(), "Content-Type", TextEncoder::new().format_type().to_string()))
+}
+
+/// Polls the most recent slot of the rpc node, the reference of the head lag.
+pub async fn poll_head_block(rpc_url: String, interval: Duration) {
+    let client = reqwest::Client::new();
+    loop {
+        let response = client.post(rpc_url.as_str())
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": "getSlot", "params": [{ "commitment": "confirmed" }] }))
+            .send()
+            .await;
+        match response {
+            Ok(response) => match response.json::<Value>().await.ok().and_then(|body| body["result"].as_u64()) {
+                Some(slot) => METRICS.observe_head(slot),
+                None => warn!("getSlot returned no slot"),
+            },
+            Err(err) => warn!("Error requesting getSlot: {}", err),
+        }
+        sleep(interval).await;
+    }
+}
@@ -23,5 +23,4 @@ pub mod tokens;
 #[path = "sf.substreams.sink.database.v1.rs"]
 pub mod database;
 
-#[path = "sa.trade.v1.rs"]
-pub mod pb_sa_trade;
\ No newline at end of file
+pub use sa_decoder::pb::sa::trade::v1 as pb_sa_trade;
\ No newline at end of file
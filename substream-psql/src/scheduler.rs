@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use database_psql::coverage::{BlockInterval, covered_intervals, missing_intervals, module_cursors};
+use database_psql::model::Cursor;
+
+/// Block range `[start, end)` streamed by one worker, `end` shrinks when the range is split.
+#[derive(Debug)]
+pub struct Segment {
+    pub start: u64,
+    /// Original end, part of the cursor id.
+    pub stop: u64,
+    bounds: Mutex<SegmentBounds>,
+}
+
+#[derive(Debug)]
+struct SegmentBounds {
+    end: u64,
+    /// Next block which was not processed yet.
+    next: u64,
+}
+
+impl Segment {
+    pub fn new(start: u64, end: u64) -> Segment {
+        Segment {
+            start,
+            stop: end,
+            bounds: Mutex::new(SegmentBounds { end, next: start }),
+        }
+    }
+
+    /// Streams from `start` to the chain head, never split.
+    pub fn live(start: u64) -> Segment {
+        Segment {
+            start,
+            stop: 0,
+            bounds: Mutex::new(SegmentBounds { end: u64::MAX, next: start }),
+        }
+    }
+
+    pub fn cursor_id(&self, module_name: &str) -> String {
+        format!("{}_{}_{}", module_name, self.start, self.stop)
+    }
+
+    pub fn end(&self) -> u64 {
+        self.bounds.lock().unwrap().end
+    }
+
+    /// Blocks left to process.
+    pub fn remaining(&self) -> u64 {
+        let bounds = self.bounds.lock().unwrap();
+        bounds.end.saturating_sub(bounds.next)
+    }
+
+    /// Claims `block` for processing, `false` once the block is beyond the (possibly shrunk) end.
+    pub fn advance(&self, block: u64) -> bool {
+        let mut bounds = self.bounds.lock().unwrap();
+        if block >= bounds.end {
+            return false;
+        }
+        bounds.next = bounds.next.max(block + 1);
+        true
+    }
+
+    /// Hands the upper half of the remaining blocks to a new segment.
+    fn split(&self, min_split_blocks: u64) -> Option<Segment> {
+        let mut bounds = self.bounds.lock().unwrap();
+        let remaining = bounds.end.saturating_sub(bounds.next);
+        if self.stop == 0 || remaining < 2 * min_split_blocks.max(1) {
+            return None;
+        }
+        let mid = bounds.next + remaining / 2;
+        let split = Segment::new(mid, bounds.end);
+        bounds.end = mid;
+        Some(split)
+    }
+}
+
+/// Queue of segments pulled by the backfill workers.
+///
+/// A worker finding the queue empty steals the upper half of the running segment with the most blocks left,
+/// so a dense range is shared by all workers instead of running on its own till the end.
+pub struct RangeScheduler {
+    queue: Mutex<VecDeque<Arc<Segment>>>,
+    running: Mutex<Vec<Arc<Segment>>>,
+    total: AtomicUsize,
+    min_split_blocks: u64,
+}
+
+impl RangeScheduler {
+    pub fn new(intervals: Vec<BlockInterval>, segment_size: u64, min_split_blocks: u64) -> RangeScheduler {
+        let queue: VecDeque<Arc<Segment>> = split_segments(&intervals, segment_size).into_iter().map(Arc::new).collect();
+        RangeScheduler {
+            total: AtomicUsize::new(queue.len()),
+            queue: Mutex::new(queue),
+            running: Mutex::new(vec![]),
+            min_split_blocks,
+        }
+    }
+
+    pub fn queued(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+
+    /// Segments queued so far, including the split ones.
+    pub fn total(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    /// Next segment to stream, `None` once everything is queued out and no running segment can be split.
+    pub fn next(&self) -> Option<Arc<Segment>> {
+        let segment = match self.queue.lock().unwrap().pop_front() {
+            Some(segment) => Some(segment),
+            None => self.steal(),
+        }?;
+        self.running.lock().unwrap().push(segment.clone());
+        Some(segment)
+    }
+
+    pub fn finish(&self, segment: &Arc<Segment>) {
+        self.running.lock().unwrap().retain(|running| !Arc::ptr_eq(running, segment));
+    }
+
+    fn steal(&self) -> Option<Arc<Segment>> {
+        let running = self.running.lock().unwrap();
+        let mut candidates: Vec<&Arc<Segment>> = running.iter().collect();
+        candidates.sort_by_key(|segment| std::cmp::Reverse(segment.remaining()));
+        candidates.into_iter()
+            .find_map(|segment| segment.split(self.min_split_blocks))
+            .map(|segment| {
+                self.total.fetch_add(1, Ordering::Relaxed);
+                Arc::new(segment)
+            })
+    }
+}
+
+/// Splits the intervals into segments of at most `segment_size` blocks, covering every block exactly once.
+pub fn split_segments(intervals: &[BlockInterval], segment_size: u64) -> Vec<Segment> {
+    let segment_size = segment_size.max(1);
+    let mut segments = vec![];
+    for interval in intervals {
+        let mut start = interval.start;
+        while start < interval.end {
+            let end = interval.end.min(start.saturating_add(segment_size));
+            segments.push(Segment::new(start, end));
+            start = end;
+        }
+    }
+    segments
+}
+
+/// Parts of `[start_block, stop_block)` not covered by any cursor of `module_name` yet, so a restarted backfill
+/// only streams what is left of its segments.
+pub fn pending_intervals(cursors: &[Cursor], module_name: &str, start_block: u64, stop_block: u64) -> Vec<BlockInterval> {
+    missing_intervals(&covered_intervals(&module_cursors(cursors, module_name)), BlockInterval::new(start_block, stop_block))
+}
@@ -14,6 +14,7 @@ use tokio_retry::strategy::ExponentialBackoff;
 use crate::pb::sf::substreams::rpc::v2::{
     BlockScopedData, BlockUndoSignal, Request, Response, response::Message,
 };
+use crate::metrics::METRICS;
 use crate::pb::sf::substreams::v1::Modules;
 use crate::substreams::SubstreamsEndpoint;
 
@@ -34,6 +35,7 @@ impl SubstreamsStream {
         output_module_name: String,
         start_block: i64,
         end_block: u64,
+        final_blocks_only: bool,
     ) -> Self {
         SubstreamsStream {
             stream: Box::pin(stream_blocks(
@@ -43,12 +45,14 @@ impl SubstreamsStream {
                 output_module_name,
                 start_block,
                 end_block,
+                final_blocks_only,
             )),
         }
     }
 }
 
 // Create the Stream implementation that streams blocks with auto-reconnection.
+// Without `final_blocks_only` blocks are sent before they are final and forks are reverted with `BlockUndoSignal`s.
 fn stream_blocks(
     endpoint: Arc<SubstreamsEndpoint>,
     cursor: Option<String>,
@@ -56,12 +60,18 @@ fn stream_blocks(
     output_module_name: String,
     start_block_num: i64,
     stop_block_num: u64,
+    final_blocks_only: bool,
 ) -> impl Stream<Item=Result<BlockResponse, Error>> {
     let mut latest_cursor = cursor.unwrap_or_else(|| "".to_string());
     let mut backoff = ExponentialBackoff::from_millis(500).max_delay(Duration::from_secs(45));
 
     try_stream! {
+        let mut connected_before = false;
         loop {
+            if connected_before {
+                METRICS.reconnects.inc();
+            }
+            connected_before = true;
             println!("Blockstreams disconnected, connecting (endpoint {}, start block {}, cursor {})",
                 &endpoint,
                 start_block_num,
@@ -72,7 +82,7 @@ fn stream_blocks(
                 start_block_num,
                 start_cursor: latest_cursor.clone(),
                 stop_block_num,
-                final_blocks_only: false,
+                final_blocks_only,
                 modules: modules.clone(),
                 output_module: output_module_name.clone(),
                 // There is usually no good reason for you to consume the stream development mode (so switching `true`
@@ -86,6 +96,7 @@ fn stream_blocks(
             match result {
                 Ok(stream) => {
                     println!("Blockstreams connected");
+                    let _connection = METRICS.stream_connected();
 
                     let mut encountered_error = false;
                     for await response in stream{
@@ -129,6 +140,13 @@ fn stream_blocks(
                     }
                 },
                 Err(e) => {
+                    // The server rejects a bad token before sending any message, this is not retried either
+                    if let Some(status) = e.downcast_ref::<tonic::Status>() {
+                        if status.code() == tonic::Code::Unauthenticated {
+                            Err(anyhow::Error::new(status.clone()))?;
+                        }
+                    }
+
                     // We failed to connect and will try again; this is another
                     // case where we actually _want_ to back off in case we keep
                     // having connection errors.
@@ -172,7 +190,9 @@ async fn process_substreams_response(
         Some(Message::BlockUndoSignal(block_undo_signal)) => {
             BlockProcessedResult::BlockUndoSignal(block_undo_signal)
         }
-        Some(Message::Progress(_progress)) => {
+        Some(Message::Progress(progress)) => {
+            METRICS.observe_progress(&progress);
+
             // The `ModulesProgress` messages goal is to report active parallel processing happening
             // either to fill up backward (relative to your request's start block) some missing state
             // or pre-process forward blocks (again relative).
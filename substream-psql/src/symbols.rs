@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+use log::{info, warn};
+use tokio::time::{Duration, Instant, sleep};
+
+use database_psql::db_trades::{get_unknown_symbol_pairs, update_unknown_symbol};
+use metadata_gateway::MetadataGateway;
+use staratlas::currency_source::pricescale;
+use staratlas::symbolstore::{BuilderSymbolStore, SharedSymbolStore};
+use staratlas_symbols::symbol_store::{Asset, SymbolStore};
+
+/// Minimum time between two galaxy api refreshes, and between two metadata lookups of the same pair.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Resolves symbols of pairs missing in the shared `SymbolStore`.
+///
+/// Unknown pairs first trigger a refresh of the store from the galaxy api, if the pair is still
+/// missing the symbol is built from the on-chain token metadata of the mints.
+pub struct SymbolResolver {
+    store: SharedSymbolStore,
+    builder: BuilderSymbolStore,
+    metadata: MetadataGateway,
+    last_refresh: Mutex<Option<Instant>>,
+    failed_lookups: Mutex<HashMap<String, Instant>>,
+}
+
+impl SymbolResolver {
+    pub fn new(store: SharedSymbolStore, builder: BuilderSymbolStore, metadata: MetadataGateway) -> SymbolResolver {
+        SymbolResolver {
+            store,
+            builder,
+            metadata,
+            last_refresh: Mutex::new(None),
+            failed_lookups: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn store(&self) -> Arc<SymbolStore> {
+        self.store.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Returns a store snapshot, which contains the pair unless it could not be resolved.
+    pub async fn resolve(&self, asset_mint: &str, currency_mint: &str) -> Arc<SymbolStore> {
+        let store = self.store();
+        if store.find_symbol(asset_mint, currency_mint).is_some() {
+            return store;
+        }
+
+        if self.refresh().await {
+            let store = self.store();
+            if store.find_symbol(asset_mint, currency_mint).is_some() {
+                return store;
+            }
+        }
+
+        let key = format!("{}{}", asset_mint, currency_mint);
+        if let Some(failed_at) = self.failed_lookups.lock().unwrap().get(&key) {
+            if failed_at.elapsed() < RETRY_INTERVAL {
+                return self.store();
+            }
+        }
+
+        match self.request_metadata_asset(asset_mint, currency_mint).await {
+            Ok(asset) => {
+                info!("Resolved unknown pair {} {} from token metadata as {}", asset_mint, currency_mint, asset.symbol);
+                self.failed_lookups.lock().unwrap().remove(&key);
+                self.builder.add_asset(&self.store, asset);
+            }
+            Err(err) => {
+                warn!("Unable to resolve pair {} {}: {}", asset_mint, currency_mint, err);
+                self.failed_lookups.lock().unwrap().insert(key, Instant::now());
+            }
+        }
+
+        self.store()
+    }
+
+    /// Reloads the store from its source on demand, at most once per `RETRY_INTERVAL`.
+    async fn refresh(&self) -> bool {
+        {
+            let mut last_refresh = self.last_refresh.lock().unwrap();
+            if let Some(refreshed_at) = *last_refresh {
+                if refreshed_at.elapsed() < RETRY_INTERVAL {
+                    return false;
+                }
+            }
+            *last_refresh = Some(Instant::now());
+        }
+
+        match self.builder.refresh(&self.store).await {
+            Ok(_) => true,
+            Err(err) => {
+                warn!("Unable to refresh symbol store: {}", err);
+                false
+            }
+        }
+    }
+
+    async fn request_metadata_asset(&self, asset_mint: &str, currency_mint: &str) -> Result<Asset, String> {
+        let asset_symbol = self.request_metadata_symbol(asset_mint).await?;
+        // pairs in currencies that are not registered keep the previous default scale
+        let (currency_name, pricescale) = match self.store().find_currency(currency_mint) {
+            Some(currency) => (currency.name.clone(), pricescale(currency.decimals)),
+            None => (self.request_metadata_symbol(currency_mint).await?, 1000000),
+        };
+
+        Ok(Asset {
+            asset_name: asset_symbol.clone(),
+            pair_name: currency_name.clone(),
+            description: format!("{} [{}]", asset_symbol, currency_name),
+            asset_type: "Unknown".to_string(),
+            symbol: format!("{}{}", asset_symbol, currency_name),
+            mint: asset_mint.to_string(),
+            pair_mint: currency_mint.to_string(),
+            pricescale,
+            metadata: None,
+        })
+    }
+
+    /// Fetches the token metadata of `mints` in batches, so following `resolve` calls hit the cache.
+    pub async fn prefetch_metadata(&self, mints: Vec<String>) {
+        let results = self.metadata.metadata_batch(mints.as_slice()).await;
+        let failed = results.values().filter(|result| result.is_err()).count();
+        info!("Prefetched token metadata of {} mints, {} failed", results.len() - failed, failed);
+    }
+
+    async fn request_metadata_symbol(&self, mint: &str) -> Result<String, String> {
+        let symbol = self.metadata.symbol(mint).await.map_err(|err| err.to_string())?;
+
+        if symbol.is_empty() {
+            return Err("empty metadata symbol".to_string());
+        }
+        Ok(symbol)
+    }
+}
+
+/// Periodically retries the pairs of trades stored with an `UNKNOWN:<mint>` symbol.
+pub async fn reconcile_unknown_symbols(connection_pool: Pool<ConnectionManager<PgConnection>>, resolver: Arc<SymbolResolver>) {
+    loop {
+        sleep(RECONCILE_INTERVAL).await;
+
+        let pairs = match connection_pool.get().map_err(|err| err.to_string())
+            .and_then(|mut connection| get_unknown_symbol_pairs(&mut connection).map_err(|err| err.to_string())) {
+            Ok(pairs) => pairs,
+            Err(err) => {
+                warn!("Unable to load unknown symbol pairs, retrying next interval: {}", err);
+                continue;
+            }
+        };
+        if pairs.is_empty() {
+            continue;
+        }
+
+        let mut mints: Vec<String> = pairs.iter().flat_map(|(asset_mint, currency_mint)| [asset_mint.clone(), currency_mint.clone()]).collect();
+        mints.sort();
+        mints.dedup();
+        resolver.prefetch_metadata(mints).await;
+
+        for (asset_mint, currency_mint) in pairs {
+            let store = resolver.resolve(asset_mint.as_str(), currency_mint.as_str()).await;
+            if let Some(symbol) = store.find_symbol(asset_mint.as_str(), currency_mint.as_str()) {
+                let updated = connection_pool.get().map_err(|err| err.to_string())
+                    .and_then(|mut connection| update_unknown_symbol(&mut connection, asset_mint.as_str(), currency_mint.as_str(), symbol.as_str()).map_err(|err| err.to_string()));
+                match updated {
+                    Ok(updated) => info!("Reconciled {} trades of {} {} to {}", updated, asset_mint, currency_mint, symbol),
+                    Err(err) => warn!("Unable to reconcile trades of {} {} to {}, retrying next interval: {}", asset_mint, currency_mint, symbol, err),
+                }
+            }
+        }
+    }
+}
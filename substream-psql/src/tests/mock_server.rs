@@ -0,0 +1,157 @@
+//! In-process `sf.substreams.rpc.v2.Stream` server replaying scripted responses.
+//!
+//! Every call to `Blocks` takes the next session from the script, once the script is exhausted the
+//! server answers with an empty stream which the client treats as having reached the stop block.
+#![cfg(test)]
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use futures03::Stream;
+use prost::Message as _;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::Status;
+use tonic::transport::Server;
+
+use crate::pb::pb_sa_trade::{ProcessExchange, ProcessExchanges};
+use crate::pb::sf::substreams::rpc::v2::{BlockScopedData, BlockUndoSignal, MapModuleOutput, ModulesProgress, Request, Response, SessionInit};
+use crate::pb::sf::substreams::rpc::v2::response::Message;
+use crate::pb::sf::substreams::rpc::v2::stream_server::{self, StreamServer};
+use crate::pb::sf::substreams::v1::{BlockRef, Clock};
+use crate::substreams::SubstreamsEndpoint;
+
+pub const MODULE_NAME: &str = "map_sa_trades";
+
+pub enum MockSession {
+    /// Fails the call before any message is sent, like the server does for an invalid token.
+    Reject(Status),
+    /// Sends the messages in order, an `Err` ends the stream with that status.
+    Replay(Vec<Result<Response, Status>>),
+}
+
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    pub request: Request,
+    pub authorization: Option<String>,
+}
+
+struct MockState {
+    sessions: Mutex<VecDeque<MockSession>>,
+    requests: Mutex<Vec<MockRequest>>,
+}
+
+struct MockStream {
+    state: Arc<MockState>,
+}
+
+#[tonic::async_trait]
+impl stream_server::Stream for MockStream {
+    type BlocksStream = Pin<Box<dyn Stream<Item=Result<Response, Status>> + Send>>;
+
+    async fn blocks(&self, request: tonic::Request<Request>) -> Result<tonic::Response<Self::BlocksStream>, Status> {
+        let authorization = request.metadata().get("authorization").map(|value| value.to_str().unwrap().to_string());
+        self.state.requests.lock().unwrap().push(MockRequest { request: request.into_inner(), authorization });
+
+        let session = self.state.sessions.lock().unwrap().pop_front();
+        match session {
+            None => Ok(tonic::Response::new(Box::pin(futures03::stream::empty()))),
+            Some(MockSession::Reject(status)) => Err(status),
+            Some(MockSession::Replay(messages)) => Ok(tonic::Response::new(Box::pin(futures03::stream::iter(messages)))),
+        }
+    }
+}
+
+pub struct MockServer {
+    pub url: String,
+    state: Arc<MockState>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Starts the server on a random local port, it is stopped when dropped.
+    pub async fn start(sessions: Vec<MockSession>) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(MockState {
+            sessions: Mutex::new(sessions.into()),
+            requests: Mutex::new(vec![]),
+        });
+
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+        let service = StreamServer::new(MockStream { state: state.clone() });
+        tokio::spawn(
+            Server::builder()
+                .add_service(service)
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                    shutdown_signal.await.ok();
+                })
+        );
+
+        MockServer { url, state, shutdown: Some(shutdown) }
+    }
+
+    pub async fn endpoint(&self, token: Option<String>) -> Arc<SubstreamsEndpoint> {
+        Arc::new(SubstreamsEndpoint::new(self.url.as_str(), token).await.unwrap())
+    }
+
+    /// Requests received so far, one per (re)connection.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+pub fn session() -> Result<Response, Status> {
+    Ok(Response { message: Some(Message::Session(SessionInit { trace_id: "mock".to_string() })) })
+}
+
+pub fn progress() -> Result<Response, Status> {
+    Ok(Response { message: Some(Message::Progress(ModulesProgress { modules: vec![] })) })
+}
+
+pub fn block(number: u64, cursor: &str, exchanges: Vec<ProcessExchange>) -> Result<Response, Status> {
+    let output = ProcessExchanges { process_exchanges: exchanges };
+
+    Ok(Response {
+        message: Some(Message::BlockScopedData(BlockScopedData {
+            output: Some(MapModuleOutput {
+                name: MODULE_NAME.to_string(),
+                map_output: Some(prost_types::Any {
+                    type_url: "type.googleapis.com/sa.trade.v1.ProcessExchanges".to_string(),
+                    value: output.encode_to_vec(),
+                }),
+                debug_info: None,
+            }),
+            clock: Some(Clock {
+                id: format!("block-{}", number),
+                number,
+                timestamp: None,
+            }),
+            cursor: cursor.to_string(),
+            final_block_height: number,
+            ..Default::default()
+        })),
+    })
+}
+
+pub fn undo(number: u64, cursor: &str) -> Result<Response, Status> {
+    Ok(Response {
+        message: Some(Message::BlockUndoSignal(BlockUndoSignal {
+            last_valid_block: Some(BlockRef {
+                id: format!("block-{}", number),
+                number,
+            }),
+            last_valid_cursor: cursor.to_string(),
+        })),
+    })
+}
@@ -13,6 +13,14 @@ use crate::substreams_stream::{BlockResponse, SubstreamsStream};
 
 mod test_map01;
 mod base;
+mod test_amounts;
+mod mock_server;
+mod test_stream;
+mod test_coverage;
+mod test_scheduler;
+mod test_handoff;
+mod test_metrics;
+mod test_health;
 
 async fn test_helper_substreams(expected_data: String, start: i64, stop: u64) {
     const ENDPOINT_URL: &str = "https://mainnet.sol.streamingfast.io:443";
@@ -31,6 +39,7 @@ async fn test_helper_substreams(expected_data: String, start: i64, stop: u64) {
         MODULE_NAME.to_string(),
         start,
         stop,
+        false,
     );
 
 
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use bigdecimal::BigDecimal;
+    use staratlas_symbols::symbol_store::{Asset, Exchange, SymbolStore};
+
+    use sa_decoder::trade::{map_exchange_to_trade, rate_from_raw};
+    use crate::pb::pb_sa_trade::ProcessExchange;
+
+    const ASSET_MINT: &str = "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK";
+    const ATLAS_MINT: &str = "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx";
+
+    fn symbol_store() -> Arc<SymbolStore> {
+        Arc::new(SymbolStore::new(
+            vec![Asset {
+                asset_name: "Ammunition".to_string(),
+                pair_name: "ATLAS".to_string(),
+                description: "".to_string(),
+                asset_type: "resource".to_string(),
+                symbol: "AMMOATLAS".to_string(),
+                mint: ASSET_MINT.to_string(),
+                pair_mint: ATLAS_MINT.to_string(),
+                pricescale: 1000000,
+                metadata: None,
+            }],
+            vec![],
+            Exchange::galactic_market(),
+        ))
+    }
+
+    fn exchange(price: &str, total_cost: &str) -> ProcessExchange {
+        ProcessExchange {
+            pk: "pk".to_string(),
+            signature: "signature".to_string(),
+            block: 143203232,
+            timestamp: 1658823438,
+            currency_mint: ATLAS_MINT.to_string(),
+            asset_mint: ASSET_MINT.to_string(),
+            asset_change: "18446744073709551615".to_string(),
+            currency_change: total_cost.to_string(),
+            market_fee: "0.00000001".to_string(),
+            total_cost: total_cost.to_string(),
+            price: price.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_amounts_are_kept_exact() {
+        let trade = map_exchange_to_trade(exchange("184467440737.09551615", "184467440737.09551615"), &symbol_store()).unwrap();
+
+        assert_eq!(trade.symbol, "AMMOATLAS");
+        assert_eq!(trade.asset_change.to_string(), "18446744073709551615");
+        assert_eq!(trade.price.to_string(), "184467440737.09551615");
+        assert_eq!(trade.total_cost.to_string(), "184467440737.09551615");
+        assert_eq!(trade.market_fee.to_string(), "0.00000001");
+    }
+
+    #[test]
+    fn test_amounts_round_trip_json() {
+        for value in ["0", "0.00000001", "0.525", "9007199254740993", "184467440737.09551615"] {
+            let trade = map_exchange_to_trade(exchange(value, value), &symbol_store()).unwrap();
+            let json = serde_json::to_value(&trade).unwrap();
+            assert_eq!(json["price"], value);
+
+            let back: database_psql::model::Trade = serde_json::from_value(json).unwrap();
+            assert_eq!(back.price, BigDecimal::from_str(value).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_unknown_pair_gets_placeholder_symbol() {
+        let mut exchange = exchange("0.525", "0.525");
+        exchange.currency_mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string();
+        let trade = map_exchange_to_trade(exchange, &symbol_store()).unwrap();
+
+        assert_eq!(trade.symbol, format!("UNKNOWN:{}", ASSET_MINT));
+    }
+
+    #[test]
+    fn test_malformed_exchange_is_an_error() {
+        assert!(map_exchange_to_trade(exchange("0.525", "not a number"), &symbol_store()).is_err());
+
+        let mut exchange = exchange("0.525", "0.525");
+        exchange.timestamp = i64::MAX / 1000;
+        assert!(map_exchange_to_trade(exchange, &symbol_store()).is_err());
+    }
+
+    #[test]
+    fn test_fee_rates_from_raw() {
+        assert_eq!(rate_from_raw(0).to_string(), "0");
+        assert_eq!(rate_from_raw(1).to_string(), "0.000001");
+        assert_eq!(rate_from_raw(60000).to_string(), "0.06");
+        assert_eq!(rate_from_raw(1000000).to_string(), "1");
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use database_psql::coverage::{BlockInterval, compute_coverage, coverage_window, covered_intervals, cursor_interval, is_module_cursor, module_cursors};
+    use database_psql::model::Cursor;
+
+    use crate::helper::detect_gaps;
+
+    const MODULE_NAME: &str = "db_sa_trades";
+
+    fn cursor(start_block: i64, end_block: i64, block: Option<i64>) -> Cursor {
+        Cursor {
+            id: format!("db_sa_trades_{}_{}", start_block, end_block),
+            value: None,
+            block,
+            start_block: Some(start_block),
+            end_block: Some(end_block),
+        }
+    }
+
+    #[test]
+    fn test_cursor_interval() {
+        assert_eq!(cursor_interval(&cursor(100, 200, Some(149))), Some(BlockInterval::new(100, 150)));
+        assert_eq!(cursor_interval(&cursor(100, 200, Some(199))), Some(BlockInterval::new(100, 200)));
+        assert_eq!(cursor_interval(&cursor(100, 200, None)), None);
+        // live cursors have no end
+        assert_eq!(cursor_interval(&cursor(100, 0, Some(500))), Some(BlockInterval::new(100, 501)));
+    }
+
+    #[test]
+    fn test_covered_intervals_are_merged() {
+        let cursors = vec![
+            cursor(300, 400, Some(399)),
+            cursor(100, 200, Some(199)),
+            cursor(200, 300, Some(249)),
+            cursor(150, 180, Some(179)),
+        ];
+
+        assert_eq!(covered_intervals(&cursors), vec![BlockInterval::new(100, 250), BlockInterval::new(300, 400)]);
+        assert_eq!(coverage_window(&cursors), Some(BlockInterval::new(100, 400)));
+    }
+
+    #[test]
+    fn test_coverage_percentages() {
+        let cursors = vec![
+            cursor(100, 200, Some(199)),
+            cursor(200, 300, Some(249)),
+            cursor(300, 500, Some(499)),
+        ];
+
+        let coverage = compute_coverage(&cursors, coverage_window(&cursors).unwrap());
+
+        assert_eq!((coverage.start, coverage.end), (100, 500));
+        assert_eq!(coverage.covered_percent, 87.5);
+        assert_eq!(coverage.missing_percent, 12.5);
+        assert_eq!(coverage.missing.len(), 1);
+        assert_eq!((coverage.missing[0].start, coverage.missing[0].end, coverage.missing[0].blocks), (250, 300, 50));
+    }
+
+    #[test]
+    fn test_detect_gaps_skips_planned_ranges() {
+        let cursors = vec![
+            cursor(100, 200, Some(199)),
+            cursor(200, 300, Some(249)),
+            cursor(400, 0, Some(999)),
+            cursor(1000, 2000, None),
+        ];
+
+        assert_eq!(detect_gaps(&cursors, MODULE_NAME, &[]), vec![BlockInterval::new(250, 400)]);
+        assert_eq!(detect_gaps(&cursors, MODULE_NAME, &[vec![250, 300]]), vec![BlockInterval::new(300, 400)]);
+        assert_eq!(detect_gaps(&cursors, MODULE_NAME, &[vec![200, 0]]), vec![]);
+        assert_eq!(detect_gaps(&[], MODULE_NAME, &[vec![200, 0]]), vec![]);
+    }
+
+    #[test]
+    fn test_foreign_cursors_do_not_hide_gaps() {
+        let foreign = |id: &str| Cursor { id: id.to_string(), value: None, block: Some(349), start_block: Some(200), end_block: Some(350) };
+        let cursors = vec![
+            cursor(100, 200, Some(199)),
+            cursor(400, 500, Some(499)),
+            foreign("backfill_rpc_200_350"),
+            foreign("map_sa_trades_200_350"),
+            foreign("db_sa_trades_v2_200_350"),
+        ];
+
+        assert!(is_module_cursor(&cursors[0], MODULE_NAME));
+        assert!(!is_module_cursor(&cursors[4], MODULE_NAME));
+        assert_eq!(detect_gaps(&cursors, MODULE_NAME, &[]), vec![BlockInterval::new(200, 400)]);
+
+        let module_cursors = module_cursors(&cursors, MODULE_NAME);
+        let coverage = compute_coverage(&module_cursors, coverage_window(&module_cursors).unwrap());
+        assert_eq!(coverage.missing_percent, 50.0);
+        assert_eq!((coverage.missing[0].start, coverage.missing[0].end), (200, 400));
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use database_psql::model::Cursor;
+
+    use crate::handoff::{fetch_head_block, find_live_start, resolve_handoff};
+    use crate::tests::mock_server::{block, MockServer, MockSession, MODULE_NAME, progress, session};
+
+    fn cursor(id: &str, start_block: i64, end_block: i64) -> Cursor {
+        Cursor { id: id.to_string(), value: None, block: None, start_block: Some(start_block), end_block: Some(end_block) }
+    }
+
+    #[test]
+    fn test_find_live_start() {
+        let cursors = vec![
+            cursor("map_sa_trades_100_200", 100, 200),
+            cursor("map_sa_trades_150_0", 150, 0),
+            cursor("map_sa_trades_300_0", 300, 0),
+            cursor("backfill_rpc_500_0", 500, 0),
+        ];
+
+        assert_eq!(find_live_start(&cursors, MODULE_NAME), Some(300));
+        assert_eq!(find_live_start(&cursors[..1], MODULE_NAME), None);
+    }
+
+    #[tokio::test]
+    async fn test_head_block_is_first_final_block() {
+        let server = MockServer::start(vec![
+            MockSession::Replay(vec![session(), progress(), block(250, "c250", vec![]), block(251, "c251", vec![])]),
+        ]).await;
+
+        let head = fetch_head_block(server.endpoint(None).await, None, MODULE_NAME.to_string()).await.unwrap();
+
+        assert_eq!(head, 250);
+        let requests = server.requests();
+        assert_eq!(requests[0].request.start_block_num, -1);
+        assert!(requests[0].request.final_blocks_only);
+    }
+
+    #[tokio::test]
+    async fn test_restart_keeps_handoff_of_live_cursor() {
+        let server = MockServer::start(vec![]).await;
+
+        let handoff = resolve_handoff(&[cursor("map_sa_trades_300_0", 300, 0)], server.endpoint(None).await, None, MODULE_NAME.to_string()).await.unwrap();
+
+        assert_eq!(handoff, 300);
+        assert!(server.requests().is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use database_psql::model::Cursor;
+
+    use crate::health::{HealthStatus, live_cursor_block, worker_health};
+
+    const NOW: i64 = 1_680_000_000;
+
+    #[test]
+    fn test_unavailable_without_stream() {
+        let health = worker_health(0, NOW - 5, 1000, Some(990), 100, NOW);
+
+        assert_eq!(health.status, HealthStatus::Unavailable);
+        assert_eq!(health.seconds_since_last_block, Some(5));
+    }
+
+    #[test]
+    fn test_degraded_when_cursor_lags() {
+        let health = worker_health(1, NOW, 1000, Some(850), 100, NOW);
+
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!(health.cursor_lag_blocks, Some(150));
+    }
+
+    #[test]
+    fn test_ok_without_head_or_live_cursor() {
+        let health = worker_health(2, 0, 0, Some(850), 100, NOW);
+        assert_eq!(health.status, HealthStatus::Ok);
+        assert_eq!((health.head_block, health.cursor_lag_blocks, health.seconds_since_last_block), (None, None, None));
+
+        assert_eq!(worker_health(1, NOW, 1000, None, 100, NOW).status, HealthStatus::Ok);
+        assert_eq!(worker_health(1, NOW, 1000, Some(950), 100, NOW).status, HealthStatus::Ok);
+    }
+
+    #[test]
+    fn test_live_cursor_block() {
+        let cursor = |id: &str, block: Option<i64>, end_block: i64| Cursor {
+            id: id.to_string(),
+            value: None,
+            block,
+            start_block: Some(0),
+            end_block: Some(end_block),
+        };
+        let cursors = vec![
+            cursor("map_sa_trades_0_100", Some(99), 100),
+            cursor("map_sa_trades_100_0", Some(500), 0),
+            cursor("other_module_100_0", Some(900), 0),
+        ];
+
+        assert_eq!(live_cursor_block(&cursors, "map_sa_trades"), Some(500));
+        assert_eq!(live_cursor_block(&cursors[..1], "map_sa_trades"), None);
+    }
+}
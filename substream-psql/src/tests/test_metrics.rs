@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use crate::metrics::{ROLE_BACKFILL, ROLE_LIVE, WorkerMetrics};
+    use crate::pb::sf::substreams::rpc::v2::{BlockRange, ModuleProgress, ModulesProgress};
+    use crate::pb::sf::substreams::rpc::v2::module_progress::{ProcessedRanges, Type};
+
+    #[test]
+    fn test_head_lag_of_live_tail() {
+        let metrics = WorkerMetrics::new();
+
+        metrics.observe_block(ROLE_BACKFILL, 1, 1000, None);
+        metrics.observe_head(5000);
+        assert_eq!(metrics.head_lag_blocks.get(), 0);
+
+        metrics.observe_block(ROLE_LIVE, 0, 4990, None);
+        assert_eq!(metrics.head_lag_blocks.get(), 10);
+        metrics.observe_head(5002);
+        assert_eq!(metrics.head_lag_blocks.get(), 12);
+        assert_eq!(metrics.current_block.with_label_values(&[ROLE_BACKFILL, "1"]).get(), 1000);
+    }
+
+    #[test]
+    fn test_current_block_per_backfill_task() {
+        let metrics = WorkerMetrics::new();
+
+        metrics.observe_block(ROLE_BACKFILL, 1, 1000, None);
+        metrics.observe_block(ROLE_BACKFILL, 2, 500, None);
+
+        assert_eq!(metrics.current_block.with_label_values(&[ROLE_BACKFILL, "1"]).get(), 1000);
+        assert_eq!(metrics.current_block.with_label_values(&[ROLE_BACKFILL, "2"]).get(), 500);
+        assert!(metrics.gather().contains("roguemarket_worker_current_block{role=\"backfill\",task=\"2\"} 500"));
+    }
+
+    #[test]
+    fn test_processed_ranges_of_progress() {
+        let metrics = WorkerMetrics::new();
+
+        metrics.observe_progress(&ModulesProgress {
+            modules: vec![ModuleProgress {
+                name: "map_sa_trades".to_string(),
+                r#type: Some(Type::ProcessedRanges(ProcessedRanges {
+                    processed_ranges: vec![
+                        BlockRange { start_block: 100, end_block: 200 },
+                        BlockRange { start_block: 200, end_block: 300 },
+                    ],
+                })),
+            }],
+        });
+
+        assert_eq!(metrics.module_processed_block.with_label_values(&["map_sa_trades"]).get(), 300);
+        assert_eq!(metrics.module_processed_ranges.with_label_values(&["map_sa_trades"]).get(), 2);
+    }
+
+    #[test]
+    fn test_gather_exposes_all_metrics() {
+        let metrics = WorkerMetrics::new();
+        metrics.trades_inserted.with_label_values(&[ROLE_LIVE]).inc();
+        metrics.db_write_seconds.observe(0.01);
+        metrics.reconnects.inc();
+        metrics.undos.inc();
+        metrics.unknown_symbol_trades.inc();
+
+        let text = metrics.gather();
+
+        assert!(text.contains("roguemarket_worker_trades_inserted_total{role=\"live\"} 1"));
+        assert!(text.contains("roguemarket_worker_db_write_seconds_count 1"));
+        assert!(text.contains("roguemarket_worker_stream_reconnects_total 1"));
+        assert!(text.contains("roguemarket_worker_undo_signals_total 1"));
+        assert!(text.contains("roguemarket_worker_unknown_symbol_trades_total 1"));
+    }
+}
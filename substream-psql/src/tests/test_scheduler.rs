@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use database_psql::coverage::BlockInterval;
+    use database_psql::model::Cursor;
+
+    use crate::scheduler::{pending_intervals, RangeScheduler, Segment, split_segments};
+
+    #[test]
+    fn test_split_segments_keeps_remainder() {
+        let segments = split_segments(&[BlockInterval::new(100, 350), BlockInterval::new(500, 510)], 100);
+
+        let bounds: Vec<(u64, u64)> = segments.iter().map(|segment| (segment.start, segment.end())).collect();
+        assert_eq!(bounds, vec![(100, 200), (200, 300), (300, 350), (500, 510)]);
+    }
+
+    #[test]
+    fn test_idle_worker_steals_upper_half() {
+        let scheduler = RangeScheduler::new(vec![BlockInterval::new(0, 100)], 100, 10);
+        let first = scheduler.next().unwrap();
+        for block in 0..20 {
+            assert!(first.advance(block));
+        }
+
+        let stolen = scheduler.next().unwrap();
+
+        assert_eq!((stolen.start, stolen.end()), (60, 100));
+        assert_eq!(stolen.cursor_id("db_sa_trades"), "db_sa_trades_60_100");
+        assert_eq!(first.end(), 60);
+        assert!(!first.advance(60));
+        assert_eq!(scheduler.total(), 2);
+    }
+
+    #[test]
+    fn test_small_segments_are_not_split() {
+        let scheduler = RangeScheduler::new(vec![BlockInterval::new(0, 15)], 100, 10);
+        let _first = scheduler.next().unwrap();
+
+        assert!(scheduler.next().is_none());
+    }
+
+    /// Workers stream one block per round, the first segment is dense and a lot slower than the others.
+    #[test]
+    fn test_every_block_is_streamed_once() {
+        let scheduler = RangeScheduler::new(vec![BlockInterval::new(0, 1000)], 250, 5);
+        let mut workers: Vec<Option<(Arc<Segment>, u64)>> = vec![None, None, None];
+        let mut streamed = vec![];
+
+        for round in 0..10000 {
+            for (index, worker) in workers.iter_mut().enumerate() {
+                if worker.is_none() {
+                    *worker = scheduler.next().map(|segment| {
+                        let start = segment.start;
+                        (segment, start)
+                    });
+                }
+                let Some((segment, block)) = worker.as_mut() else { continue };
+                if index == 0 && round % 4 != 0 {
+                    continue;
+                }
+                if *block < segment.stop && segment.advance(*block) {
+                    streamed.push(*block);
+                    *block += 1;
+                } else {
+                    scheduler.finish(segment);
+                    *worker = None;
+                }
+            }
+        }
+
+        streamed.sort_unstable();
+        assert_eq!(streamed, (0..1000).collect::<Vec<u64>>());
+        assert!(scheduler.total() > 4);
+    }
+
+    #[test]
+    fn test_resume_skips_covered_blocks() {
+        let cursors = vec![
+            Cursor { id: "db_sa_trades_0_100".to_string(), value: None, block: Some(99), start_block: Some(0), end_block: Some(100) },
+            Cursor { id: "db_sa_trades_100_200".to_string(), value: Some("cursor".to_string()), block: Some(149), start_block: Some(100), end_block: Some(200) },
+            // shrunk by a split, the rest was streamed by the stolen segment
+            Cursor { id: "db_sa_trades_200_300".to_string(), value: None, block: Some(239), start_block: Some(200), end_block: Some(240) },
+            Cursor { id: "db_sa_trades_240_300".to_string(), value: None, block: None, start_block: Some(240), end_block: Some(300) },
+        ];
+
+        assert_eq!(pending_intervals(&cursors, "db_sa_trades", 0, 300), vec![BlockInterval::new(150, 200), BlockInterval::new(240, 300)]);
+    }
+
+    #[test]
+    fn test_resume_ignores_cursors_of_other_streams() {
+        let cursors = vec![
+            Cursor { id: "db_sa_trades_0_100".to_string(), value: None, block: Some(99), start_block: Some(0), end_block: Some(100) },
+            Cursor { id: "map_sa_trades_100_300".to_string(), value: None, block: Some(299), start_block: Some(100), end_block: Some(300) },
+            Cursor { id: "backfill_rpc_0_300".to_string(), value: None, block: Some(299), start_block: Some(0), end_block: Some(300) },
+            Cursor { id: "db_sa_trades_v2_0_300".to_string(), value: None, block: Some(299), start_block: Some(0), end_block: Some(300) },
+        ];
+
+        assert_eq!(pending_intervals(&cursors, "db_sa_trades", 0, 300), vec![BlockInterval::new(100, 300)]);
+    }
+}
@@ -0,0 +1,263 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use anyhow::Error;
+    use database_psql::model::{Cursor, Trade};
+    use futures03::StreamExt;
+    use staratlas_symbols::symbol_store::{Exchange, SymbolStore};
+    use tonic::Status;
+
+    use sa_decoder::trade::map_exchange_to_trade;
+    use crate::helper::extract_pb_sa_trades_from_map;
+    use crate::pb::pb_sa_trade::ProcessExchange;
+    use crate::scheduler::Segment;
+    use crate::substreams_stream::{BlockResponse, SubstreamsStream};
+    use crate::tests::mock_server::{block, MockServer, MockSession, MODULE_NAME, progress, session, undo};
+    use crate::writer::{revert_stream_undo, TradeWriter, write_stream_trade};
+
+    /// `trades` and `cursors` tables kept in memory, with the semantics of the psql writer.
+    #[derive(Default)]
+    struct MemoryWriter {
+        trades: Mutex<HashMap<String, Trade>>,
+        cursors: Mutex<HashMap<String, Cursor>>,
+    }
+
+    impl MemoryWriter {
+        fn trades(&self) -> Vec<(String, String, i64)> {
+            let mut trades: Vec<_> = self.trades.lock().unwrap().values()
+                .map(|trade| (trade.source.clone(), trade.pk.clone(), trade.block))
+                .collect();
+            trades.sort();
+            trades
+        }
+    }
+
+    impl TradeWriter for MemoryWriter {
+        fn write_trade(&self, trade: Trade, cursor: Cursor) {
+            self.trades.lock().unwrap().insert(trade.pk.clone(), trade);
+            self.cursors.lock().unwrap().insert(cursor.id.clone(), cursor);
+        }
+
+        fn revert_from_block(&self, source: &str, from_block: i64, cursor: Cursor) -> Result<usize, Error> {
+            let mut trades = self.trades.lock().unwrap();
+            let before = trades.len();
+            trades.retain(|_, trade| trade.source != source || trade.block < from_block);
+            self.cursors.lock().unwrap().insert(cursor.id.clone(), cursor);
+            Ok(before - trades.len())
+        }
+    }
+
+    fn symbol_store() -> SymbolStore {
+        SymbolStore::new(vec![], vec![], Exchange::galactic_market())
+    }
+
+    fn trade_exchange(pk: &str, block: u64) -> ProcessExchange {
+        ProcessExchange {
+            pk: pk.to_string(),
+            signature: format!("signature-{}", pk),
+            block,
+            timestamp: 1658823438,
+            asset_change: "1".to_string(),
+            currency_change: "1".to_string(),
+            market_fee: "0".to_string(),
+            price: "1".to_string(),
+            total_cost: "1".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Writes the stream the way `run_substream` does, until the stream is consumed.
+    async fn write_stream(server: &MockServer, writer: &MemoryWriter, segment: &Segment) {
+        let symbol_store = symbol_store();
+        let cursor_id = segment.cursor_id(MODULE_NAME);
+        let mut stream = SubstreamsStream::new(server.endpoint(None).await, None, None, MODULE_NAME.to_string(), segment.start as i64, segment.stop, false);
+
+        while let Some(event) = stream.next().await {
+            match event.unwrap() {
+                BlockResponse::New(data) => {
+                    let cursor = Some(data.cursor.clone());
+                    for exchange in extract_pb_sa_trades_from_map(data).unwrap().process_exchanges {
+                        let trade = map_exchange_to_trade(exchange, &symbol_store).unwrap();
+                        write_stream_trade(writer, cursor_id.as_str(), segment, cursor.clone(), trade);
+                    }
+                }
+                BlockResponse::Undo(undo_signal) => {
+                    revert_stream_undo(writer, cursor_id.as_str(), segment, &undo_signal).unwrap();
+                }
+            }
+        }
+    }
+
+    async fn stream_events(server: &MockServer, cursor: Option<String>, token: Option<String>) -> Vec<String> {
+        let mut stream = SubstreamsStream::new(
+            server.endpoint(token).await,
+            cursor,
+            None,
+            MODULE_NAME.to_string(),
+            100,
+            200,
+            false,
+        );
+
+        let mut events = vec![];
+        while let Some(event) = stream.next().await {
+            events.push(match event {
+                Ok(BlockResponse::New(data)) => format!("new:{}:{}", data.clock.unwrap().number, data.cursor),
+                Ok(BlockResponse::Undo(signal)) => format!("undo:{}:{}", signal.last_valid_block.unwrap().number, signal.last_valid_cursor),
+                Err(err) => format!("error:{:?}", err.downcast::<Status>().unwrap().code()),
+            });
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn test_blocks_are_streamed() {
+        let server = MockServer::start(vec![
+            MockSession::Replay(vec![session(), progress(), block(100, "c100", vec![]), progress(), block(101, "c101", vec![])]),
+        ]).await;
+
+        let events = stream_events(&server, None, None).await;
+        assert_eq!(events, vec!["new:100:c100", "new:101:c101"]);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].request.start_block_num, 100);
+        assert_eq!(requests[0].request.stop_block_num, 200);
+        assert_eq!(requests[0].request.start_cursor, "");
+        assert_eq!(requests[0].request.output_module, MODULE_NAME);
+        assert!(!requests[0].request.final_blocks_only);
+    }
+
+    #[tokio::test]
+    async fn test_final_blocks_only_is_requested() {
+        let server = MockServer::start(vec![]).await;
+
+        let mut stream = SubstreamsStream::new(server.endpoint(None).await, None, None, MODULE_NAME.to_string(), 100, 200, true);
+        assert!(stream.next().await.is_none());
+        assert!(server.requests()[0].request.final_blocks_only);
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_from_latest_cursor() {
+        let server = MockServer::start(vec![
+            MockSession::Replay(vec![block(100, "c100", vec![]), Err(Status::unavailable("connection reset"))]),
+            MockSession::Replay(vec![block(101, "c101", vec![])]),
+        ]).await;
+
+        let events = stream_events(&server, None, None).await;
+        assert_eq!(events, vec!["new:100:c100", "new:101:c101"]);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].request.start_cursor, "c100");
+    }
+
+    #[tokio::test]
+    async fn test_resumes_from_stored_cursor() {
+        let server = MockServer::start(vec![]).await;
+
+        let events = stream_events(&server, Some("c142".to_string()), None).await;
+        assert!(events.is_empty());
+        assert_eq!(server.requests()[0].request.start_cursor, "c142");
+    }
+
+    #[tokio::test]
+    async fn test_undo_rewinds_cursor() {
+        let server = MockServer::start(vec![
+            MockSession::Replay(vec![block(100, "c100", vec![]), block(101, "c101", vec![]), undo(100, "c100"), Err(Status::internal("restart"))]),
+        ]).await;
+
+        let events = stream_events(&server, None, None).await;
+        assert_eq!(events, vec!["new:100:c100", "new:101:c101", "undo:100:c100"]);
+        assert_eq!(server.requests()[1].request.start_cursor, "c100");
+    }
+
+    #[tokio::test]
+    async fn test_unauthenticated_is_not_retried() {
+        let server = MockServer::start(vec![
+            MockSession::Reject(Status::unauthenticated("invalid token")),
+        ]).await;
+
+        let events = stream_events(&server, None, Some("Bearer invalid".to_string())).await;
+        assert_eq!(events, vec!["error:Unauthenticated"]);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].authorization, Some("Bearer invalid".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_unauthenticated_during_stream_is_not_retried() {
+        let server = MockServer::start(vec![
+            MockSession::Replay(vec![block(100, "c100", vec![]), Err(Status::unauthenticated("token expired"))]),
+        ]).await;
+
+        let events = stream_events(&server, None, None).await;
+        assert_eq!(events, vec!["new:100:c100", "error:Unauthenticated"]);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_exchanges_are_decoded() {
+        let exchange = ProcessExchange {
+            signature: "signature".to_string(),
+            block: 100,
+            price: "0.525".to_string(),
+            ..Default::default()
+        };
+        let server = MockServer::start(vec![
+            MockSession::Replay(vec![block(100, "c100", vec![exchange.clone()])]),
+        ]).await;
+
+        let mut stream = SubstreamsStream::new(server.endpoint(None).await, None, None, MODULE_NAME.to_string(), 100, 200, false);
+        match stream.next().await {
+            Some(Ok(BlockResponse::New(data))) => {
+                let decoded = extract_pb_sa_trades_from_map(data).unwrap();
+                assert_eq!(decoded.process_exchanges, vec![exchange]);
+            }
+            _ => panic!("expected a block"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_undo_deletes_trades_of_the_stream() {
+        let server = MockServer::start(vec![
+            MockSession::Replay(vec![
+                block(100, "c100", vec![trade_exchange("a", 100)]),
+                block(101, "c101", vec![trade_exchange("b", 101)]),
+                block(102, "c102", vec![trade_exchange("c", 102)]),
+                undo(100, "c100"),
+                Err(Status::internal("restart")),
+            ]),
+            MockSession::Replay(vec![block(101, "c101b", vec![trade_exchange("d", 101)])]),
+        ]).await;
+        let segment = Segment::new(100, 200);
+        let cursor_id = segment.cursor_id(MODULE_NAME);
+
+        // trades of the same blocks written by another stream
+        let writer = MemoryWriter::default();
+        let other_stream = Segment::new(0, 200);
+        let other_id = other_stream.cursor_id(MODULE_NAME);
+        for (pk, block) in [("x", 100), ("y", 101), ("z", 102)] {
+            let trade = map_exchange_to_trade(trade_exchange(pk, block), &symbol_store()).unwrap();
+            write_stream_trade(&writer, other_id.as_str(), &other_stream, None, trade);
+        }
+
+        write_stream(&server, &writer, &segment).await;
+
+        assert_eq!(writer.trades(), vec![
+            (other_id.clone(), "x".to_string(), 100),
+            (other_id.clone(), "y".to_string(), 101),
+            (other_id.clone(), "z".to_string(), 102),
+            (cursor_id.clone(), "a".to_string(), 100),
+            (cursor_id.clone(), "d".to_string(), 101),
+        ]);
+        assert_eq!(server.requests()[1].request.start_cursor, "c100");
+
+        let cursor = writer.cursors.lock().unwrap()[&cursor_id].clone();
+        assert_eq!(cursor.value, Some("c101b".to_string()));
+        assert_eq!(cursor.block, Some(101));
+    }
+}
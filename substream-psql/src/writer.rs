@@ -0,0 +1,60 @@
+use anyhow::Error;
+use database_psql::db_cursors::update_cursor;
+use database_psql::db_trades::{create_or_update_trade_table, delete_trades_from_block};
+use database_psql::model::{Cursor, Trade};
+use diesel::PgConnection;
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::pb::sf::substreams::rpc::v2::BlockUndoSignal;
+use crate::scheduler::Segment;
+use crate::segment_end_block;
+
+/// Storage of the streamed trades and the stream cursors.
+pub trait TradeWriter {
+    /// Upserts the trade, then moves the stream cursor.
+    fn write_trade(&self, trade: Trade, cursor: Cursor);
+
+    /// Deletes the trades of the stream `source` from `from_block` on, then rewinds the stream cursor.
+    fn revert_from_block(&self, source: &str, from_block: i64, cursor: Cursor) -> Result<usize, Error>;
+}
+
+impl TradeWriter for Pool<ConnectionManager<PgConnection>> {
+    fn write_trade(&self, trade: Trade, cursor: Cursor) {
+        create_or_update_trade_table(&mut self.get().expect("Error getting connection"), trade);
+        update_cursor(&mut self.get().expect("Error getting connection"), cursor.id.clone(), cursor);
+    }
+
+    fn revert_from_block(&self, source: &str, from_block: i64, cursor: Cursor) -> Result<usize, Error> {
+        let deleted = delete_trades_from_block(&mut self.get()?, source, from_block)?;
+        update_cursor(&mut self.get()?, cursor.id.clone(), cursor);
+        Ok(deleted)
+    }
+}
+
+/// Stores a trade of the segment stream, tagged with the stream cursor id, and moves the cursor to its block.
+pub fn write_stream_trade<W: TradeWriter>(writer: &W, cursor_id: &str, segment: &Segment, cursor: Option<String>, mut trade: Trade) {
+    trade.source = cursor_id.to_string();
+    let new_cursor = Cursor {
+        id: cursor_id.to_string(),
+        value: cursor,
+        block: Some(trade.block),
+        start_block: Some(segment.start as i64),
+        end_block: Some(segment_end_block(segment)),
+    };
+    writer.write_trade(trade, new_cursor);
+}
+
+/// Blocks after the last valid one were forked away, their trades are removed and the cursor rewound.
+/// Trades of other streams are kept. On error the cursor is not rewound, a restart receives the undo again.
+pub fn revert_stream_undo<W: TradeWriter>(writer: &W, cursor_id: &str, segment: &Segment, undo_signal: &BlockUndoSignal) -> Result<usize, Error> {
+    let last_valid_block = undo_signal.last_valid_block.as_ref().map(|block_ref| block_ref.number).unwrap_or_default();
+    let from_block = (last_valid_block + 1).max(segment.start) as i64;
+    let new_cursor = Cursor {
+        id: cursor_id.to_string(),
+        value: Some(undo_signal.last_valid_cursor.clone()),
+        block: (last_valid_block >= segment.start).then(|| last_valid_block as i64),
+        start_block: Some(segment.start as i64),
+        end_block: Some(segment_end_block(segment)),
+    };
+    writer.revert_from_block(cursor_id, from_block, new_cursor)
+}
@@ -17,4 +17,19 @@ create table cursors
     cursor    text,
     block_num bigint,
     block_id  text
-);
\ No newline at end of file
+);
+create table candle
+(
+    id            text not null
+        constraint candle_pk primary key,
+    asset_mint    text,
+    currency_mint text,
+    timestamp     bigint,
+    open          numeric,
+    high          numeric,
+    low           numeric,
+    close         numeric,
+    volume        numeric,
+    quote_volume  numeric,
+    trades        numeric
+);
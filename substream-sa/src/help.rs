@@ -1,69 +1,56 @@
-use std::collections::HashMap;
-use std::str::FromStr;
+use sa_decoder::pb::sa::trade::v1::ProcessExchange;
 
-use bigdecimal::BigDecimal;
-use substreams::log;
-use substreams_solana::pb::sol::v1::{CompiledInstruction, TransactionStatusMeta};
-
-pub fn calc_token_decimals(value: u64, mint: String) -> f64 {
-    let mut store = HashMap::new();
-    store.insert("ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx", 8);
-    store.insert("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", 6);
-
-
-    (value as f64) / (10.0_f64).powi(*store.get(mint.as_str()).unwrap())
+/// Start of the minute of `timestamp`, in seconds.
+pub fn candle_minute(timestamp: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(60)
 }
 
-pub fn calc_token_balance_change(meta: &TransactionStatusMeta, currency_mint: String, authority: String) -> f64 {
-    log::info!("{}", meta
-        .clone().post_token_balances
-        .into_iter()
-        .find(|tb| { tb.mint == currency_mint && tb.owner == authority })
-        .unwrap_or_default()
-        .ui_token_amount
-        .unwrap_or_default()
-        .ui_amount);
-
-    log::info!("{}", meta
-        .clone().pre_token_balances
-        .into_iter()
-        .find(|tb| { tb.mint == currency_mint && tb.owner == authority })
-        .unwrap_or_default()
-        .ui_token_amount
-        .unwrap_or_default()
-        .ui_amount);
-
-
-    let delta = BigDecimal::from_str(meta
-        .clone().post_token_balances
-        .into_iter()
-        .find(|tb| { tb.mint == currency_mint && tb.owner == authority })
-        .unwrap_or_default()
-        .ui_token_amount
-        .unwrap_or_default()
-        .ui_amount_string.as_str()).unwrap_or_default() -
-        BigDecimal::from_str(meta.clone().pre_token_balances
-            .into_iter()
-            .find(|tb| { tb.mint == currency_mint && tb.owner == authority })
-            .unwrap_or_default()
-            .ui_token_amount
-            .unwrap_or_default()
-            .ui_amount_string.as_str()).unwrap_or_default();
-
-    return delta.abs().to_string().parse::<f64>().unwrap_or_default();
+/// Key of the 1-minute candle of a trade: `{minute_timestamp}:{asset_mint}:{currency_mint}`.
+///
+/// The minute comes first so all candles of a closed minute are dropped with one `delete_prefix`.
+pub fn candle_key(exchange: &ProcessExchange) -> String {
+    format!("{}:{}:{}", candle_minute(exchange.timestamp), exchange.asset_mint, exchange.currency_mint)
 }
 
-pub fn find_asset_mint_in_inner_instruction_get_index(inner_instructions: Vec<CompiledInstruction>, asset_mint_account: u8) -> Option<usize> {
-    log::info!("{:?}", inner_instructions);
-    for (idx, instruction) in inner_instructions.clone().into_iter().enumerate() {
-        if instruction.accounts.contains(&asset_mint_account) {
-            return Some(idx);
-        }
-    }
-    None
+/// Prefix of the candle keys of the minute before the one of `timestamp`.
+pub fn closed_candle_prefix(timestamp: i64) -> String {
+    format!("{}:", candle_minute(timestamp) - 60)
 }
 
+pub fn candle_key_segment(key: &str, index: usize) -> &str {
+    key.split(':').nth(index).unwrap_or_default()
+}
 
 pub fn db_change_create(value: &str) -> (&str, &str) {
     return (value, value);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use sa_decoder::pb::sa::trade::v1::ProcessExchange;
+
+    use super::{candle_key, candle_key_segment, closed_candle_prefix};
+
+    #[test]
+    fn candle_key_is_bucketed_per_minute() {
+        let exchange = ProcessExchange {
+            asset_mint: "ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK".to_string(),
+            currency_mint: "ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx".to_string(),
+            timestamp: 1658823438,
+            ..Default::default()
+        };
+
+        let key = candle_key(&exchange);
+        assert_eq!(key, "1658823420:ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK:ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx");
+        assert_eq!(candle_key_segment(&key, 0), "1658823420");
+        assert_eq!(candle_key_segment(&key, 3), "");
+    }
+
+    #[test]
+    fn closed_candle_prefix_is_the_previous_minute() {
+        assert_eq!(closed_candle_prefix(1658823480), "1658823420:");
+        assert_eq!(closed_candle_prefix(1658823539), "1658823420:");
+        // the open candle of the block's own minute is kept
+        assert!(!"1658823480:ammoK8AkX2wnebQb35cDAZtTkvsXQbi82cGeTnUvvfK:ATLASXmbPQxBUYbxPsV97usA3fPQYEqzQBUHgiFCUsXx".starts_with(closed_candle_prefix(1658823480).as_str()));
+    }
+}
@@ -1,43 +1,52 @@
-use std::ops::Index;
 use std::str::FromStr;
 
-use bigdecimal::BigDecimal;
+use sa_decoder::pb::sa::trade::v1::{CurrencyRoyalty, ProcessingWarnings};
+use sa_decoder::pb::sa::trade::v1::ProcessExchange;
+use sa_decoder::pb::sa::trade::v1::ProcessExchanges;
+use sa_decoder::process_blocks;
 use substreams::errors::Error;
 use substreams::log;
+use substreams::pb::substreams::Clock;
+use substreams::scalar::BigDecimal as ScalarBigDecimal;
+use substreams::store::{StoreAdd, StoreAddBigDecimal, StoreDelete, StoreGetBigDecimal, StoreMax, StoreMaxBigDecimal, StoreMin, StoreMinBigDecimal, StoreSetBigDecimal, StoreSetIfNotExists, StoreSetIfNotExistsBigDecimal};
 use substreams::store::{StoreGet, StoreGetProto, StoreSet, StoreSetProto};
 use substreams::store::StoreNew;
 use substreams_database_change::pb::database::DatabaseChanges;
 use substreams_database_change::pb::database::table_change::Operation;
 use substreams_solana::pb::sol;
-use substreams_solana::pb::sol::v1::Block;
 
-use crate::help::{calc_token_balance_change, calc_token_decimals, db_change_create, find_asset_mint_in_inner_instruction_get_index};
-use crate::pb::sa::trade::v1::ProcessExchange;
-use crate::pb::sa::trade::v1::ProcessExchanges;
-use crate::sa_instruction::MarketplaceInstruction;
+use crate::help::{candle_key, candle_key_segment, closed_candle_prefix, db_change_create};
 
-mod pb;
 mod help;
-mod sa_instruction;
 mod option;
 
 
 #[substreams::handlers::map]
-fn map_sa_trades(blk: sol::v1::Block) -> Result<ProcessExchanges, Error> {
+fn map_sa_trades(blk: sol::v1::Block, royalties: StoreGetProto<CurrencyRoyalty>) -> Result<ProcessExchanges, Error> {
     log::info!("map_sa_trades");
     let mut process_exchanges = vec![];
-    process_blocks(blk, &mut process_exchanges).unwrap();
+    process_blocks(blk, &mut process_exchanges, &mut vec![], &mut vec![]);
+    process_exchanges.iter_mut().for_each(|exchange| apply_royalty(exchange, &royalties));
 
     return Ok(ProcessExchanges { process_exchanges });
 }
 
+#[substreams::handlers::map]
+fn map_sa_warnings(blk: sol::v1::Block) -> Result<ProcessingWarnings, Error> {
+    log::info!("map_sa_warnings");
+    let mut warnings = vec![];
+    process_blocks(blk, &mut vec![], &mut warnings, &mut vec![]);
+
+    return Ok(ProcessingWarnings { warnings });
+}
+
 
 #[substreams::handlers::store]
 fn store_sa_trades(blk: sol::v1::Block, output: StoreSetProto<ProcessExchange>) {
     log::info!("store_sa_trades");
 
     let mut process_exchanges = vec![];
-    process_blocks(blk, &mut process_exchanges).unwrap();
+    process_blocks(blk, &mut process_exchanges, &mut vec![], &mut vec![]);
 
 
     for exchange in process_exchanges {
@@ -45,6 +54,132 @@ fn store_sa_trades(blk: sol::v1::Block, output: StoreSetProto<ProcessExchange>)
     }
 }
 
+#[substreams::handlers::store]
+fn store_sa_currency_royalty(blk: sol::v1::Block, output: StoreSetProto<CurrencyRoyalty>) {
+    log::info!("store_sa_currency_royalty");
+
+    let mut royalties = vec![];
+    process_blocks(blk, &mut vec![], &mut vec![], &mut royalties);
+
+    for (ordinal, royalty) in royalties.into_iter().enumerate() {
+        output.set(ordinal as u64, format!("royalty:{}", royalty.currency_mint), &royalty);
+    }
+}
+
+#[substreams::handlers::store]
+fn store_sa_candle_open(clock: Clock, exchanges: ProcessExchanges, output: StoreSetIfNotExistsBigDecimal) {
+    log::info!("store_sa_candle_open");
+    prune_closed_candles(&clock, &output);
+    for (ordinal, exchange) in exchanges.process_exchanges.iter().enumerate() {
+        if let Ok(price) = ScalarBigDecimal::from_str(exchange.price.as_str()) {
+            output.set_if_not_exists(ordinal as u64, candle_key(exchange), &price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_sa_candle_high(clock: Clock, exchanges: ProcessExchanges, output: StoreMaxBigDecimal) {
+    log::info!("store_sa_candle_high");
+    prune_closed_candles(&clock, &output);
+    for (ordinal, exchange) in exchanges.process_exchanges.iter().enumerate() {
+        if let Ok(price) = ScalarBigDecimal::from_str(exchange.price.as_str()) {
+            output.max(ordinal as u64, candle_key(exchange), price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_sa_candle_low(clock: Clock, exchanges: ProcessExchanges, output: StoreMinBigDecimal) {
+    log::info!("store_sa_candle_low");
+    prune_closed_candles(&clock, &output);
+    for (ordinal, exchange) in exchanges.process_exchanges.iter().enumerate() {
+        if let Ok(price) = ScalarBigDecimal::from_str(exchange.price.as_str()) {
+            output.min(ordinal as u64, candle_key(exchange), price);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_sa_candle_close(clock: Clock, exchanges: ProcessExchanges, output: StoreSetBigDecimal) {
+    log::info!("store_sa_candle_close");
+    prune_closed_candles(&clock, &output);
+    for (ordinal, exchange) in exchanges.process_exchanges.iter().enumerate() {
+        if let Ok(price) = ScalarBigDecimal::from_str(exchange.price.as_str()) {
+            output.set(ordinal as u64, candle_key(exchange), &price);
+        }
+    }
+}
+
+/// Keeps `volume` (assets), `quote_volume` (currency) and `trades` (count) per candle, as suffixes of the candle key.
+#[substreams::handlers::store]
+fn store_sa_candle_volume(clock: Clock, exchanges: ProcessExchanges, output: StoreAddBigDecimal) {
+    log::info!("store_sa_candle_volume");
+    prune_closed_candles(&clock, &output);
+    for (ordinal, exchange) in exchanges.process_exchanges.iter().enumerate() {
+        let key = candle_key(exchange);
+        if let Ok(volume) = ScalarBigDecimal::from_str(exchange.asset_change.as_str()) {
+            output.add(ordinal as u64, format!("{}:volume", key), volume);
+        }
+        if let Ok(quote_volume) = ScalarBigDecimal::from_str(exchange.total_cost.as_str()) {
+            output.add(ordinal as u64, format!("{}:quote_volume", key), quote_volume);
+        }
+        output.add(ordinal as u64, format!("{}:trades", key), ScalarBigDecimal::one());
+    }
+}
+
+/// Drops the candles of the minute before the block, no trade can be added to them anymore.
+///
+/// Solana produces a block every ~400ms, so the blocks of each minute prune the one before it. Only a minute
+/// followed by no block at all, e.g. during a cluster halt, keeps its keys.
+fn prune_closed_candles<S: StoreDelete>(clock: &Clock, output: &S) {
+    if let Some(timestamp) = clock.timestamp.as_ref() {
+        output.delete_prefix(0, &closed_candle_prefix(timestamp.seconds));
+    }
+}
+
+/// Upserts the 1-minute candles touched by the trades of this block.
+#[substreams::handlers::map]
+fn candles_db_out(
+    exchanges: ProcessExchanges,
+    open: StoreGetBigDecimal,
+    high: StoreGetBigDecimal,
+    low: StoreGetBigDecimal,
+    close: StoreGetBigDecimal,
+    volume: StoreGetBigDecimal) -> Result<DatabaseChanges, Error> {
+    log::info!("candles_db_out");
+
+    let mut database_changes: DatabaseChanges = Default::default();
+    let mut keys: Vec<String> = vec![];
+    for exchange in exchanges.process_exchanges.iter() {
+        let key = candle_key(exchange);
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    for key in keys {
+        // the open price is only set once, when it did not exist before this block the candle is new
+        let operation = match open.get_first(&key) {
+            None => Operation::Create,
+            Some(_) => Operation::Update,
+        };
+        let get = |store: &StoreGetBigDecimal, key: String| store.get_last(key).map(|value| value.to_string()).unwrap_or("0".to_string());
+
+        database_changes.push_change("candle", key.as_str(), 0, operation)
+            .change("asset_mint", db_change_create(candle_key_segment(&key, 1)))
+            .change("currency_mint", db_change_create(candle_key_segment(&key, 2)))
+            .change("timestamp", db_change_create(candle_key_segment(&key, 0)))
+            .change("open", db_change_create(get(&open, key.clone()).as_str()))
+            .change("high", db_change_create(get(&high, key.clone()).as_str()))
+            .change("low", db_change_create(get(&low, key.clone()).as_str()))
+            .change("close", db_change_create(get(&close, key.clone()).as_str()))
+            .change("volume", db_change_create(get(&volume, format!("{}:volume", key)).as_str()))
+            .change("quote_volume", db_change_create(get(&volume, format!("{}:quote_volume", key)).as_str()))
+            .change("trades", db_change_create(get(&volume, format!("{}:trades", key)).as_str()));
+    }
+    return Ok(database_changes);
+}
+
 #[substreams::handlers::map]
 fn db_out(exchanges: ProcessExchanges) -> Result<DatabaseChanges, Error> {
     log::info!("db_sa_trades");
@@ -69,12 +204,13 @@ fn db_out(exchanges: ProcessExchanges) -> Result<DatabaseChanges, Error> {
 
 
 #[substreams::handlers::map]
-fn db_sa_trades(blk: sol::v1::Block) -> Result<DatabaseChanges, Error> {
+fn db_sa_trades(blk: sol::v1::Block, royalties: StoreGetProto<CurrencyRoyalty>) -> Result<DatabaseChanges, Error> {
     log::info!("db_sa_trades");
 
     substreams::register_panic_hook();
     let mut process_exchanges = vec![];
-    process_blocks(blk, &mut process_exchanges).unwrap();
+    process_blocks(blk, &mut process_exchanges, &mut vec![], &mut vec![]);
+    process_exchanges.iter_mut().for_each(|exchange| apply_royalty(exchange, &royalties));
 
 
     let mut database_changes: DatabaseChanges = Default::default();
@@ -94,7 +230,11 @@ fn db_sa_trades(blk: sol::v1::Block) -> Result<DatabaseChanges, Error> {
 
             .change("market_fee", db_change_create(format!("{:}", exchange.market_fee).as_str()))
             .change("price", db_change_create(format!("{:}", exchange.price).as_str()))
-            .change("total_cost", db_change_create(format!("{:}", exchange.total_cost).as_str()));
+            .change("total_cost", db_change_create(format!("{:}", exchange.total_cost).as_str()))
+            .change("fee_payer", db_change_create(format!("{:}", exchange.fee_payer).as_str()))
+            .change("fee_rate", db_change_create(format!("{:}", exchange.fee_rate).as_str()))
+            .change("royalty", db_change_create(format!("{:}", exchange.royalty).as_str()))
+            .change("royalty_discount", db_change_create(format!("{:}", exchange.royalty_discount).as_str()));
     }
     return Ok(database_changes);
 }
@@ -129,93 +269,9 @@ fn sa_trades_db_out(_store: StoreGetProto<ProcessExchanges>) -> Result<DatabaseC
     return Ok(database_changes);
 }
 
-fn process_blocks(blk: Block, process_exchanges: &mut Vec<ProcessExchange>) -> Result<(), Error> {
-    for trx in blk.transactions {
-        if let Some(meta) = trx.clone().meta {
-            if let Some(_) = meta.err {
-                continue;
-            }
-            if let Some(transaction) = trx.clone().transaction {
-                if let Some(msg) = transaction.clone().message {
-                    for (inst_idx, inst) in msg.instructions.clone().into_iter().enumerate() {
-                        let program_id = &msg.account_keys[inst.program_id_index as usize];
-                        if bs58::encode(program_id).into_string() != "traderDnaR5w6Tcoi3NFm53i48FTDNbGjBSZwWXDRrg" {
-                            continue;
-                        }
-                        //Continue Parsing
-                        let sig = bs58::encode(transaction.signatures[0].as_slice()).into_string();
-                        log::info!("{}", sig.clone());
-
-                        let instruction = MarketplaceInstruction::unpack(&inst.data)?;
-                        match instruction {
-                            MarketplaceInstruction::ProcessExchange { purchase_quantity, expected_price } => {
-                                let order_taker = bs58::encode(&msg.account_keys[inst.accounts[0] as usize]).into_string();
-                                let currency_mint = bs58::encode(&msg.account_keys[inst.accounts[3] as usize]).into_string();
-                                let order_initializer = bs58::encode(&msg.account_keys[inst.accounts[5] as usize]).into_string();
-                                let asset_mint = bs58::encode(&msg.account_keys[inst.accounts[4] as usize]).into_string();
-
-
-                                log::info!("instruction none={:?}", trx.clone().meta.clone().unwrap().inner_instructions_none);
-                                trx.clone().meta.clone().unwrap().inner_instructions.clone().into_iter().for_each(|e| {
-                                    log::info!("instruction inner={:?}", e.index);
-                                });
-                                log::info!("instruction index={:?}", inst_idx);
-
-
-                                let mut asset_receiving_wallet_index = 0;
-                                if trx.meta.clone().unwrap().inner_instructions_none {
-                                    asset_receiving_wallet_index = 4
-                                } else {
-                                    asset_receiving_wallet_index =
-                                        find_asset_mint_in_inner_instruction_get_index(
-                                            meta.clone().inner_instructions.into_iter()
-                                                .find(|i| i.index == inst_idx as u32).unwrap()
-                                                .clone().instructions, inst.accounts[4]).unwrap();
-                                }
-
-                                log::info!("asset_receiving_wallet_index={:?}", asset_receiving_wallet_index);
-                                let asset_receiving_wallet = match asset_receiving_wallet_index {
-                                    1 => { bs58::encode(&msg.account_keys[inst.accounts[5] as usize]).into_string() }
-                                    2 => { bs58::encode(&msg.account_keys[inst.accounts[0] as usize]).into_string() }
-                                    3 => { bs58::encode(&msg.account_keys[inst.accounts[5] as usize]).into_string() }
-                                    4 => { bs58::encode(&msg.account_keys[inst.accounts[0] as usize]).into_string() }
-                                    _ => { bs58::encode(&msg.account_keys[inst.accounts[0] as usize]).into_string() }
-                                };
-
-
-                                let currency_change_abs =
-                                    calc_token_balance_change(&meta, currency_mint.clone(), bs58::encode(&msg.account_keys[inst.accounts[0] as usize]).into_string());
-                                let fees_change_abs = calc_token_balance_change(&meta, currency_mint.clone(), "feesQYAaH3wjGUUQYD959mmi5pY8HSz3F5C3SVc1fp3".to_string());
-
-                                let price = match expected_price {
-                                    None => { (BigDecimal::from_str(currency_change_abs.to_string().as_str()).unwrap() / BigDecimal::from_str(purchase_quantity.to_string().as_str()).unwrap()).to_string().parse::<f64>().unwrap() }
-                                    Some(value) => { calc_token_decimals(value, currency_mint.clone()) }
-                                };
-
-                                process_exchanges.push(ProcessExchange {
-                                    pk: format!("{}_{}_{}", sig.clone(), order_taker.clone(), order_initializer.clone()),
-                                    signature: sig,
-                                    block: blk.slot,
-                                    timestamp: blk.block_time.clone().unwrap_or_default().timestamp,
-                                    order_taker,
-                                    order_initializer,
-                                    currency_mint: currency_mint.clone(),
-                                    asset_mint,
-                                    asset_receiving_wallet,
-                                    currency_change: currency_change_abs.to_string(),
-                                    market_fee: fees_change_abs.to_string(),
-                                    asset_change: purchase_quantity.to_string(),
-                                    price: price.to_string(),
-                                    total_cost: (purchase_quantity as f64 * price).to_string(),
-
-                                })
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        }
+/// Adds the royalty registered for the currency of the exchange, see `sa_decoder::apply_royalty`.
+fn apply_royalty(exchange: &mut ProcessExchange, royalties: &StoreGetProto<CurrencyRoyalty>) {
+    if let Some(registered) = royalties.get_last(format!("royalty:{}", exchange.currency_mint)) {
+        sa_decoder::apply_royalty(exchange, registered.royalty);
     }
-    Ok(())
 }